@@ -0,0 +1,206 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::utils::db::env::Env;
+use crate::utils::db::project::Project;
+use crate::utils::db::{workspace, DB};
+use crate::utils::runner::data_file::DataFile;
+use crate::utils::runner::{self, CollectionRun, RunScope, RunnerOptions};
+
+pub mod report;
+pub mod send;
+
+use report::Reporter;
+use send::SendArgs;
+
+/// Exit code of a run with failed requests or tests.
+const EXIT_FAILED: i32 = 1;
+/// Exit code when the run could not start.
+const EXIT_ERROR: i32 = 2;
+
+/// Without a subcommand the desktop app is started.
+#[derive(Parser, Debug)]
+#[command(name = "falcon", version, about = "Falcon, an HTTP request client.")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Runs the requests of a project or folder with their assertions.
+    Run(RunArgs),
+    /// Sends a single request and prints the response.
+    Send(SendArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    /// Name of the project to run.
+    pub project: String,
+    /// Projects file, workspace directory, exported project or Hurl file, defaults to the
+    /// projects of the app.
+    #[arg(short, long)]
+    pub file: Option<PathBuf>,
+    /// Only runs the requests of this folder.
+    #[arg(long)]
+    pub folder: Option<String>,
+    /// Env to use, defaults to the default env of the project.
+    #[arg(short, long)]
+    pub env: Option<String>,
+    /// CSV or JSON data file, one iteration per row.
+    #[arg(short, long)]
+    pub data: Option<PathBuf>,
+    #[arg(short = 'n', long, default_value_t = 1)]
+    pub iterations: u32,
+    /// Delay between requests in milliseconds.
+    #[arg(long, default_value_t = 0)]
+    pub delay: u64,
+    /// Stops at the first failed request.
+    #[arg(long)]
+    pub bail: bool,
+    #[arg(short, long, value_enum, default_value_t = Reporter::Human)]
+    pub reporter: Reporter,
+    /// Writes the report to a file instead of stdout.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Runs the command and returns the exit code of the process.
+pub fn run(command: Command) -> i32 {
+    let result = match command {
+        Command::Run(args) => run_collection(args),
+        Command::Send(args) => send::send(args),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("{:<10}[FALCON]: (CLI) {}", "ERROR", err);
+            EXIT_ERROR
+        }
+    }
+}
+
+fn load_db(file: Option<&Path>) -> Result<DB, String> {
+    match file {
+        Some(path) => DB::load(path),
+        None => match workspace::current() {
+            Some(dir) => DB::load(&dir),
+            None => DB::load(Path::new(&DB::default_path())),
+        },
+    }
+}
+
+fn find_project<'a>(db: &'a DB, name: &str) -> Result<&'a Project, String> {
+    db.project_by_name(name).ok_or_else(|| {
+        let names: Vec<&str> = db.projects().iter().map(|p| p.name.as_str()).collect();
+        format!(
+            "Project \"{}\" not found, available: {}",
+            name,
+            names.join(", ")
+        )
+    })
+}
+
+/// The named env, or the default env of the project when no name is given.
+fn find_env(db: &DB, project: &Project, name: Option<&str>) -> Result<Option<Env>, String> {
+    match name {
+        Some(name) => db.env_by_name(name).cloned().map(Some).ok_or_else(|| {
+            let names: Vec<&str> = db.envs().iter().map(|env| env.name.as_str()).collect();
+            format!(
+                "Env \"{}\" not found, available: {}",
+                name,
+                names.join(", ")
+            )
+        }),
+        None => Ok(project.default_env.and_then(|id| db.env_by_id(id)).cloned()),
+    }
+}
+
+fn run_collection(args: RunArgs) -> Result<i32, String> {
+    let db = load_db(args.file.as_deref())?;
+    let project = find_project(&db, &args.project)?;
+    let env = find_env(&db, project, args.env.as_deref())?;
+
+    let scope = match args.folder {
+        Some(folder) => {
+            let folders = project.folders();
+            let found = folders
+                .iter()
+                .find(|name| name.eq_ignore_ascii_case(folder.trim()))
+                .ok_or(format!(
+                    "Folder \"{}\" not found, available: {}",
+                    folder,
+                    folders
+                        .iter()
+                        .map(|name| name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))?;
+            RunScope::Folder(found.to_string())
+        }
+        None => RunScope::Project,
+    };
+
+    let data = args.data.as_deref().map(DataFile::load).transpose()?;
+    let options = RunnerOptions {
+        scope,
+        delay_ms: args.delay,
+        iterations: args.iterations.max(1),
+        stop_on_failure: args.bail,
+    };
+
+    let base_url = env
+        .as_ref()
+        .and_then(|env| env.base_url.clone())
+        .unwrap_or_default();
+    let mut run = CollectionRun::new(project, env, base_url, options, data.as_ref());
+
+    let runtime = tokio::runtime::Runtime::new().map_err(|err| err.to_string())?;
+    // the human report is streamed, the others are written once the run is over
+    let stream = args.reporter == Reporter::Human && args.output.is_none();
+
+    loop {
+        let delay = run.delay();
+        let Some(step) = run.next_step() else {
+            break;
+        };
+
+        let (result, env, output) = runtime.block_on(runner::run_step(
+            step,
+            run.env.clone(),
+            run.base_url.clone(),
+            run.scripts.clone(),
+            delay,
+        ));
+
+        for line in output.console.iter() {
+            eprintln!("{}", line);
+        }
+        if stream {
+            print!("{}", report::human_result(&result));
+        }
+
+        run.record(result, env);
+    }
+
+    let run_report = run.report();
+    let rendered = match (args.reporter, stream) {
+        (Reporter::Human, true) => format!("\n{}\n", run_report.summary),
+        (reporter, _) => reporter.render(&run_report)?,
+    };
+
+    match args.output {
+        Some(path) => fs::write(&path, rendered)
+            .map_err(|err| format!("Failed to save file, cause {}", err))?,
+        None => print!("{}", rendered),
+    }
+
+    Ok(match run_report.summary.failed {
+        0 => 0,
+        _ => EXIT_FAILED,
+    })
+}
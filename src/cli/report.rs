@@ -0,0 +1,218 @@
+use std::fmt::Write;
+
+use clap::ValueEnum;
+
+use crate::utils::runner::{RunReport, RunResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Reporter {
+    Human,
+    Json,
+    Junit,
+}
+
+impl Reporter {
+    pub fn render(&self, report: &RunReport) -> Result<String, String> {
+        match self {
+            Reporter::Human => Ok(human(report)),
+            Reporter::Json => report.to_json().map(|json| json + "\n"),
+            Reporter::Junit => Ok(junit(report)),
+        }
+    }
+}
+
+/// One line per request, followed by its failures.
+pub fn human_result(result: &RunResult) -> String {
+    let mut out = format!(
+        "{} {} {} {} {}\n",
+        if result.passed() { "PASS" } else { "FAIL" },
+        result.method,
+        result.name,
+        result
+            .status_code
+            .map(|code| code.to_string())
+            .unwrap_or("ERR".into()),
+        result
+            .duration_ms
+            .map(|ms| format!("{} ms", ms))
+            .unwrap_or_default(),
+    );
+
+    if let Some(error) = &result.error {
+        let _ = writeln!(out, "    {}", error);
+    }
+    for test in result.tests.iter().filter(|test| !test.passed) {
+        let _ = writeln!(out, "    {}: {}", test.name, test.message);
+    }
+
+    out
+}
+
+fn human(report: &RunReport) -> String {
+    let mut out = String::new();
+
+    for result in report.results.iter() {
+        out.push_str(&human_result(result));
+    }
+    let _ = writeln!(out, "\n{}", report.summary);
+
+    out
+}
+
+/// One test suite per folder and one test case per request sent.
+fn junit(report: &RunReport) -> String {
+    let mut folders: Vec<&str> = vec![];
+    for result in report.results.iter() {
+        if !folders.contains(&result.folder.as_str()) {
+            folders.push(&result.folder);
+        }
+    }
+    let iterations = report
+        .results
+        .iter()
+        .map(|r| r.iteration)
+        .max()
+        .unwrap_or(1);
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{}\">",
+        escape(&report.project),
+        report.summary.total,
+        report.summary.failed,
+        seconds(report.summary.duration_ms),
+    );
+
+    for folder in folders {
+        let results: Vec<&RunResult> = report
+            .results
+            .iter()
+            .filter(|result| result.folder == folder)
+            .collect();
+        let failures = results.iter().filter(|result| !result.passed()).count();
+        let time: u64 = results.iter().filter_map(|result| result.duration_ms).sum();
+
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{}\">",
+            escape(folder),
+            results.len(),
+            failures,
+            seconds(time),
+        );
+
+        for result in results {
+            let name = match iterations {
+                1 => result.name.clone(),
+                _ => format!("{} (iteration {})", result.name, result.iteration),
+            };
+            let _ = write!(
+                out,
+                "    <testcase name=\"{}\" classname=\"{}.{}\" time=\"{}\"",
+                escape(&name),
+                escape(&report.project),
+                escape(folder),
+                seconds(result.duration_ms.unwrap_or_default()),
+            );
+
+            if result.passed() {
+                out.push_str("/>\n");
+                continue;
+            }
+
+            let mut failures: Vec<String> = result.error.iter().cloned().collect();
+            failures.extend(
+                result
+                    .tests
+                    .iter()
+                    .filter(|test| !test.passed)
+                    .map(|test| format!("{}: {}", test.name, test.message)),
+            );
+
+            let _ = writeln!(
+                out,
+                ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                escape(failures.first().map(String::as_str).unwrap_or_default()),
+                escape(&failures.join("\n")),
+            );
+        }
+
+        out.push_str("  </testsuite>\n");
+    }
+
+    out.push_str("</testsuites>\n");
+    out
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::utils::request::assertion::AssertionResult;
+    use crate::utils::runner::{RunSummary, RunnerOptions};
+
+    fn result(folder: &str, name: &str, test: Result<(), String>) -> RunResult {
+        RunResult {
+            iteration: 1,
+            folder: folder.into(),
+            request_id: Uuid::now_v7(),
+            name: name.into(),
+            method: "GET".into(),
+            url: "https://example.com".into(),
+            status_code: Some(200),
+            duration_ms: Some(1500),
+            error: None,
+            tests: vec![AssertionResult::new("status", test)],
+            data: vec![],
+        }
+    }
+
+    #[test]
+    fn test_junit_groups_by_folder() {
+        let report = RunReport {
+            project: "Shop & Co".into(),
+            env: None,
+            data_file: None,
+            options: RunnerOptions::default(),
+            started_at: Utc::now(),
+            finished_at: None,
+            summary: RunSummary {
+                total: 2,
+                passed: 1,
+                failed: 1,
+                ..Default::default()
+            },
+            results: vec![
+                result("root", "login", Ok(())),
+                result("users", "list <all>", Err("expected 200, got 500".into())),
+            ],
+        };
+
+        let xml = junit(&report);
+
+        assert!(xml.contains("<testsuites name=\"Shop &amp; Co\" tests=\"2\" failures=\"1\""));
+        assert!(xml.contains("<testsuite name=\"root\" tests=\"1\" failures=\"0\" time=\"1.500\">"));
+        assert!(xml.contains(
+            "<testcase name=\"login\" classname=\"Shop &amp; Co.root\" time=\"1.500\"/>"
+        ));
+        assert!(xml.contains("<testcase name=\"list &lt;all&gt;\""));
+        assert!(xml.contains("<failure message=\"status: expected 200, got 500\">"));
+        assert!(xml.ends_with("</testsuites>\n"));
+    }
+}
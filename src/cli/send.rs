@@ -0,0 +1,163 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::utils::request::json_body;
+use crate::utils::request::FalconResponse;
+use crate::utils::script::{self, ScriptOutput};
+
+use super::{find_env, find_project, load_db, EXIT_FAILED};
+
+#[derive(Args, Debug)]
+pub struct SendArgs {
+    /// The request to send, as `PROJECT/REQUEST` or `PROJECT/FOLDER/REQUEST`.
+    pub request: String,
+    /// Projects file, workspace directory or exported project, defaults to the projects of the app.
+    #[arg(short, long)]
+    pub file: Option<PathBuf>,
+    /// Env to use, defaults to the default env of the project.
+    #[arg(short, long)]
+    pub env: Option<String>,
+    /// Sets or overrides an env variable, can be repeated.
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+    pub vars: Vec<(String, String)>,
+    /// Pretty prints JSON bodies.
+    #[arg(short, long)]
+    pub pretty: bool,
+    /// Only prints the body.
+    #[arg(short, long)]
+    pub body_only: bool,
+    /// Exits with an error on 4xx and 5xx responses.
+    #[arg(long)]
+    pub fail: bool,
+}
+
+fn parse_var(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got \"{}\"", input)),
+    }
+}
+
+pub fn send(args: SendArgs) -> Result<i32, String> {
+    let (project_name, request_path) = args
+        .request
+        .split_once('/')
+        .ok_or("The request has to be given as PROJECT/REQUEST".to_string())?;
+
+    let db = load_db(args.file.as_deref())?;
+    let project = find_project(&db, project_name)?;
+    let (_, pending) = project.find_request(request_path).ok_or(format!(
+        "Request \"{}\" not found in {}",
+        request_path, project.name
+    ))?;
+
+    let mut env = find_env(&db, project, args.env.as_deref())?.unwrap_or_default();
+    for (key, value) in args.vars {
+        env.set_variable(key, value);
+    }
+    let base_url = env.base_url.clone().unwrap_or_default();
+
+    let mut output = ScriptOutput::default();
+    let prepared = script::prepare_request(
+        pending,
+        &[&project.pre_request_script, &pending.pre_request_script],
+        &mut env,
+        &base_url,
+        &mut output,
+    );
+    for line in output.console.iter() {
+        eprintln!("{}", line);
+    }
+
+    let runtime = tokio::runtime::Runtime::new().map_err(|err| err.to_string())?;
+    let response = runtime
+        .block_on(prepared?.send())
+        .map_err(|err| format!("Request failed, {}", err))?;
+
+    print!(
+        "{}",
+        format_response(&response, args.pretty, args.body_only)
+    );
+
+    Ok(match args.fail && !response.status_code.is_success() {
+        true => EXIT_FAILED,
+        false => 0,
+    })
+}
+
+/// The status line and headers, then the body. Bodies that aren't JSON are never reformatted.
+fn format_response(response: &FalconResponse, pretty: bool, body_only: bool) -> String {
+    let mut out = String::new();
+
+    if !body_only {
+        let _ = writeln!(out, "{}", response.status_code);
+        for (name, value) in response.headers.iter() {
+            let _ = writeln!(
+                out,
+                "{}: {}",
+                name,
+                String::from_utf8_lossy(value.as_bytes())
+            );
+        }
+        out.push('\n');
+    }
+
+    let body = match pretty {
+        true => json_body::prettify(&response.body).unwrap_or(response.body.clone()),
+        false => response.body.clone(),
+    };
+    out.push_str(&body);
+    if !body.ends_with('\n') {
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::StatusCode;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_var() {
+        assert_eq!(
+            parse_var("TOKEN=a=b"),
+            Ok(("TOKEN".to_string(), "a=b".to_string()))
+        );
+        assert!(parse_var("=value").is_err());
+        assert!(parse_var("TOKEN").is_err());
+    }
+
+    #[test]
+    fn test_format_response() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        let response = FalconResponse {
+            status_code: StatusCode::CREATED,
+            body: r#"{"id":1}"#.into(),
+            headers,
+            cookies: vec![],
+            duration: Duration::from_millis(5).into(),
+            size_kb: 1.0,
+        };
+
+        assert_eq!(
+            format_response(&response, false, false),
+            "201 Created\ncontent-type: application/json\n\n{\"id\":1}\n"
+        );
+        assert_eq!(
+            format_response(&response, true, true),
+            "{\n  \"id\": 1\n}\n"
+        );
+    }
+}
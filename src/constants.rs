@@ -25,6 +25,8 @@ pub const ADD_DOC_SVG: &[u8] = include_bytes!("../assets/add-document.svg");
 pub const COG_API_SVG: &[u8] = include_bytes!("../assets/api.svg");
 pub const ANGLE_LEFT_SVG: &[u8] = include_bytes!("../assets/angle-left.svg");
 pub const FILE_CIRCLE_INFO_SVG: &[u8] = include_bytes!("../assets/file-circle-info.svg");
+pub const TIME_PAST_SVG: &[u8] = include_bytes!("../assets/time-past.svg");
+pub const PLAY_SVG: &[u8] = include_bytes!("../assets/play.svg");
 
 // fonts
 pub const ROBOTO_FONT: &[u8] = include_bytes!("../assets/Roboto-Regular.ttf");
\ No newline at end of file
@@ -1,16 +1,22 @@
 use std::borrow::Cow;
 
+use clap::Parser;
+use cli::Cli;
 use constants::ROBOTO_FONT;
 use iced::{window, Application, Font, Settings, Size};
 
+mod cli;
 mod constants;
 mod ui;
 mod utils;
 
 use ui::app_state::AppState;
 
-#[tokio::main]
-pub async fn main() -> iced::Result {
+pub fn main() -> iced::Result {
+    if let Some(command) = Cli::parse().command {
+        std::process::exit(cli::run(command));
+    }
+
     println!(
         "{:<10}[FALCON]: (MAIN) Starting Falcon, an HTTP request client.",
         "INFO"
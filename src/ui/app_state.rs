@@ -1,4 +1,4 @@
-use iced::{window, Application, Command, Element, Theme};
+use iced::{window, Application, Command, Element, Subscription, Theme};
 
 use super::{
     // app_component::AppComponent,
@@ -84,4 +84,8 @@ impl Application for AppState {
     fn view(&self) -> Element<Self::Message> {
         self.view()
     }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        self.home_state.subscription().map(MessageBus::HomeMessage)
+    }
 }
@@ -1,48 +1,93 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread::sleep;
 use std::time::{Duration, Instant};
 
 use env_tabs_block::env_tabs_block;
 use events::{EnvEvent, ProjectEvent, RequestEvent};
+use file_conflict_block::file_conflict_block;
 use iced::widget::text_editor::Action;
 use iced::widget::{column, container, mouse_area, row, text, text_editor, Row, Space};
-use iced::{clipboard, Application, Command, Element, Length, Theme};
+use iced::futures::{future, SinkExt};
+use iced::{
+    clipboard, subscription, Application, Command, Element, Length, Subscription, Theme,
+};
 use project_tabs_block::project_tabs_block;
+use recovery_block::recovery_block;
 use request_and_response_card::request_and_response_card;
+use request_log_block::request_log_block;
+use runner_block::runner_block;
+use scripts_block::ScriptEditors;
 use sidebar_envs::get_env_items;
+use sidebar_history::sidebar_history;
 use sidebar_projects::get_sidebar_projects_items;
 use sidebar_requests::sidebar_requests;
 use tob_bar::tob_bar;
+use uuid::Uuid;
 
 // use crate::ui::app_component::AppComponent;
 use crate::ui::app_theme::AppContainer;
 use crate::ui::elements::tabs::TabNode;
 use crate::ui::elements::tabs::Tabs;
 use crate::ui::message_bus::Route;
+use crate::ui::tokenizer::ResponseSyntax;
+use crate::utils::codegen::CodeLanguage;
+use crate::utils::db::request_log::{LogRetention, RequestLog, RequestLogEntry};
+use crate::utils::db::response_history::{ResponseHistory, ResponseRecord};
+use crate::utils::db::env::Env;
+use crate::utils::db::merge;
+use crate::utils::db::watcher::FileWatcher;
 use crate::utils::db::DB;
 use crate::utils::helpers::page_title;
-use crate::utils::request::{FalconResponse, FlBody, PendingRequest};
+use crate::utils::http_file::{self, HttpProject};
+use crate::utils::import::{self, curl, har, ExportFormat, ImportReport, Imported};
+use crate::utils::load_test::{self, LoadLimit, LoadLimitKind, LoadReport, LoadTestOptions};
+use crate::utils::request::assertion::{evaluate_all, AssertionResult};
+use crate::utils::request::extraction::{self, ExtractionResult};
+use crate::utils::request::{
+    json_body, FalconAuthorization, FalconResponse, FlBody, PendingRequest, RequestUrl,
+    ResolvedRequest,
+};
+use crate::utils::runner::data_file::DataFile;
+use crate::utils::runner::{self, CollectionRun, RunResult, RunScope, RunnerOptions};
+use crate::utils::script::{self, ConsoleLine, ScriptOutput, ScriptStage};
 
+mod code_block;
 mod env_tabs_block;
 mod events;
+mod file_conflict_block;
+mod history_block;
 mod http_badge_column;
 mod key_and_value_input_row;
+mod load_test_block;
 mod project_tabs_block;
+mod recovery_block;
 mod request_and_response_card;
+mod request_log_block;
 mod request_tabs_block;
 mod response_tabs_block;
+mod runner_block;
+mod scripts_block;
 mod sidebar_envs;
+mod sidebar_history;
 mod sidebar_item;
 mod sidebar_projects;
 mod sidebar_requests;
 mod tob_bar;
 mod url_input_bar;
 
+/// Oldest script console lines are dropped past this.
+const MAX_CONSOLE_LINES: usize = 500;
+
 #[derive(Default, Debug, Clone)]
 pub enum HomePageState {
     #[default]
     Requests,
     Projects,
     Envs,
+    History,
+    Runner,
 }
 
 pub struct HomePage {
@@ -57,20 +102,79 @@ pub struct HomePage {
     request_body_context: text_editor::Content,
     scheduled_sync_at: Instant,
     show_env_examples: bool,
+    response_syntax: ResponseSyntax,
+    history: ResponseHistory,
+    history_compare: Vec<Uuid>,
+    test_results: Vec<AssertionResult>,
+    request_log: RequestLog,
+    log_query: String,
+    selected_log_entry: Option<Uuid>,
+    log_retention: LogRetention,
+    log_notice: Option<String>,
+    request_scripts: ScriptEditors,
+    project_scripts: ScriptEditors,
+    script_console: Vec<ConsoleLine>,
+    extraction_results: Vec<ExtractionResult>,
+    runner_options: RunnerOptions,
+    runner_delay: String,
+    runner_iterations: String,
+    runner_notice: Option<String>,
+    runner_data: Option<DataFile>,
+    collection_run: Option<CollectionRun>,
+    code_language: CodeLanguage,
+    code_keep_variables: bool,
+    load_concurrency: String,
+    load_limit_kind: LoadLimitKind,
+    load_limit: String,
+    load_rps: String,
+    /// Set while a load test runs, raising it stops the workers.
+    load_stop: Option<Arc<AtomicBool>>,
+    load_report: Option<Result<LoadReport, String>>,
+    import_report: Option<Result<ImportReport, String>>,
+    /// Responses recorded in the last imported file, until kept or dismissed.
+    imported_responses: Vec<(Uuid, ResponseRecord)>,
+    project_notice: Option<String>,
+    /// Projects opened from `.http` files, written back on every sync.
+    http_projects: Vec<HttpProject>,
+    /// The DB as last read from or written to its files, changes made outside of the app
+    /// are merged against it.
+    saved_db: DB,
+    /// Set while the files changed outside of the app conflict with edits of the app,
+    /// nothing is saved until it is resolved.
+    file_conflict: Option<FileConflict>,
+}
+
+#[derive(Debug)]
+struct FileConflict {
+    base: DB,
+    files: DB,
+    conflicts: Vec<String>,
 }
 
 impl Default for HomePage {
     fn default() -> Self {
-        let db = DB::new();
+        let mut db = DB::new();
+        let http_projects = reopen_http_projects(&mut db);
+        let history = ResponseHistory::new();
+        let request_log = RequestLog::new();
+        let log_notice = request_log
+            .load_error()
+            .or(history.load_error())
+            .map(String::from);
 
-        let req_body = if let Some(proj) = db.active() {
-            if let Some((_, req)) = proj.current_request() {
-                req.body.to_string()
-            } else {
-                "".to_string()
-            }
-        } else {
-            "".to_string()
+        let current = db
+            .active()
+            .and_then(|proj| proj.current_request().map(|(_, req)| req.clone()));
+        let (req_body, request_scripts) = match current {
+            Some(req) => (
+                req.body.to_string(),
+                ScriptEditors::new(&req.pre_request_script, &req.post_response_script),
+            ),
+            None => ("".to_string(), ScriptEditors::new("", "")),
+        };
+        let project_scripts = match db.active() {
+            Some(proj) => ScriptEditors::new(&proj.pre_request_script, &proj.post_response_script),
+            None => ScriptEditors::new("", ""),
         };
 
         Self {
@@ -78,20 +182,114 @@ impl Default for HomePage {
             sidebar_closed: Default::default(),
             state: Default::default(),
             request_tabs: Tabs::new(
-                vec!["Query", "Header", "Body", "Authorization", "Cookies"],
+                vec![
+                    "Query",
+                    "Header",
+                    "Body",
+                    "Authorization",
+                    "Cookies",
+                    "Tests",
+                    "Extract",
+                    "Scripts",
+                    "Code",
+                    "Load",
+                    "History",
+                ],
                 "Query",
             ),
-            response_tabs: Tabs::new(vec!["Header", "Body", "Cookies"], "Body"),
+            response_tabs: Tabs::new(vec!["Header", "Body", "Cookies", "Tests"], "Body"),
+            saved_db: db.clone(),
+            file_conflict: None,
             db,
             is_requesting: false,
             response: None,
             request_body_context: text_editor::Content::with_text(&req_body),
             scheduled_sync_at: Instant::now(),
             show_env_examples: false,
+            response_syntax: ResponseSyntax::default(),
+            history,
+            history_compare: vec![],
+            test_results: vec![],
+            request_log,
+            log_query: String::new(),
+            selected_log_entry: None,
+            log_retention: LogRetention::default(),
+            log_notice,
+            request_scripts,
+            project_scripts,
+            script_console: vec![],
+            extraction_results: vec![],
+            runner_options: RunnerOptions::default(),
+            runner_delay: String::new(),
+            runner_iterations: String::new(),
+            runner_notice: None,
+            runner_data: None,
+            collection_run: None,
+            code_language: CodeLanguage::default(),
+            code_keep_variables: false,
+            load_concurrency: String::new(),
+            load_limit_kind: LoadLimitKind::default(),
+            load_limit: String::new(),
+            load_rps: String::new(),
+            load_stop: None,
+            load_report: None,
+            import_report: None,
+            imported_responses: vec![],
+            project_notice: None,
+            http_projects,
         }
     }
 }
 
+/// Reopens the projects of the DB opened from `.http` files, reading their edits.
+fn reopen_http_projects(db: &mut DB) -> Vec<HttpProject> {
+    let http_dirs: Vec<PathBuf> = db
+        .projects()
+        .iter()
+        .filter_map(|project| project.http_dir.clone())
+        .collect();
+
+    http_dirs
+        .iter()
+        .filter_map(|dir| match http_file::open_in(db, dir) {
+            Ok(http) => Some(http),
+            Err(err) => {
+                println!("{:<10}[FALCON]: (HTTP) Failed to reopen, {}", "ERROR", err);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Sends the message every time the watched files change.
+fn watch_files(
+    path: PathBuf,
+    extensions: &'static [&'static str],
+    message: HomeEventMessage,
+) -> Subscription<HomeEventMessage> {
+    subscription::channel((path.clone(), extensions), 1, move |mut output| async move {
+        match FileWatcher::new(&path, extensions) {
+            Ok(mut watcher) => {
+                while watcher.changed().await.is_some() {
+                    let _ = output.send(message.clone()).await;
+                }
+            }
+            Err(err) => println!("{:<10}[FALCON]: (DB) {}", "ERROR", err),
+        }
+
+        future::pending().await
+    })
+}
+
+/// A finished send, `source` is `None` for re-runs from the history log.
+#[derive(Debug, Clone)]
+pub struct SentRequest {
+    source: Option<PendingRequest>,
+    env_name: Option<String>,
+    request: ResolvedRequest,
+    response: Result<FalconResponse, String>,
+}
+
 #[derive(Debug, Clone)]
 pub enum HomeEventMessage {
     NavigateTo(Route),
@@ -101,19 +299,86 @@ pub enum HomeEventMessage {
     OnResponseTabChange(TabNode),
     OnBodyTabChange(TabNode),
     OnAuthorizationTabChange(TabNode),
+    OnResponseSyntaxChange(ResponseSyntax),
     MinimizeRequestTabs,
 
     // request process events
     SendRequest,
-    RequestFinished(FalconResponse),
+    RequestFinished(Box<SentRequest>),
     RequestErr(String),
 
     // state events
     ToggleSidebar,
     OnRequestBodyContextAction(Action),
+    PrettifyRequestBody,
+    MinifyRequestBody,
+    OnScriptAction(ScriptStage, Action),
+    OnProjectScriptAction(ScriptStage, Action),
+    ClearScriptConsole,
     OnChangePageState(HomePageState),
     ToggleEnvExample,
 
+    // history events
+    OpenHistoryEntry(Uuid),
+    ToggleHistoryCompare(Uuid),
+    ClearHistory,
+    RequestLogSearchInput(String),
+    SelectRequestLogEntry(Uuid),
+    RerunRequestLogEntry(Uuid),
+    OpenRequestLogEntry(Uuid),
+    RequestLogRetentionChange(LogRetention),
+    ClearRequestLog,
+    ExportRequestLog,
+    RequestLogExported(Result<Option<String>, String>),
+
+    // runner events
+    RunnerScopeSelect(RunScope),
+    RunnerDelayInput(String),
+    RunnerIterationsInput(String),
+    ToggleRunnerStopOnFailure(bool),
+    PickRunnerDataFile,
+    RunnerDataFileLoaded(Result<Option<DataFile>, String>),
+    ClearRunnerDataFile,
+    StartRun,
+    StopRun,
+    RunStepFinished(Box<(RunResult, Env, ScriptOutput)>),
+    ExportRunReport,
+    RunReportExported(Result<Option<String>, String>),
+
+    // code events
+    CodeLanguageSelect(CodeLanguage),
+    ToggleCodeVariables(bool),
+
+    // load test events
+    LoadConcurrencyInput(String),
+    LoadLimitKindSelect(LoadLimitKind),
+    LoadLimitInput(String),
+    LoadRpsInput(String),
+    StartLoadTest,
+    StopLoadTest,
+    LoadTestFinished(Box<Result<LoadReport, String>>),
+
+    // import events
+    ImportFile,
+    ImportCurl,
+    CurlPasted(Option<String>),
+    ImportFinished(Box<Result<Option<Imported>, String>>),
+    OpenHttpFolder,
+    HttpFolderPicked(Option<PathBuf>),
+    OpenWorkspace,
+    WorkspacePicked(Option<PathBuf>),
+    CloseWorkspace,
+    FilesChanged,
+    HttpFilesChanged(PathBuf),
+    KeepMyChanges,
+    UseFileChanges,
+    RestoreBackup(PathBuf),
+    DiscardUnreadableProjects,
+    KeepImportedResponses,
+    DismissImportReport,
+    ExportProject(ExportFormat),
+    ProjectExported(Result<Option<String>, String>),
+
     // DB events
     SyncProjects,
     SyncedDone,
@@ -152,9 +417,30 @@ impl HomePage {
         )
     }
 
-    fn perform_sync(&self) -> Command<HomeEventMessage> {
+    /// Writes the edits of projects opened from `.http` files, debounced like the DB sync.
+    /// Nothing is written while a conflict with the DB files is shown.
+    fn write_http_projects(&mut self) {
+        if self.file_conflict.is_some()
+            || Instant::now().duration_since(self.scheduled_sync_at) <= Duration::from_millis(500)
+        {
+            return;
+        }
+
+        for http in self.http_projects.iter_mut() {
+            if let Err(err) = http.write(&self.db) {
+                println!("{:<10}[FALCON]: (HTTP) Failed to write, {}", "ERROR", err);
+            }
+        }
+    }
+
+    fn perform_sync(&mut self) -> Command<HomeEventMessage> {
+        if self.file_conflict.is_some() {
+            return Command::none();
+        }
+
         if Instant::now().duration_since(self.scheduled_sync_at) > Duration::from_millis(500) {
             let db = self.db.clone();
+            self.saved_db = db.clone();
 
             return Command::perform(
                 async move {
@@ -173,13 +459,241 @@ impl HomePage {
         Command::none()
     }
 
+    fn sync_history(&self) -> Command<HomeEventMessage> {
+        let history = self.history.clone();
+        let request_log = self.request_log.clone();
+
+        Command::perform(
+            async move {
+                if let Err(err) = history.sync() {
+                    println!("{:<10}[FALCON]: (HISTORY) Failed to sync, {:?}", "ERROR", err)
+                }
+                if let Err(err) = request_log.sync() {
+                    println!("{:<10}[FALCON]: (HISTORY) Failed to sync log, {:?}", "ERROR", err)
+                }
+                HomeEventMessage::SyncedDone
+            },
+            |msg| msg,
+        )
+    }
+
+    fn send_resolved(
+        &mut self,
+        source: Option<PendingRequest>,
+        request: ResolvedRequest,
+    ) -> Command<HomeEventMessage> {
+        self.is_requesting = true;
+        self.response = None;
+        let env_name = self.db.active_env().map(|env| env.name);
+
+        Command::perform(
+            async move {
+                let response = request.send().await.map_err(|err| err.to_string());
+
+                SentRequest {
+                    source,
+                    env_name,
+                    request,
+                    response,
+                }
+            },
+            |sent| HomeEventMessage::RequestFinished(Box::new(sent)),
+        )
+    }
+
+    /// Sends the next request of the collection run, if any.
+    fn next_run_step(&mut self) -> Command<HomeEventMessage> {
+        let Some(run) = self.collection_run.as_mut() else {
+            return Command::none();
+        };
+        let delay = run.delay();
+
+        match run.next_step() {
+            Some(step) => Command::perform(
+                runner::run_step(
+                    step,
+                    run.env.clone(),
+                    run.base_url.clone(),
+                    run.scripts.clone(),
+                    delay,
+                ),
+                |finished| HomeEventMessage::RunStepFinished(Box::new(finished)),
+            ),
+            None => Command::none(),
+        }
+    }
+
+    /// Options of the load test, empty inputs fall back to the defaults.
+    fn load_test_options(&self) -> Result<LoadTestOptions, String> {
+        let defaults = LoadTestOptions::default();
+        let number = |input: &str, name: &str| -> Result<Option<f64>, String> {
+            match input.trim() {
+                "" => Ok(None),
+                input => match input.parse::<f64>() {
+                    Ok(value) if value > 0.0 => Ok(Some(value)),
+                    _ => Err(format!("{} has to be a positive number", name)),
+                },
+            }
+        };
+
+        let concurrency = number(&self.load_concurrency, "Concurrency")?
+            .map(|value| value as usize)
+            .unwrap_or(defaults.concurrency);
+        let limit = match self.load_limit_kind {
+            LoadLimitKind::Count => number(&self.load_limit, "Total requests")?
+                .map(|value| LoadLimit::Count(value as u64))
+                .unwrap_or(defaults.limit),
+            LoadLimitKind::Duration => LoadLimit::Duration(Duration::from_secs_f64(
+                number(&self.load_limit, "Duration")?.unwrap_or(10.0),
+            )),
+        };
+
+        Ok(LoadTestOptions {
+            concurrency: concurrency.max(1),
+            limit,
+            target_rps: number(&self.load_rps, "Target RPS")?,
+        })
+    }
+
+    /// Merges changes made to the files outside of the app, unsaved edits of the same
+    /// items are a conflict for the user to resolve.
+    fn reload_files(&mut self) -> Option<Command<HomeEventMessage>> {
+        let files = match self.db.reload() {
+            Ok(Some(files)) => files,
+            Ok(None) => return None,
+            Err(err) => {
+                println!("{:<10}[FALCON]: (DB) Failed to reload, {}", "ERROR", err);
+                self.project_notice = Some(format!("Files changed but could not be read: {}", err));
+                return None;
+            }
+        };
+        // saves of the app are not reported, the files may still be edited back to them
+        if merge::same(&files, &self.saved_db) {
+            return None;
+        }
+
+        let base = match self.file_conflict.take() {
+            Some(conflict) => conflict.base,
+            None => self.saved_db.clone(),
+        };
+        let merged = merge::merge(&base, &self.db, &files);
+        if !merged.conflicts.is_empty() {
+            self.file_conflict = Some(FileConflict {
+                base,
+                files,
+                conflicts: merged.conflicts,
+            });
+            return None;
+        }
+
+        println!("{:<10}[FALCON]: (DB) Reloaded changed files", "INFO");
+        self.saved_db = files;
+        self.apply_merged(merged.mine)
+    }
+
+    /// Parses a project opened from `.http` files again once they changed outside of the
+    /// app, the files win over edits of the project not written to them yet.
+    fn reload_http_project(&mut self, dir: &Path) -> Option<Command<HomeEventMessage>> {
+        match http_file::open_in(&mut self.db, dir) {
+            Ok(http) => {
+                println!("{:<10}[FALCON]: (HTTP) Reloaded {}", "INFO", dir.display());
+                self.http_projects.retain(|opened| opened.dir != dir);
+                self.http_projects.push(http);
+                self.update_request_body();
+                self.update_project_scripts();
+
+                Some(self.schedule_sync())
+            }
+            Err(err) => {
+                println!("{:<10}[FALCON]: (HTTP) Failed to reload, {}", "ERROR", err);
+                self.project_notice = Some(format!(
+                    "{} changed but could not be read: {}",
+                    dir.display(),
+                    err
+                ));
+                None
+            }
+        }
+    }
+
+    fn resolve_file_conflict(&mut self, keep_mine: bool) -> Option<Command<HomeEventMessage>> {
+        let conflict = self.file_conflict.take()?;
+        // merged again, the app may have been edited while the conflict was shown
+        let merged = merge::merge(&conflict.base, &self.db, &conflict.files);
+        self.saved_db = conflict.files;
+
+        self.apply_merged(match keep_mine {
+            true => merged.mine,
+            false => merged.theirs,
+        })
+    }
+
+    /// Replaces the DB with a merge, edits of the app kept in it are saved.
+    fn apply_merged(&mut self, db: DB) -> Option<Command<HomeEventMessage>> {
+        let unsaved = !merge::same(&db, &self.saved_db);
+        self.db = db;
+        self.update_request_body();
+        self.update_project_scripts();
+
+        unsaved.then(|| self.schedule_sync())
+    }
+
+    /// Replaces the projects and envs after a workspace was opened or closed.
+    fn switch_db(&mut self, db: Result<DB, String>) -> Option<Command<HomeEventMessage>> {
+        match db {
+            Ok(mut db) => {
+                self.http_projects = reopen_http_projects(&mut db);
+                self.saved_db = db.clone();
+                self.file_conflict = None;
+                self.db = db;
+                self.project_notice = None;
+                self.update_request_body();
+                self.update_project_scripts();
+            }
+            Err(err) => {
+                println!("{:<10}[FALCON]: (DB) Failed to switch workspace, {}", "ERROR", err);
+                self.project_notice = Some(err);
+            }
+        }
+        self.state = HomePageState::Projects;
+
+        None
+    }
+
     fn update_request_body(&mut self) {
         if let Some(proj) = self.db.active() {
             if let Some((_, req)) = proj.current_request() {
                 self.request_body_context = text_editor::Content::with_text(&req.body.to_string());
+                self.request_scripts =
+                    ScriptEditors::new(&req.pre_request_script, &req.post_response_script);
             }
         }
     }
+
+    fn update_project_scripts(&mut self) {
+        self.project_scripts = match self.db.active() {
+            Some(proj) => ScriptEditors::new(&proj.pre_request_script, &proj.post_response_script),
+            None => ScriptEditors::new("", ""),
+        };
+    }
+
+    /// Keeps the script console lines and stores the env variables changed after a send.
+    fn apply_script_output(&mut self, env: Env, output: ScriptOutput) -> Command<HomeEventMessage> {
+        self.script_console.extend(output.console);
+        if self.script_console.len() > MAX_CONSOLE_LINES {
+            let overflow = self.script_console.len() - MAX_CONSOLE_LINES;
+            self.script_console.drain(..overflow);
+        }
+        self.test_results.extend(output.tests);
+
+        match self.db.active_env_mut() {
+            Some(active) if active.items != env.items => {
+                active.items = env.items;
+                self.schedule_sync()
+            }
+            _ => Command::none(),
+        }
+    }
 }
 
 // impl AppComponent for HomePage {
@@ -213,6 +727,23 @@ impl Application for HomePage {
         page_title("Home")
     }
 
+    /// Reloads the files of the DB and of the projects opened from `.http` files when
+    /// they change outside of the app. An unreadable projects file is not watched, it is
+    /// restored from the recovery block.
+    fn subscription(&self) -> Subscription<Self::Message> {
+        if self.db.load_error().is_some() {
+            return Subscription::none();
+        }
+
+        let db_files = watch_files(self.db.files(), &["toml"], HomeEventMessage::FilesChanged);
+        let http_files = self.http_projects.iter().map(|http| {
+            let message = HomeEventMessage::HttpFilesChanged(http.dir.clone());
+            watch_files(http.dir.clone(), &http_file::EXTENSIONS, message)
+        });
+
+        Subscription::batch(std::iter::once(db_files).chain(http_files))
+    }
+
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
         match message {
             HomeEventMessage::IntApp => None,
@@ -229,41 +760,600 @@ impl Application for HomePage {
                 self.response_tabs.set_active(&node.label);
                 None
             }
+            HomeEventMessage::OnResponseSyntaxChange(syntax) => {
+                self.response_syntax = syntax;
+                None
+            }
             HomeEventMessage::MinimizeRequestTabs => {
                 self.request_tabs.toggle_activation();
                 None
             }
             HomeEventMessage::SendRequest => {
-                if let Some(project) = self.db.active() {
-                    if let Some((_, req)) = project.current_request() {
-                        self.is_requesting = true;
-                        self.response = None;
-                        let env = self.db.active_env().unwrap_or_default();
-                        let request = req.clone();
-                        let base_url = self.db.get_active_base_url();
+                let current = self.db.active().and_then(|project| {
+                    project
+                        .current_request()
+                        .map(|(_, req)| (project.pre_request_script.clone(), req.clone()))
+                });
 
-                        return Command::perform(
-                            async move { request.send(&env, &base_url).await },
-                            |response| match response {
-                                Ok(res) => HomeEventMessage::RequestFinished(res),
-                                Err(err) => HomeEventMessage::RequestErr(err.to_string()),
-                            },
-                        );
-                    }
+                if let Some((project_script, req)) = current {
+                    let mut env = self.db.active_env().unwrap_or_default();
+                    let base_url = self.db.get_active_base_url();
+                    let mut output = ScriptOutput::default();
+
+                    let prepared = script::prepare_request(
+                        &req,
+                        &[&project_script, &req.pre_request_script],
+                        &mut env,
+                        &base_url,
+                        &mut output,
+                    );
+                    let synced = self.apply_script_output(env, output);
+
+                    return Command::batch([
+                        synced,
+                        match prepared {
+                            Ok(request) => self.send_resolved(Some(req), request),
+                            Err(err) => self.update(HomeEventMessage::RequestErr(err)),
+                        },
+                    ]);
                 }
 
                 None
             }
-            HomeEventMessage::RequestFinished(res) => {
-                self.response = Some(res);
+            HomeEventMessage::RequestFinished(sent) => {
+                let SentRequest {
+                    source,
+                    env_name,
+                    request,
+                    response,
+                } = *sent;
+
+                let entry = RequestLogEntry::new(request.clone(), env_name.clone(), &response);
+                if let HomePageState::History = self.state {
+                    self.selected_log_entry = Some(entry.id);
+                }
+                self.request_log.record(entry);
                 self.is_requesting = false;
-                None
+                self.test_results = vec![];
+                let mut synced = Command::none();
+
+                match response {
+                    Ok(res) => {
+                        if let Some(source) = source {
+                            self.test_results = evaluate_all(&source.assertions, &res);
+                            self.history
+                                .record(source.id, ResponseRecord::new(&res, env_name));
+
+                            let project_script = self
+                                .db
+                                .active()
+                                .map(|project| project.post_response_script)
+                                .unwrap_or_default();
+                            let mut env = self.db.active_env().unwrap_or_default();
+                            let mut output = ScriptOutput::default();
+
+                            // extracted values are visible to the post-response scripts
+                            if res.status_code.is_success() {
+                                self.extraction_results =
+                                    extraction::apply_all(&source.extractions, &res, &mut env);
+                            }
+
+                            script::run_post_response_scripts(
+                                &[&project_script, &source.post_response_script],
+                                &request,
+                                &res,
+                                &mut env,
+                                &mut output,
+                            );
+                            synced = self.apply_script_output(env, output);
+                        }
+                        self.response = Some(res);
+                    }
+                    Err(msg) => {
+                        println!("{:<10}[FALCON]: (SEND) Request failed, {}", "ERROR", msg);
+                    }
+                }
+
+                Some(Command::batch([synced, self.sync_history()]))
             }
             HomeEventMessage::RequestErr(msg) => {
                 self.is_requesting = false;
                 println!("{:<10}[FALCON]: (SEND) Request failed, {}", "ERROR", msg);
                 None
             }
+            HomeEventMessage::RequestLogSearchInput(query) => {
+                self.log_query = query;
+                None
+            }
+            HomeEventMessage::SelectRequestLogEntry(id) => {
+                self.selected_log_entry = Some(id);
+                None
+            }
+            HomeEventMessage::RerunRequestLogEntry(id) => match self.request_log.find(&id) {
+                // the placeholders of the credentials would be sent
+                Some(entry) if entry.redacted => {
+                    let notice = "Credentials are not kept, open it as a new request to set them";
+                    self.log_notice = Some(notice.into());
+                    None
+                }
+                Some(entry) => return self.send_resolved(None, entry.request.clone()),
+                None => None,
+            },
+            HomeEventMessage::OpenRequestLogEntry(id) => {
+                let request = self
+                    .request_log
+                    .find(&id)
+                    .map(|entry| PendingRequest::from(&entry.editable_request()));
+
+                if let Some(mut request) = request {
+                    let base_url = self.db.get_active_base_url();
+                    request.url = RequestUrl::from(request.url).extract(&base_url);
+
+                    self.state = HomePageState::Requests;
+                    return self.update(RequestEvent::Add(Box::new(request)).into());
+                }
+
+                None
+            }
+            HomeEventMessage::RequestLogRetentionChange(retention) => {
+                self.log_retention = retention;
+                None
+            }
+            HomeEventMessage::ClearRequestLog => {
+                self.request_log.clear_older_than(self.log_retention);
+
+                if let Some(id) = self.selected_log_entry {
+                    if self.request_log.find(&id).is_none() {
+                        self.selected_log_entry = None;
+                    }
+                }
+
+                Some(self.sync_history())
+            }
+            HomeEventMessage::ExportRequestLog => {
+                let har = har::export_log(&self.request_log.search(&self.log_query));
+
+                Some(Command::perform(
+                    async move {
+                        let har = har?;
+                        let file = rfd::AsyncFileDialog::new()
+                            .add_filter("HAR", &["har"])
+                            .set_file_name("falcon.har")
+                            .save_file()
+                            .await;
+
+                        match file {
+                            Some(file) => std::fs::write(file.path(), har)
+                                .map(|_| Some(file.path().display().to_string()))
+                                .map_err(|err| format!("Failed to save file, cause {}", err)),
+                            None => Ok(None),
+                        }
+                    },
+                    HomeEventMessage::RequestLogExported,
+                ))
+            }
+            HomeEventMessage::RequestLogExported(result) => {
+                self.log_notice = match result {
+                    Ok(Some(path)) => Some(format!("Saved to {}", path)),
+                    Ok(None) => None,
+                    Err(err) => {
+                        println!("{:<10}[FALCON]: (HISTORY) Export failed, {}", "ERROR", err);
+                        Some(err)
+                    }
+                };
+                None
+            }
+            HomeEventMessage::OpenHistoryEntry(id) => {
+                let (_, req) = self.pending_request();
+
+                if let Some(record) = self.history.find(&req.id, &id) {
+                    let response: FalconResponse = record.into();
+                    self.test_results = evaluate_all(&req.assertions, &response);
+                    self.response = Some(response);
+                }
+
+                None
+            }
+            HomeEventMessage::ToggleHistoryCompare(id) => {
+                if let Some(index) = self.history_compare.iter().position(|i| *i == id) {
+                    self.history_compare.remove(index);
+                } else {
+                    // only two entries can be compared, drop the oldest selection
+                    if self.history_compare.len() == 2 {
+                        self.history_compare.remove(0);
+                    }
+                    self.history_compare.push(id);
+                }
+
+                None
+            }
+            HomeEventMessage::ClearHistory => {
+                let (_, req) = self.pending_request();
+                self.history.clear(&req.id);
+                self.history_compare.clear();
+                Some(self.sync_history())
+            }
+            HomeEventMessage::RunnerScopeSelect(scope) => {
+                self.runner_options.scope = scope;
+                None
+            }
+            HomeEventMessage::RunnerDelayInput(delay) => {
+                self.runner_options.delay_ms = delay.trim().parse().unwrap_or_default();
+                self.runner_delay = delay;
+                None
+            }
+            HomeEventMessage::RunnerIterationsInput(iterations) => {
+                self.runner_options.iterations = iterations.trim().parse().unwrap_or(1);
+                self.runner_iterations = iterations;
+                None
+            }
+            HomeEventMessage::ToggleRunnerStopOnFailure(stop) => {
+                self.runner_options.stop_on_failure = stop;
+                None
+            }
+            HomeEventMessage::PickRunnerDataFile => Some(Command::perform(
+                async {
+                    let file = rfd::AsyncFileDialog::new()
+                        .add_filter("CSV or JSON", &["csv", "json"])
+                        .pick_file()
+                        .await;
+
+                    match file {
+                        Some(file) => DataFile::load(file.path()).map(Some),
+                        None => Ok(None),
+                    }
+                },
+                HomeEventMessage::RunnerDataFileLoaded,
+            )),
+            HomeEventMessage::RunnerDataFileLoaded(result) => {
+                match result {
+                    Ok(Some(data)) => {
+                        self.runner_notice = None;
+                        self.runner_data = Some(data);
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        println!("{:<10}[FALCON]: (RUNNER) Data file failed, {}", "ERROR", err);
+                        self.runner_notice = Some(err);
+                    }
+                }
+                None
+            }
+            HomeEventMessage::ClearRunnerDataFile => {
+                self.runner_data = None;
+                None
+            }
+            HomeEventMessage::StartRun => {
+                let Some(project) = self.db.active() else {
+                    return Command::none();
+                };
+
+                self.runner_notice = None;
+                self.collection_run = Some(CollectionRun::new(
+                    &project,
+                    self.db.active_env(),
+                    self.db.get_active_base_url(),
+                    self.runner_options.clone(),
+                    self.runner_data.as_ref(),
+                ));
+
+                Some(self.next_run_step())
+            }
+            HomeEventMessage::StopRun => {
+                if let Some(run) = self.collection_run.as_mut() {
+                    run.stop();
+                }
+                None
+            }
+            HomeEventMessage::RunStepFinished(finished) => {
+                let (result, env, output) = *finished;
+
+                if let Some(run) = self.collection_run.as_mut() {
+                    run.record(result, env.clone());
+                }
+
+                // variables extracted or set by scripts are kept, same as single sends
+                let synced = self.apply_script_output(env, output);
+
+                Some(Command::batch([synced, self.next_run_step()]))
+            }
+            HomeEventMessage::ExportRunReport => {
+                let Some(run) = self.collection_run.as_ref() else {
+                    return Command::none();
+                };
+                let report = run.report();
+
+                Some(Command::perform(
+                    async move {
+                        let json = report.to_json()?;
+                        let file = rfd::AsyncFileDialog::new()
+                            .add_filter("JSON", &["json"])
+                            .set_file_name("falcon-run.json")
+                            .save_file()
+                            .await;
+
+                        match file {
+                            Some(file) => std::fs::write(file.path(), json)
+                                .map(|_| Some(file.path().display().to_string()))
+                                .map_err(|err| format!("Failed to save file, cause {}", err)),
+                            None => Ok(None),
+                        }
+                    },
+                    HomeEventMessage::RunReportExported,
+                ))
+            }
+            HomeEventMessage::RunReportExported(result) => {
+                self.runner_notice = match result {
+                    Ok(Some(path)) => Some(format!("Saved to {}", path)),
+                    Ok(None) => None,
+                    Err(err) => {
+                        println!("{:<10}[FALCON]: (RUNNER) Export failed, {}", "ERROR", err);
+                        Some(err)
+                    }
+                };
+                None
+            }
+            HomeEventMessage::CodeLanguageSelect(language) => {
+                self.code_language = language;
+                None
+            }
+            HomeEventMessage::ToggleCodeVariables(keep) => {
+                self.code_keep_variables = keep;
+                None
+            }
+            HomeEventMessage::LoadConcurrencyInput(concurrency) => {
+                self.load_concurrency = concurrency;
+                None
+            }
+            HomeEventMessage::LoadLimitKindSelect(kind) => {
+                self.load_limit_kind = kind;
+                None
+            }
+            HomeEventMessage::LoadLimitInput(limit) => {
+                self.load_limit = limit;
+                None
+            }
+            HomeEventMessage::LoadRpsInput(rps) => {
+                self.load_rps = rps;
+                None
+            }
+            HomeEventMessage::StartLoadTest => {
+                let current = self.db.active().and_then(|project| {
+                    project
+                        .current_request()
+                        .map(|(_, req)| (project.pre_request_script.clone(), req.clone()))
+                });
+                let Some((project_script, req)) = current else {
+                    return Command::none();
+                };
+
+                let options = match self.load_test_options() {
+                    Ok(options) => options,
+                    Err(err) => {
+                        self.load_report = Some(Err(err));
+                        return Command::none();
+                    }
+                };
+
+                let mut env = self.db.active_env().unwrap_or_default();
+                let base_url = self.db.get_active_base_url();
+                let mut output = ScriptOutput::default();
+
+                // scripts run once, every request of the test is the same
+                let prepared = script::prepare_request(
+                    &req,
+                    &[&project_script, &req.pre_request_script],
+                    &mut env,
+                    &base_url,
+                    &mut output,
+                );
+                let synced = self.apply_script_output(env, output);
+
+                let request = match prepared {
+                    Ok(request) => request,
+                    Err(err) => {
+                        self.load_report = Some(Err(err));
+                        return synced;
+                    }
+                };
+
+                let stop = Arc::new(AtomicBool::new(false));
+                self.load_stop = Some(stop.clone());
+                self.load_report = None;
+
+                Some(Command::batch([
+                    synced,
+                    Command::perform(load_test::run(request, options, stop), |report| {
+                        HomeEventMessage::LoadTestFinished(Box::new(report))
+                    }),
+                ]))
+            }
+            HomeEventMessage::StopLoadTest => {
+                if let Some(stop) = self.load_stop.as_ref() {
+                    stop.store(true, Ordering::SeqCst);
+                }
+                None
+            }
+            HomeEventMessage::LoadTestFinished(report) => {
+                if let Err(err) = report.as_ref() {
+                    println!("{:<10}[FALCON]: (LOAD) Load test failed, {}", "ERROR", err);
+                }
+                self.load_stop = None;
+                self.load_report = Some(*report);
+                None
+            }
+            HomeEventMessage::ImportFile => Some(Command::perform(
+                async {
+                    let file = rfd::AsyncFileDialog::new()
+                        .add_filter(
+                            "Postman, OpenAPI, HAR or Hurl file",
+                            &["json", "yaml", "yml", "har", "hurl"],
+                        )
+                        .pick_file()
+                        .await;
+
+                    match file {
+                        Some(file) => import::import_file(file.path()).map(Some),
+                        None => Ok(None),
+                    }
+                },
+                |result| HomeEventMessage::ImportFinished(Box::new(result)),
+            )),
+            HomeEventMessage::ImportFinished(result) => match *result {
+                Ok(Some(mut imported)) => {
+                    self.imported_responses = std::mem::take(&mut imported.responses);
+                    self.import_report = Some(Ok(imported.apply(&mut self.db)));
+                    self.state = HomePageState::Projects;
+                    self.update_request_body();
+                    self.update_project_scripts();
+
+                    Some(self.schedule_sync())
+                }
+                Ok(None) => None,
+                Err(err) => {
+                    println!("{:<10}[FALCON]: (IMPORT) Import failed, {}", "ERROR", err);
+                    self.import_report = Some(Err(err));
+                    self.state = HomePageState::Projects;
+                    None
+                }
+            },
+            HomeEventMessage::OpenHttpFolder => Some(Command::perform(
+                async {
+                    rfd::AsyncFileDialog::new()
+                        .pick_folder()
+                        .await
+                        .map(|dir| dir.path().to_path_buf())
+                },
+                HomeEventMessage::HttpFolderPicked,
+            )),
+            HomeEventMessage::HttpFolderPicked(Some(dir)) => {
+                match http_file::open_in(&mut self.db, &dir) {
+                    Ok(http) => {
+                        self.db.set_active(&http.project_id);
+                        self.http_projects.retain(|opened| opened.dir != dir);
+                        self.http_projects.push(http);
+                        self.project_notice = Some(format!(
+                            "Opened {}, edits are written back to its files",
+                            dir.display()
+                        ));
+                        self.update_request_body();
+                        self.update_project_scripts();
+                    }
+                    Err(err) => {
+                        println!("{:<10}[FALCON]: (HTTP) Failed to open, {}", "ERROR", err);
+                        self.import_report = Some(Err(err));
+                    }
+                }
+                self.state = HomePageState::Projects;
+
+                Some(self.schedule_sync())
+            }
+            HomeEventMessage::HttpFolderPicked(None) => None,
+            HomeEventMessage::OpenWorkspace => Some(Command::perform(
+                async {
+                    rfd::AsyncFileDialog::new()
+                        .pick_folder()
+                        .await
+                        .map(|dir| dir.path().to_path_buf())
+                },
+                HomeEventMessage::WorkspacePicked,
+            )),
+            HomeEventMessage::WorkspacePicked(Some(dir)) => {
+                let db = self.db.open_workspace(&dir);
+                self.switch_db(db)
+            }
+            HomeEventMessage::WorkspacePicked(None) => None,
+            HomeEventMessage::CloseWorkspace => self.switch_db(DB::close_workspace()),
+            HomeEventMessage::FilesChanged => self.reload_files(),
+            HomeEventMessage::HttpFilesChanged(dir) => self.reload_http_project(&dir),
+            HomeEventMessage::KeepMyChanges => self.resolve_file_conflict(true),
+            HomeEventMessage::UseFileChanges => self.resolve_file_conflict(false),
+            HomeEventMessage::RestoreBackup(path) => self.switch_db(DB::restore_backup(&path)),
+            HomeEventMessage::DiscardUnreadableProjects => {
+                self.project_notice = match self.db.discard_unreadable() {
+                    Ok(aside) => Some(format!("Unreadable projects moved to {}", aside.display())),
+                    Err(err) => Some(err),
+                };
+                self.state = HomePageState::Projects;
+
+                None
+            }
+            HomeEventMessage::ImportCurl => Some(clipboard::read(HomeEventMessage::CurlPasted)),
+            HomeEventMessage::CurlPasted(contents) => {
+                let import = contents
+                    .filter(|contents| curl::is_command(contents))
+                    .ok_or("The clipboard has no cURL command".to_string())
+                    .and_then(|contents| curl::import_command(&contents));
+
+                match import {
+                    Ok(import) => {
+                        let base_url = self.db.get_active_base_url();
+                        let mut request = import.request;
+                        request.url = RequestUrl::from(request.url).extract(&base_url);
+
+                        self.import_report = Some(Ok(import.report));
+                        self.imported_responses = vec![];
+                        return self.update(RequestEvent::Add(Box::new(request)).into());
+                    }
+                    Err(err) => {
+                        println!("{:<10}[FALCON]: (IMPORT) Import failed, {}", "ERROR", err);
+                        self.import_report = Some(Err(err));
+                        None
+                    }
+                }
+            }
+            HomeEventMessage::KeepImportedResponses => {
+                for (request_id, record) in std::mem::take(&mut self.imported_responses) {
+                    self.history.record(request_id, record);
+                }
+
+                Some(self.sync_history())
+            }
+            HomeEventMessage::DismissImportReport => {
+                self.import_report = None;
+                self.imported_responses = vec![];
+                None
+            }
+            HomeEventMessage::ExportProject(format) => {
+                let Some(project) = self.db.active() else {
+                    return Command::none();
+                };
+                let env = self.db.project_default_env();
+                let file_name = format.file_name(&project.name);
+                let (filter, extension) = (format.to_string(), format.extension());
+                let exported =
+                    import::export_project(&project, env.as_ref(), &self.history, format);
+
+                Some(Command::perform(
+                    async move {
+                        let exported = exported?;
+                        let file = rfd::AsyncFileDialog::new()
+                            .add_filter(filter, &[extension])
+                            .set_file_name(file_name)
+                            .save_file()
+                            .await;
+
+                        match file {
+                            Some(file) => std::fs::write(file.path(), exported)
+                                .map(|_| Some(file.path().display().to_string()))
+                                .map_err(|err| format!("Failed to save file, cause {}", err)),
+                            None => Ok(None),
+                        }
+                    },
+                    HomeEventMessage::ProjectExported,
+                ))
+            }
+            HomeEventMessage::ProjectExported(result) => {
+                self.project_notice = match result {
+                    Ok(Some(path)) => Some(format!("Saved to {}", path)),
+                    Ok(None) => None,
+                    Err(err) => {
+                        println!("{:<10}[FALCON]: (EXPORT) Export failed, {}", "ERROR", err);
+                        Some(err)
+                    }
+                };
+                None
+            }
             HomeEventMessage::OnChangePageState(state) => {
                 self.state = state;
                 None
@@ -280,6 +1370,7 @@ impl Application for HomePage {
                     }
                     _ => (),
                 }
+                self.update_project_scripts();
 
                 Some(self.schedule_sync())
             }
@@ -309,17 +1400,86 @@ impl Application for HomePage {
 
                 Some(self.schedule_sync())
             }
+            HomeEventMessage::OnScriptAction(stage, action) => {
+                let editor = self.request_scripts.get_mut(stage);
+                editor.perform(action);
+                let source = editor.text();
+
+                if let Some(req) = self.db.active_mut().and_then(|p| p.current_request_mut()) {
+                    match stage {
+                        ScriptStage::PreRequest => req.pre_request_script = source,
+                        ScriptStage::PostResponse => req.post_response_script = source,
+                    }
+                }
+
+                Some(self.schedule_sync())
+            }
+            HomeEventMessage::OnProjectScriptAction(stage, action) => {
+                let editor = self.project_scripts.get_mut(stage);
+                editor.perform(action);
+                let source = editor.text();
+
+                if let Some(project) = self.db.active_mut() {
+                    match stage {
+                        ScriptStage::PreRequest => project.pre_request_script = source,
+                        ScriptStage::PostResponse => project.post_response_script = source,
+                    }
+                }
+
+                Some(self.schedule_sync())
+            }
+            HomeEventMessage::ClearScriptConsole => {
+                self.script_console.clear();
+                None
+            }
+            HomeEventMessage::PrettifyRequestBody | HomeEventMessage::MinifyRequestBody => {
+                let body = self.request_body_context.text();
+                let formatted = match message {
+                    HomeEventMessage::PrettifyRequestBody => json_body::prettify(&body),
+                    _ => json_body::minify(&body),
+                };
+
+                // invalid bodies are left untouched, the error is shown under the editor
+                match formatted {
+                    Ok(formatted) => {
+                        if let Some(project) = self.db.active_mut() {
+                            if let Some(req) = project.current_request_mut() {
+                                req.set_body(FlBody::ApplicationJson(formatted));
+                            }
+                        }
+
+                        self.update_request_body();
+                        Some(self.schedule_sync())
+                    }
+                    Err(_) => None,
+                }
+            }
             HomeEventMessage::ToggleEnvExample => {
                 self.show_env_examples = !self.show_env_examples;
                 None
             }
-            HomeEventMessage::SyncProjects => Some(self.perform_sync()),
+            HomeEventMessage::SyncProjects => {
+                self.write_http_projects();
+                Some(self.perform_sync())
+            }
             HomeEventMessage::SyncedDone => {
                 println!("{:<10}[FALCON]: (DB) Synced to local file", "INFO");
                 None
             }
             HomeEventMessage::CopyTxt(txt) => Some(clipboard::write(txt)),
-            HomeEventMessage::OnAuthorizationTabChange(_) => None,
+            HomeEventMessage::OnAuthorizationTabChange(node) => {
+                let (_, req) = self.pending_request();
+                let auth = match (node.label.as_str(), req.authorization) {
+                    ("Basic", FalconAuthorization::Bearer { .. }) => FalconAuthorization::Basic {
+                        username: String::new(),
+                        password: String::new(),
+                    },
+                    ("Bearer", FalconAuthorization::Basic { .. }) => FalconAuthorization::default(),
+                    _ => return Command::none(),
+                };
+
+                return self.update(RequestEvent::AuthorizationInput(auth).into());
+            }
             HomeEventMessage::OnBodyTabChange(_) => None,
             HomeEventMessage::NavigateTo(_) => None,
         }
@@ -335,6 +1495,8 @@ impl Application for HomePage {
                 HomePageState::Requests => sidebar_requests(self),
                 HomePageState::Projects => get_sidebar_projects_items(self),
                 HomePageState::Envs => get_env_items(self),
+                HomePageState::History => sidebar_history(self),
+                HomePageState::Runner => sidebar_requests(self),
             };
 
             base_row = base_row.push(
@@ -368,17 +1530,37 @@ impl Application for HomePage {
                 base_row =
                     base_row.push(env_tabs_block(self.db.active_env(), self.show_env_examples));
             }
+            HomePageState::Runner => {
+                base_row = base_row.push(runner_block(self));
+            }
+            HomePageState::History => {
+                base_row = base_row.push(request_log_block(
+                    self.selected_log_entry
+                        .and_then(|id| self.request_log.find(&id)),
+                    self.log_retention,
+                    self.is_requesting,
+                    self.log_notice.clone(),
+                ));
+            }
         }
 
         // build main view here
-        column![
-            tob_bar(
-                self.db.into_options(),
-                self.db.selected_project(),
-                self.sidebar_closed,
-            ),
-            base_row
-        ]
+        column![tob_bar(
+            self.db.into_options(),
+            self.db.selected_project(),
+            self.sidebar_closed,
+        )]
+        .push_maybe(
+            self.db
+                .load_error()
+                .map(|err| container(recovery_block(err, DB::backups())).padding(10)),
+        )
+        .push_maybe(
+            self.file_conflict
+                .as_ref()
+                .map(|conflict| container(file_conflict_block(&conflict.conflicts)).padding(10)),
+        )
+        .push(base_row)
         .into()
     }
 }
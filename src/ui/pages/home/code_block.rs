@@ -0,0 +1,64 @@
+use iced::{
+    widget::{button, checkbox, column, container, pick_list, row, scrollable, text, Space},
+    Color, Element, Font, Length, Padding, Renderer, Theme,
+};
+
+use crate::{
+    ui::app_theme::{AppBtn, AppColor, AppContainer, AppSelect},
+    utils::codegen::{self, CodeLanguage},
+};
+
+use super::{HomeEventMessage, HomePage};
+
+pub fn code_block(page: &HomePage) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let (_, request) = page.pending_request();
+    let env = page.db.active_env().unwrap_or_default();
+    let code = codegen::generate(
+        &request,
+        &env,
+        &page.db.get_active_base_url(),
+        page.code_language,
+        page.code_keep_variables,
+    );
+
+    let mut copy = button("Copy")
+        .padding(Padding::from([5, 10]))
+        .style(AppBtn::Primary);
+    if let Ok(code) = code.as_ref() {
+        copy = copy.on_press(HomeEventMessage::CopyTxt(code.clone()));
+    }
+
+    let options_row = row![
+        pick_list(
+            CodeLanguage::ALL,
+            Some(page.code_language),
+            HomeEventMessage::CodeLanguageSelect
+        )
+        .padding(2)
+        .text_size(14)
+        .style(AppSelect::Card),
+        Space::with_width(10),
+        checkbox("Keep {{VARS}}", page.code_keep_variables)
+            .text_size(14)
+            .on_toggle(HomeEventMessage::ToggleCodeVariables),
+        Space::with_width(Length::Fill),
+        copy,
+    ]
+    .align_items(iced::Alignment::Center);
+
+    let content = match code {
+        Ok(code) => text(code).size(14).font(Font::MONOSPACE),
+        Err(err) => text(err).size(14).style(Into::<Color>::into(AppColor::RED)),
+    };
+
+    column![
+        options_row,
+        Space::with_height(10),
+        container(scrollable(content).width(Length::Fill))
+            .padding(10)
+            .width(Length::Fill)
+            .style(AppContainer::Rounded),
+    ]
+    .padding(10)
+    .into()
+}
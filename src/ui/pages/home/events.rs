@@ -2,7 +2,11 @@ use uuid::Uuid;
 
 use crate::utils::{
     db::{env::Env, project::Project, DB},
-    request::{FalconAuthorization, http_method::HttpMethod, PendingRequest, PendingRequestItem},
+    import::curl,
+    request::{
+        assertion::AssertionKind, extraction::ExtractionSource, http_method::HttpMethod,
+        FalconAuthorization, PendingRequest, PendingRequestItem,
+    },
 };
 
 use super::HomeEventMessage;
@@ -140,11 +144,22 @@ pub enum RequestEvent {
     ItemValueInput(PendingRequestItem, usize, String),
     NameInput(String),
     RemoveItem(PendingRequestItem, usize),
-    Add(PendingRequest),
+    Add(Box<PendingRequest>),
     Select(Uuid),
     Delete(Uuid),
     AuthorizationInput(FalconAuthorization),
     UrlInput(String),
+    ResponseFilterInput(String),
+    AddAssertion,
+    RemoveAssertion(usize),
+    AssertionKindSelect(usize, AssertionKind),
+    AssertionTargetInput(usize, String),
+    AssertionExpectedInput(usize, String),
+    AddExtraction,
+    RemoveExtraction(usize),
+    ExtractionSourceSelect(usize, ExtractionSource),
+    ExtractionExpressionInput(usize, String),
+    ExtractionVariableInput(usize, String),
     New
 }
 
@@ -177,7 +192,7 @@ impl RequestEvent {
                 }
             }
             RequestEvent::Add(request) => {
-                project.add_request("root", request);
+                project.add_request("root", *request);
             }
             RequestEvent::Select(id) => {
                 project.set_current_request(id);
@@ -191,7 +206,66 @@ impl RequestEvent {
                 }
             }
             RequestEvent::UrlInput(url) => {
-                project.update_request_url(url.into(), base_url);
+                // a pasted cURL command fills the whole request
+                match curl::is_command(&url).then(|| curl::import_command(&url)) {
+                    Some(Ok(import)) => project.replace_current_request(import.request, base_url),
+                    _ => project.update_request_url(url.into(), base_url),
+                }
+            }
+            RequestEvent::ResponseFilterInput(filter) => {
+                if let Some(req) = project.current_request_mut() {
+                    req.set_response_filter(filter);
+                }
+            }
+            RequestEvent::AddAssertion => {
+                if let Some(req) = project.current_request_mut() {
+                    req.add_assertion();
+                }
+            }
+            RequestEvent::RemoveAssertion(index) => {
+                if let Some(req) = project.current_request_mut() {
+                    req.remove_assertion(index);
+                }
+            }
+            RequestEvent::AssertionKindSelect(index, kind) => {
+                if let Some(req) = project.current_request_mut() {
+                    req.update_assertion_kind(index, kind);
+                }
+            }
+            RequestEvent::AssertionTargetInput(index, target) => {
+                if let Some(req) = project.current_request_mut() {
+                    req.update_assertion_target(index, target);
+                }
+            }
+            RequestEvent::AssertionExpectedInput(index, expected) => {
+                if let Some(req) = project.current_request_mut() {
+                    req.update_assertion_expected(index, expected);
+                }
+            }
+            RequestEvent::AddExtraction => {
+                if let Some(req) = project.current_request_mut() {
+                    req.add_extraction();
+                }
+            }
+            RequestEvent::RemoveExtraction(index) => {
+                if let Some(req) = project.current_request_mut() {
+                    req.remove_extraction(index);
+                }
+            }
+            RequestEvent::ExtractionSourceSelect(index, source) => {
+                if let Some(req) = project.current_request_mut() {
+                    req.update_extraction_source(index, source);
+                }
+            }
+            RequestEvent::ExtractionExpressionInput(index, expression) => {
+                if let Some(req) = project.current_request_mut() {
+                    req.update_extraction_expression(index, expression);
+                }
+            }
+            RequestEvent::ExtractionVariableInput(index, variable) => {
+                if let Some(req) = project.current_request_mut() {
+                    req.update_extraction_variable(index, variable);
+                }
             }
             RequestEvent::New => {
                 project.add_new_request(base_url);
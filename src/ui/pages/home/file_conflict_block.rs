@@ -0,0 +1,46 @@
+use iced::{
+    widget::{button, column, container, row, text, Column, Space},
+    Color, Element, Length, Padding,
+};
+
+use crate::ui::app_theme::{AppBtn, AppColor, AppContainer};
+
+use super::HomeEventMessage;
+
+/// Shown when the files changed outside of the app and the same items have unsaved
+/// edits, nothing is saved until one side is picked.
+pub fn file_conflict_block<'a>(conflicts: &[String]) -> Element<'a, HomeEventMessage> {
+    let items = conflicts
+        .iter()
+        .fold(Column::new().spacing(2), |items, item| {
+            items.push(text(format!("- {}", item)).size(14))
+        });
+
+    container(
+        column![
+            text("The files changed outside of Falcon")
+                .size(14)
+                .style(Into::<Color>::into(AppColor::RED)),
+            text("These have unsaved edits here too, other changes were merged:").size(14),
+            items,
+            Space::with_height(5),
+            row![
+                Space::with_width(Length::Fill),
+                button("Use the files")
+                    .padding(Padding::from([3, 10]))
+                    .style(AppBtn::Secondary)
+                    .on_press(HomeEventMessage::UseFileChanges),
+                Space::with_width(5),
+                button("Keep my changes")
+                    .padding(Padding::from([3, 10]))
+                    .style(AppBtn::Primary)
+                    .on_press(HomeEventMessage::KeepMyChanges),
+            ],
+        ]
+        .spacing(5),
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .style(AppContainer::Rounded)
+    .into()
+}
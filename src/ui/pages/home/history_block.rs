@@ -0,0 +1,205 @@
+use iced::{
+    widget::{button, column, container, row, scrollable, text, Column, Row, Space, Text},
+    Color, Element, Length, Padding, Renderer, Theme,
+};
+use uuid::Uuid;
+
+use crate::{
+    ui::app_theme::{AppBtn, AppColor, AppContainer},
+    utils::{
+        db::response_history::ResponseRecord,
+        diff::{diff_bodies, diff_lines, DiffKind, DiffRow},
+    },
+};
+
+use super::HomeEventMessage;
+
+pub fn history_block<'a>(
+    entries: &[ResponseRecord],
+    selection: &[Uuid],
+) -> Element<'a, HomeEventMessage, Theme, Renderer> {
+    if entries.is_empty() {
+        return container(text("No responses recorded for this request yet."))
+            .padding(10)
+            .into();
+    }
+
+    let mut list = Column::new().spacing(5);
+
+    for entry in entries {
+        let is_selected = selection.contains(&entry.id);
+
+        list = list.push(
+            container(
+                row![
+                    text(entry.status_code)
+                        .style(status_color(entry.status_code))
+                        .width(40),
+                    text(entry.label()).width(Length::Fill),
+                    text(format!("{} ms", entry.duration_ms)).width(80),
+                    text(format!("{} KB", entry.size_kb)).width(70),
+                    text(entry.env_name.clone().unwrap_or("-".into())).width(100),
+                    button("Open")
+                        .padding(Padding::from([3, 10]))
+                        .style(AppBtn::Secondary)
+                        .on_press(HomeEventMessage::OpenHistoryEntry(entry.id)),
+                    Space::with_width(5),
+                    button(if is_selected { "Selected" } else { "Compare" })
+                        .padding(Padding::from([3, 10]))
+                        .style(if is_selected {
+                            AppBtn::Primary
+                        } else {
+                            AppBtn::Secondary
+                        })
+                        .on_press(HomeEventMessage::ToggleHistoryCompare(entry.id)),
+                ]
+                .align_items(iced::Alignment::Center),
+            )
+            .padding(Padding::from([5, 10]))
+            .style(AppContainer::FlatSecondary),
+        );
+    }
+
+    let mut content = column![
+        row![
+            text(format!("Last {} responses", entries.len())).width(Length::Fill),
+            button("Clear")
+                .padding(Padding::from([3, 10]))
+                .style(AppBtn::Secondary)
+                .on_press(HomeEventMessage::ClearHistory),
+        ]
+        .align_items(iced::Alignment::Center),
+        Space::with_height(10),
+        list,
+    ];
+
+    let compared: Vec<&ResponseRecord> = selection
+        .iter()
+        .filter_map(|id| entries.iter().find(|entry| entry.id == *id))
+        .collect();
+
+    if let [left, right] = compared[..] {
+        content = content
+            .push(Space::with_height(20))
+            .push(diff_block(left, right));
+    } else {
+        content = content
+            .push(Space::with_height(10))
+            .push(text("Select two responses to compare them side by side.").size(14));
+    }
+
+    scrollable(content.padding(Padding::from([0, 10])))
+        .height(Length::Fill)
+        .into()
+}
+
+fn diff_block<'a>(
+    left: &ResponseRecord,
+    right: &ResponseRecord,
+) -> Element<'a, HomeEventMessage, Theme, Renderer> {
+    let header_lines = |record: &ResponseRecord| {
+        let mut headers: Vec<String> = record
+            .headers
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect();
+        headers.sort();
+        headers.join("\n")
+    };
+
+    let body = diff_bodies(&left.body, &right.body);
+
+    let mut content = column![
+        diff_row_view(DiffRow {
+            kind: DiffKind::Same,
+            left: Some((0, left.label())),
+            right: Some((0, right.label())),
+        }),
+        diff_row_view(DiffRow {
+            kind: if left.status_code == right.status_code {
+                DiffKind::Same
+            } else {
+                DiffKind::Changed
+            },
+            left: Some((
+                0,
+                format!("Status {}, {} ms", left.status_code, left.duration_ms)
+            )),
+            right: Some((
+                0,
+                format!("Status {}, {} ms", right.status_code, right.duration_ms)
+            )),
+        }),
+        Space::with_height(10),
+        text("Headers").size(14),
+    ]
+    .spacing(2);
+
+    for row in diff_lines(&header_lines(left), &header_lines(right)) {
+        content = content.push(diff_row_view(row));
+    }
+
+    content = content
+        .push(Space::with_height(10))
+        .push(text("Body").size(14));
+
+    if let Some(changes) = body.json_changes {
+        if changes.is_empty() {
+            content = content.push(text("JSON bodies are structurally equal.").size(14));
+        }
+
+        for change in changes {
+            content = content.push(kind_text(change.to_string(), change.kind()));
+        }
+
+        content = content.push(Space::with_height(10));
+    }
+
+    for row in body.rows {
+        content = content.push(diff_row_view(row));
+    }
+
+    content.into()
+}
+
+fn diff_row_view<'a>(row: DiffRow) -> Element<'a, HomeEventMessage, Theme, Renderer> {
+    let cell = |line: Option<(usize, String)>| {
+        let content: Element<'a, HomeEventMessage, Theme, Renderer> = match line {
+            Some((0, value)) => kind_text(value, row.kind).into(),
+            Some((number, value)) => Row::new()
+                .push(text(number).size(14).width(40))
+                .push(kind_text(value, row.kind))
+                .into(),
+            None => Space::with_height(0).into(),
+        };
+
+        container(content)
+            .padding(Padding::from([1, 5]))
+            .width(Length::FillPortion(1))
+            .style(AppContainer::FlatSecondary)
+    };
+
+    Row::new()
+        .push(cell(row.left.clone()))
+        .push(Space::with_width(2))
+        .push(cell(row.right.clone()))
+        .into()
+}
+
+fn kind_text<'a>(value: String, kind: DiffKind) -> Text<'a, Theme, Renderer> {
+    let color: Color = match kind {
+        DiffKind::Same => return text(value).size(14),
+        DiffKind::Changed => AppColor::YELLOW.into(),
+        DiffKind::Removed => AppColor::RED.into(),
+        DiffKind::Added => AppColor::GREEN.into(),
+    };
+
+    text(value).size(14).style(color)
+}
+
+fn status_color(status_code: u16) -> Color {
+    match status_code {
+        200..=399 => AppColor::GREEN.into(),
+        _ => AppColor::RED.into(),
+    }
+}
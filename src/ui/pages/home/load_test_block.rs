@@ -0,0 +1,183 @@
+use iced::{
+    widget::{button, column, container, pick_list, row, text, text_input, Column, Space},
+    Color, Element, Length, Padding, Renderer, Theme,
+};
+
+use crate::{
+    ui::app_theme::{AppBtn, AppColor, AppContainer, AppInput, AppSelect},
+    utils::load_test::{LoadLimitKind, LoadReport},
+};
+
+use super::{HomeEventMessage, HomePage};
+
+/// Width of the longest histogram bar.
+const MAX_BAR_WIDTH: f32 = 300.0;
+
+pub fn load_test_block(page: &HomePage) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let is_running = page.load_stop.is_some();
+
+    let action = if is_running {
+        button("Stop")
+            .padding(Padding::from([5, 10]))
+            .style(AppBtn::Secondary)
+            .on_press(HomeEventMessage::StopLoadTest)
+    } else {
+        button("Load")
+            .padding(Padding::from([5, 10]))
+            .style(AppBtn::Primary)
+            .on_press(HomeEventMessage::StartLoadTest)
+    };
+
+    let options_row = row![
+        text("Concurrency").size(14),
+        Space::with_width(5),
+        text_input("10", &page.load_concurrency)
+            .on_input(HomeEventMessage::LoadConcurrencyInput)
+            .style(AppInput)
+            .width(60),
+        Space::with_width(10),
+        pick_list(
+            LoadLimitKind::ALL,
+            Some(page.load_limit_kind),
+            HomeEventMessage::LoadLimitKindSelect
+        )
+        .padding(5)
+        .text_size(14)
+        .style(AppSelect::Card),
+        Space::with_width(5),
+        text_input(
+            match page.load_limit_kind {
+                LoadLimitKind::Count => "100",
+                LoadLimitKind::Duration => "10",
+            },
+            &page.load_limit
+        )
+        .on_input(HomeEventMessage::LoadLimitInput)
+        .style(AppInput)
+        .width(80),
+        Space::with_width(10),
+        text("Target RPS").size(14),
+        Space::with_width(5),
+        text_input("Unlimited", &page.load_rps)
+            .on_input(HomeEventMessage::LoadRpsInput)
+            .style(AppInput)
+            .width(90),
+        Space::with_width(Length::Fill),
+        action,
+    ]
+    .align_items(iced::Alignment::Center);
+
+    let content = column![container(options_row)
+        .padding(10)
+        .width(Length::Fill)
+        .style(AppContainer::Rounded)];
+
+    let details: Element<'static, HomeEventMessage, Theme, Renderer> =
+        match (is_running, page.load_report.as_ref()) {
+            (true, _) => text("Sending...").size(14).into(),
+            (false, Some(Ok(report))) => report_block(report),
+            (false, Some(Err(err))) => text(err.clone())
+                .size(14)
+                .style(Into::<Color>::into(AppColor::RED))
+                .into(),
+            (false, None) => {
+                text("Sends the request repeatedly, pre-request scripts run once before the test.")
+                    .size(14)
+                    .into()
+            }
+        };
+
+    content.push(Space::with_height(10)).push(details).into()
+}
+
+fn report_block(report: &LoadReport) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let summary = text(format!(
+        "{} requests in {:.2} s, {:.1} req/s, {} failed{}",
+        report.total,
+        report.elapsed.as_secs_f64(),
+        report.throughput,
+        report.failed,
+        if report.stopped { " (stopped)" } else { "" },
+    ))
+    .size(14)
+    .style(Into::<Color>::into(if report.failed == 0 {
+        AppColor::GREEN
+    } else {
+        AppColor::RED
+    }));
+
+    let latencies = [
+        ("p50", report.p50),
+        ("p90", report.p90),
+        ("p99", report.p99),
+        ("max", report.max),
+    ]
+    .into_iter()
+    .fold(row![].spacing(20), |row, (label, latency)| {
+        row.push(
+            text(format!(
+                "{}: {:.1} ms",
+                label,
+                latency.as_secs_f64() * 1000.0
+            ))
+            .size(14),
+        )
+    });
+
+    let mut breakdown = Column::new().spacing(2);
+    for (status, count) in report.statuses.iter() {
+        breakdown = breakdown.push(text(format!("{}: {}", status, count)).size(14));
+    }
+    for (kind, count) in report.errors.iter() {
+        breakdown = breakdown.push(
+            text(format!("{} error: {}", kind, count))
+                .size(14)
+                .style(Into::<Color>::into(AppColor::RED)),
+        );
+    }
+
+    let highest = report
+        .histogram
+        .iter()
+        .map(|bucket| bucket.count)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let mut histogram = Column::new().spacing(2);
+    for bucket in report.histogram.iter() {
+        let width = MAX_BAR_WIDTH * bucket.count as f32 / highest as f32;
+
+        histogram = histogram.push(
+            row![
+                text(format!("{:.1} - {:.1} ms", bucket.from_ms, bucket.to_ms))
+                    .size(12)
+                    .width(140),
+                container("")
+                    .width(width.max(1.0))
+                    .height(10)
+                    .style(AppContainer::Bg(AppColor::PURPLE)),
+                Space::with_width(5),
+                text(bucket.count.to_string()).size(12),
+            ]
+            .align_items(iced::Alignment::Center),
+        );
+    }
+
+    container(column![
+        summary,
+        Space::with_height(10),
+        latencies,
+        Space::with_height(10),
+        row![
+            column![text("Responses").size(14), Space::with_height(5), breakdown]
+                .width(Length::FillPortion(1)),
+            column![text("Latency").size(14), Space::with_height(5), histogram]
+                .width(Length::FillPortion(2)),
+        ],
+    ])
+    .padding(10)
+    .width(Length::Fill)
+    .style(AppContainer::Rounded)
+    .into()
+}
@@ -1,8 +1,10 @@
 use iced::widget::svg::Handle;
 use iced::widget::tooltip;
 use iced::{
-    widget::{button, column, container, pick_list, row, svg, text, text_input, Row, Space},
-    Element, Length, Padding, Renderer, Theme,
+    widget::{
+        button, column, container, pick_list, row, svg, text, text_input, Column, Row, Space,
+    },
+    Color, Element, Length, Padding, Renderer, Theme,
 };
 use uuid::Uuid;
 
@@ -11,11 +13,13 @@ use crate::ui::{
     app_theme::{AppBtn, AppColor, AppContainer, AppInput, AppSelect},
     elements::select_options::SelectOption,
 };
+use crate::utils::import::{ExportFormat, ImportReport};
 
 use super::events::ProjectEvent;
+use super::scripts_block::script_editors_block;
 use super::{HomeEventMessage, HomePage};
 
-pub fn project_tabs_block(page: &HomePage) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+pub fn project_tabs_block<'a>(page: &'a HomePage) -> Element<'a, HomeEventMessage, Theme, Renderer> {
     let active_project = page.db.active();
     let project_env: Option<SelectOption<Uuid>> = page
         .db
@@ -54,7 +58,14 @@ pub fn project_tabs_block(page: &HomePage) -> Element<'static, HomeEventMessage,
         ));
     }
 
-    column![container(column![
+    let mut content = Column::new();
+    if let Some(report) = page.import_report.as_ref() {
+        content = content
+            .push(import_report_block(report, page.imported_responses.len()))
+            .push(Space::with_height(10));
+    }
+
+    content.push(container(column![
         row![
             text("Project name: ").size(14),
             text_input(
@@ -77,11 +88,105 @@ pub fn project_tabs_block(page: &HomePage) -> Element<'static, HomeEventMessage,
         )
         .padding(Padding::from([10, 0])),
         env_row.align_items(iced::Alignment::Center),
+        container(
+            container("")
+                .style(AppContainer::Bg(AppColor::BG_DARKER))
+                .height(1)
+                .width(Length::Fill)
+        )
+        .padding(Padding::from([10, 0])),
+        export_row(page.project_notice.clone()),
+        container(
+            container("")
+                .style(AppContainer::Bg(AppColor::BG_DARKER))
+                .height(1)
+                .width(Length::Fill)
+        )
+        .padding(Padding::from([10, 0])),
+        text("Project scripts, run before the scripts of every request:").size(14),
+        Space::with_height(5),
+        script_editors_block(&page.project_scripts, HomeEventMessage::OnProjectScriptAction),
     ])
     .align_y(iced::alignment::Vertical::Center)
     .padding(10)
     .style(AppContainer::Rounded)
-    .width(Length::Fill)]
+    .width(Length::Fill))
     .padding(10)
     .into()
 }
+
+fn export_row(notice: Option<String>) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let mut export_row = Row::new()
+        .push(text("Export as:").size(14))
+        .push(Space::with_width(10));
+
+    for format in [ExportFormat::Postman, ExportFormat::OpenApi, ExportFormat::Hurl] {
+        export_row = export_row
+            .push(
+                button(text(format.to_string()).size(14))
+                    .padding(Padding::from([5, 10]))
+                    .style(AppBtn::Secondary)
+                    .on_press(HomeEventMessage::ExportProject(format)),
+            )
+            .push(Space::with_width(5));
+    }
+
+    if let Some(notice) = notice {
+        export_row = export_row
+            .push(Space::with_width(5))
+            .push(text(notice).size(14));
+    }
+
+    export_row.align_items(iced::Alignment::Center).into()
+}
+
+fn import_report_block(
+    report: &Result<ImportReport, String>,
+    responses: usize,
+) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let (summary, color) = match report {
+        Ok(report) => (report.to_string(), AppColor::GREEN),
+        Err(err) => (err.clone(), AppColor::RED),
+    };
+
+    let mut actions = row![
+        text(summary).size(14).style(Into::<Color>::into(color)),
+        Space::with_width(Length::Fill),
+    ]
+    .align_items(iced::Alignment::Center);
+
+    if responses > 0 {
+        actions = actions.push(
+            button(text(format!("Keep {} recorded responses", responses)).size(14))
+                .padding(Padding::from([5, 10]))
+                .style(AppBtn::Primary)
+                .on_press(HomeEventMessage::KeepImportedResponses),
+        );
+        actions = actions.push(Space::with_width(5));
+    }
+
+    let mut items = Column::new().spacing(2).push(
+        actions.push(
+            button(text("Dismiss").size(14))
+                .padding(Padding::from([5, 10]))
+                .style(AppBtn::Secondary)
+                .on_press(HomeEventMessage::DismissImportReport),
+        ),
+    );
+
+    if let Ok(report) = report {
+        for unsupported in report.unsupported.iter() {
+            items = items.push(
+                text(format!("Not imported: {}", unsupported))
+                    .size(12)
+                    .style(Into::<Color>::into(AppColor::YELLOW)),
+            );
+        }
+    }
+
+    container(items)
+        .padding(10)
+        .style(AppContainer::Rounded)
+        .width(Length::Fill)
+        .into()
+}
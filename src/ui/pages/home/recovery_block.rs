@@ -0,0 +1,64 @@
+use chrono::{DateTime, Local};
+use iced::{
+    widget::{button, column, container, row, text, Column, Space},
+    Color, Element, Length, Padding,
+};
+
+use crate::{
+    ui::app_theme::{AppBtn, AppColor, AppContainer},
+    utils::db::storage::Backup,
+};
+
+use super::HomeEventMessage;
+
+/// Shown when the projects file could not be read, nothing is saved until it is
+/// restored from a backup or set aside.
+pub fn recovery_block<'a>(error: &str, backups: Vec<Backup>) -> Element<'a, HomeEventMessage> {
+    let restore_text = match backups.is_empty() {
+        true => "No backups were found.",
+        false => "Restore a backup, the unreadable file is kept next to it:",
+    };
+
+    let mut items = Column::new().spacing(5);
+    for backup in backups {
+        let modified = DateTime::<Local>::from(backup.modified);
+        items = items.push(
+            row![
+                text(format!("Backup {}", backup.index)).size(14),
+                Space::with_width(10),
+                text(modified.format("%Y-%m-%d %H:%M:%S").to_string()).size(14),
+                Space::with_width(Length::Fill),
+                button("Restore")
+                    .padding(Padding::from([3, 10]))
+                    .style(AppBtn::Primary)
+                    .on_press(HomeEventMessage::RestoreBackup(backup.path.clone())),
+            ]
+            .align_items(iced::Alignment::Center),
+        );
+    }
+
+    container(
+        column![
+            text(format!("Projects file could not be read: {}", error))
+                .size(14)
+                .style(Into::<Color>::into(AppColor::RED)),
+            text("Changes are not saved until the file is restored or set aside.").size(14),
+            Space::with_height(5),
+            text(restore_text).size(14),
+            items,
+            Space::with_height(5),
+            row![
+                Space::with_width(Length::Fill),
+                button("Start over")
+                    .padding(Padding::from([3, 10]))
+                    .style(AppBtn::Secondary)
+                    .on_press(HomeEventMessage::DiscardUnreadableProjects),
+            ],
+        ]
+        .spacing(5),
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .style(AppContainer::Rounded)
+    .into()
+}
@@ -7,9 +7,13 @@ use crate::create_tabs;
 use crate::ui::app_theme::{AppContainer, AppInput};
 use crate::utils::request::RequestUrl;
 
+use super::code_block::code_block;
 use super::events::RequestEvent;
+use super::history_block::history_block;
+use super::load_test_block::load_test_block;
 use super::request_tabs_block::request_tab_container;
 use super::response_tabs_block::response_tab_container;
+use super::scripts_block::scripts_block;
 use super::url_input_bar::url_input_bar;
 use super::{HomeEventMessage, HomePage};
 
@@ -18,17 +22,35 @@ pub fn request_and_response_card<'a>(page: &'a HomePage) -> Element<'a, HomeEven
     let (_, pending_request) = page.pending_request();
 
     if let Some(tab) = page.request_tabs.get_active() {
-        conditional_container = conditional_container.push(request_tab_container(
-            &tab.label,
-            &pending_request,
-            &page.request_body_context,
-        ));
+        conditional_container = conditional_container.push(match tab.label.as_str() {
+            "History" => history_block(
+                page.history.entries(&pending_request.id),
+                &page.history_compare,
+            ),
+            "Scripts" => scripts_block(&page.request_scripts, &page.script_console),
+            "Code" => code_block(page),
+            "Load" => load_test_block(page),
+            label => request_tab_container(
+                label,
+                &pending_request,
+                &page.request_body_context,
+                page.db.active_env(),
+                &page.extraction_results,
+            )
+            .into(),
+        });
     }
 
     if let Some(response) = page.response.clone() {
         conditional_container = conditional_container
             .push(Space::with_height(10))
-            .push(response_tab_container(response, &page.response_tabs));
+            .push(response_tab_container(
+                response,
+                &page.response_tabs,
+                pending_request.response_filter.clone().unwrap_or_default(),
+                page.response_syntax,
+                &page.test_results,
+            ));
     }
 
     column![
@@ -0,0 +1,146 @@
+use iced::{
+    widget::{button, column, container, pick_list, row, scrollable, text, Column, Space},
+    Color, Element, Length, Padding, Renderer, Theme,
+};
+
+use crate::{
+    ui::app_theme::{AppBtn, AppColor, AppContainer, AppSelect},
+    utils::db::request_log::{LogRetention, RequestLogEntry},
+};
+
+use super::HomeEventMessage;
+
+pub fn request_log_block<'a>(
+    entry: Option<&RequestLogEntry>,
+    retention: LogRetention,
+    is_requesting: bool,
+    notice: Option<String>,
+) -> Element<'a, HomeEventMessage, Theme, Renderer> {
+    let clear_row = row![
+        text("Clear history").size(14),
+        Space::with_width(10),
+        pick_list(
+            LogRetention::ALL,
+            Some(retention),
+            HomeEventMessage::RequestLogRetentionChange
+        )
+        .padding(2)
+        .text_size(14)
+        .style(AppSelect::Card),
+        Space::with_width(5),
+        button("Clear")
+            .padding(Padding::from([3, 10]))
+            .style(AppBtn::Secondary)
+            .on_press(HomeEventMessage::ClearRequestLog),
+        Space::with_width(Length::Fill),
+        button("Export HAR")
+            .padding(Padding::from([3, 10]))
+            .style(AppBtn::Secondary)
+            .on_press(HomeEventMessage::ExportRequestLog),
+    ]
+    .align_items(iced::Alignment::Center);
+
+    let mut content = column![clear_row, Space::with_height(10)];
+    if let Some(notice) = notice {
+        content = content
+            .push(text(notice).size(14))
+            .push(Space::with_height(10));
+    }
+
+    let Some(entry) = entry else {
+        return container(content.push(text("Select a request from the history.")))
+            .padding(10)
+            .width(Length::Fill)
+            .into();
+    };
+
+    let mut rerun = button(if is_requesting { "Sending" } else { "Re-run" })
+        .padding(Padding::from([5, 10]))
+        .style(AppBtn::Primary);
+
+    if !is_requesting {
+        rerun = rerun.on_press(HomeEventMessage::RerunRequestLogEntry(entry.id));
+    }
+
+    let outcome = match (entry.status_code, &entry.error) {
+        (Some(code), _) => text(format!(
+            "Status {} in {} ms",
+            code,
+            entry.duration_ms.unwrap_or_default()
+        ))
+        .style(Into::<Color>::into(if code < 400 {
+            AppColor::GREEN
+        } else {
+            AppColor::RED
+        })),
+        (None, error) => text(format!("Failed, {}", error.clone().unwrap_or_default()))
+            .style(Into::<Color>::into(AppColor::RED)),
+    };
+
+    let mut headers = Column::new().spacing(2);
+    for (name, value) in entry.request.headers.iter() {
+        headers = headers.push(
+            row![
+                text(name).size(14).width(Length::FillPortion(1)),
+                text(value).size(14).width(Length::FillPortion(2)),
+            ]
+            .spacing(10),
+        );
+    }
+
+    content = content
+        .push(
+            container(
+                column![
+                    row![
+                        text(entry.request.method.clone()),
+                        Space::with_width(10),
+                        text(&entry.request.url).width(Length::Fill),
+                        rerun,
+                        Space::with_width(5),
+                        button("Open as new request")
+                            .padding(Padding::from([5, 10]))
+                            .style(AppBtn::Secondary)
+                            .on_press(HomeEventMessage::OpenRequestLogEntry(entry.id)),
+                    ]
+                    .align_items(iced::Alignment::Center),
+                    Space::with_height(10),
+                    row![
+                        outcome,
+                        Space::with_width(Length::Fill),
+                        text(format!(
+                            "{} - {}",
+                            entry.local_time(),
+                            entry.env_name.clone().unwrap_or("No env".into())
+                        ))
+                        .size(14),
+                    ],
+                ]
+                .padding(10),
+            )
+            .style(AppContainer::Rounded),
+        )
+        .push(Space::with_height(10))
+        .push(text("Headers"))
+        .push(Space::with_height(5))
+        .push(
+            container(headers)
+                .padding(10)
+                .width(Length::Fill)
+                .style(AppContainer::Rounded),
+        )
+        .push(Space::with_height(10))
+        .push(text("Body"))
+        .push(Space::with_height(5))
+        .push(
+            container(text(&entry.request.body).size(14))
+                .padding(10)
+                .width(Length::Fill)
+                .style(AppContainer::Rounded),
+        );
+
+    container(scrollable(content).height(Length::Fill))
+        .padding(10)
+        .width(Length::Fill)
+        .into()
+}
@@ -1,19 +1,30 @@
 use iced::{
     widget::{
-        column, container, row, text,
+        button, column, container, pick_list, row, svg,
+        svg::Handle,
+        text,
         text_editor::{self, Content},
-        text_input, Column, Space, TextEditor,
+        text_input, Column, Row, Space, TextEditor,
     },
-    Element, Length, Padding, Renderer, Theme,
+    Color, Element, Length, Padding, Renderer, Theme,
 };
 
 use crate::{
+    constants::TRASH_SVG,
     create_tabs,
     ui::{
-        app_theme::{AppColor, AppContainer, AppInput, FalconTextarea},
+        app_theme::{AppBtn, AppColor, AppContainer, AppInput, AppSelect, FalconTextarea},
         elements::tabs::Tabs,
+        tokenizer::json::JsonHighlighter,
+    },
+    utils::{
+        db::env::Env,
+        request::{
+            assertion::{Assertion, AssertionKind},
+            extraction::{Extraction, ExtractionResult, ExtractionSource},
+            json_body, FalconAuthorization, FlBody, PendingRequest, PendingRequestItem,
+        },
     },
-    utils::request::{FalconAuthorization, FlBody, PendingRequest, PendingRequestItem},
 };
 
 use super::{
@@ -24,6 +35,8 @@ pub fn request_tab_container<'a>(
     label: &str,
     pending_request: &PendingRequest,
     body_context: &'a text_editor::Content,
+    env: Option<Env>,
+    extraction_results: &[ExtractionResult],
 ) -> Column<'a, HomeEventMessage, Theme, Renderer> {
     let mut container_columns = Column::new();
 
@@ -37,7 +50,7 @@ pub fn request_tab_container<'a>(
                 build_key_value_input_columns(&pending_request.headers, PendingRequestItem::Header);
         }
         "Body" => {
-            container_columns = container_columns.push(body_block(&pending_request, &body_context));
+            container_columns = container_columns.push(body_block(&pending_request, &body_context, env));
         }
         "Authorization" => {
             container_columns = container_columns.push(authorization_block(pending_request));
@@ -46,6 +59,15 @@ pub fn request_tab_container<'a>(
             container_columns =
                 build_key_value_input_columns(&pending_request.cookies, PendingRequestItem::Cookie);
         }
+        "Tests" => {
+            container_columns = container_columns.push(assertions_block(&pending_request.assertions));
+        }
+        "Extract" => {
+            container_columns = container_columns.push(extractions_block(
+                &pending_request.extractions,
+                extraction_results,
+            ));
+        }
         _ => (),
     };
 
@@ -79,7 +101,13 @@ fn build_key_value_input_columns(
 fn authorization_block<'a>(req: &PendingRequest) -> Element<'a, HomeEventMessage, Theme, Renderer> {
     column![
         create_tabs!(
-            Tabs::new(vec!["Bearer"], "Bearer"),
+            Tabs::new(
+                vec!["Bearer", "Basic"],
+                match req.authorization {
+                    FalconAuthorization::Bearer { .. } => "Bearer",
+                    FalconAuthorization::Basic { .. } => "Basic",
+                }
+            ),
             HomeEventMessage::OnAuthorizationTabChange,
             None,
             None
@@ -125,6 +153,36 @@ fn authorization_block<'a>(req: &PendingRequest) -> Element<'a, HomeEventMessage
                         }),
                 ]
             }
+            FalconAuthorization::Basic { username, password } => {
+                let username_a = username.clone();
+                let password_a = password.clone();
+
+                column![
+                    container(text("Username")).padding(Padding::from([10, 0])),
+                    text_input("Username", &username)
+                        .width(Length::Fill)
+                        .style(AppInput)
+                        .on_input(move |u| {
+                            RequestEvent::AuthorizationInput(FalconAuthorization::Basic {
+                                username: u,
+                                password: password_a.clone(),
+                            })
+                            .into()
+                        }),
+                    container(text("Password")).padding(Padding::from([10, 0])),
+                    text_input("Password", &password)
+                        .width(Length::Fill)
+                        .style(AppInput)
+                        .secure(true)
+                        .on_input(move |p| {
+                            RequestEvent::AuthorizationInput(FalconAuthorization::Basic {
+                                username: username_a.clone(),
+                                password: p,
+                            })
+                            .into()
+                        }),
+                ]
+            }
         })
         .style(AppContainer::Rounded)
         .padding(10),
@@ -135,25 +193,218 @@ fn authorization_block<'a>(req: &PendingRequest) -> Element<'a, HomeEventMessage
 fn body_block<'a>(
     req: &PendingRequest,
     body: &'a Content,
+    env: Option<Env>,
 ) -> Element<'a, HomeEventMessage, Theme, Renderer> {
-    column![
-        create_tabs!(
+    let raw_body = req.body.to_string();
+
+    let tabs_row = Row::new()
+        .push(create_tabs!(
             Tabs::new(vec!["application/json"], "application/json"),
             HomeEventMessage::OnBodyTabChange,
             None,
             None
-        ),
+        ))
+        .push(Space::with_width(Length::Fill))
+        .push(
+            button("Prettify")
+                .padding(Padding::from([5, 10]))
+                .style(AppBtn::Secondary)
+                .on_press(HomeEventMessage::PrettifyRequestBody),
+        )
+        .push(Space::with_width(5))
+        .push(
+            button("Minify")
+                .padding(Padding::from([5, 10]))
+                .style(AppBtn::Secondary)
+                .on_press(HomeEventMessage::MinifyRequestBody),
+        )
+        .align_items(iced::Alignment::Center);
+
+    let mut body_column = column![
+        tabs_row,
         Space::with_height(10),
         container(match req.body.clone() {
             FlBody::ApplicationJson(_) => {
                 TextEditor::new(&body)
                     .height(Length::Fill)
                     .on_action(HomeEventMessage::OnRequestBodyContextAction)
+                    .highlight::<JsonHighlighter>((), JsonHighlighter::format)
                     .style(FalconTextarea)
             }
         })
         .style(AppContainer::Rounded)
         .padding(10),
-    ]
-    .into()
+    ];
+
+    match json_body::validate(&raw_body) {
+        Err(err) => {
+            body_column = body_column
+                .push(Space::with_height(5))
+                .push(text(err.to_string()).style(Into::<Color>::into(AppColor::RED)));
+        }
+        Ok(_) => {
+            if let Some(warning) =
+                env.and_then(|env| json_body::placeholder_warning(&raw_body, &env))
+            {
+                body_column = body_column
+                    .push(Space::with_height(5))
+                    .push(text(warning).style(Into::<Color>::into(AppColor::YELLOW)));
+            }
+        }
+    }
+
+    body_column.into()
+}
+
+fn assertions_block<'a>(assertions: &[Assertion]) -> Element<'a, HomeEventMessage, Theme, Renderer> {
+    let mut rows = Column::new();
+
+    for (index, assertion) in assertions.iter().enumerate() {
+        let mut row = Row::new()
+            .push(
+                pick_list(AssertionKind::ALL, Some(assertion.kind), move |kind| {
+                    RequestEvent::AssertionKindSelect(index, kind).into()
+                })
+                .padding(5)
+                .text_size(14)
+                .width(200)
+                .style(AppSelect::Card),
+            )
+            .push(Space::with_width(10));
+
+        if let Some(hint) = assertion.kind.target_hint() {
+            row = row
+                .push(
+                    text_input(hint, &assertion.target)
+                        .on_input(move |target| {
+                            RequestEvent::AssertionTargetInput(index, target).into()
+                        })
+                        .style(AppInput)
+                        .width(200),
+                )
+                .push(Space::with_width(10));
+        }
+
+        match assertion.kind.expected_hint() {
+            Some(hint) => {
+                row = row.push(
+                    text_input(hint, &assertion.expected)
+                        .on_input(move |expected| {
+                            RequestEvent::AssertionExpectedInput(index, expected).into()
+                        })
+                        .style(AppInput),
+                );
+            }
+            None => row = row.push(Space::with_width(Length::Fill)),
+        }
+
+        rows = rows.push(
+            container(
+                container(
+                    row.push(Space::with_width(10))
+                        .push(
+                            button(svg(Handle::from_memory(TRASH_SVG)).width(20).height(20))
+                                .style(AppBtn::Basic)
+                                .padding(5)
+                                .on_press(RequestEvent::RemoveAssertion(index).into()),
+                        )
+                        .align_items(iced::Alignment::Center),
+                )
+                .padding(10)
+                .width(Length::Fill)
+                .style(AppContainer::Rounded),
+            )
+            .padding(Padding::from([2, 0])),
+        );
+    }
+
+    rows.push(Space::with_height(5))
+        .push(
+            button("Add assertion")
+                .padding(Padding::from([5, 10]))
+                .style(AppBtn::Secondary)
+                .on_press(RequestEvent::AddAssertion.into()),
+        )
+        .into()
+}
+
+fn extractions_block<'a>(
+    extractions: &[Extraction],
+    results: &[ExtractionResult],
+) -> Element<'a, HomeEventMessage, Theme, Renderer> {
+    let mut rows = Column::new().push(
+        text("Values are stored in the active environment after every successful response.")
+            .size(14),
+    );
+
+    for (index, extraction) in extractions.iter().enumerate() {
+        let row = row![
+            pick_list(
+                ExtractionSource::ALL,
+                Some(extraction.source),
+                move |source| RequestEvent::ExtractionSourceSelect(index, source).into()
+            )
+            .padding(5)
+            .text_size(14)
+            .width(200)
+            .style(AppSelect::Card),
+            Space::with_width(10),
+            text_input(extraction.source.hint(), &extraction.expression)
+                .on_input(move |expression| {
+                    RequestEvent::ExtractionExpressionInput(index, expression).into()
+                })
+                .style(AppInput),
+            Space::with_width(10),
+            text("into").size(14),
+            Space::with_width(10),
+            text_input("ENV_VARIABLE", &extraction.variable)
+                .on_input(move |variable| {
+                    RequestEvent::ExtractionVariableInput(index, variable).into()
+                })
+                .style(AppInput)
+                .width(200),
+            Space::with_width(10),
+            button(svg(Handle::from_memory(TRASH_SVG)).width(20).height(20))
+                .style(AppBtn::Basic)
+                .padding(5)
+                .on_press(RequestEvent::RemoveExtraction(index).into()),
+        ]
+        .align_items(iced::Alignment::Center);
+
+        rows = rows.push(
+            container(
+                container(row)
+                    .padding(10)
+                    .width(Length::Fill)
+                    .style(AppContainer::Rounded),
+            )
+            .padding(Padding::from([2, 0])),
+        );
+    }
+
+    rows = rows.push(Space::with_height(5)).push(
+        button("Add extraction")
+            .padding(Padding::from([5, 10]))
+            .style(AppBtn::Secondary)
+            .on_press(RequestEvent::AddExtraction.into()),
+    );
+
+    if !results.is_empty() {
+        rows = rows.push(Space::with_height(10)).push(text("Last response").size(14));
+    }
+
+    for result in results {
+        let color = match result.value {
+            Ok(_) => AppColor::GREEN,
+            Err(_) => AppColor::RED,
+        };
+
+        rows = rows.push(
+            text(result.to_string())
+                .size(14)
+                .style(Into::<Color>::into(color)),
+        );
+    }
+
+    rows.into()
 }
@@ -2,28 +2,40 @@ use chrono::{DateTime, Utc};
 use iced::{
     widget::svg::Handle,
     widget::{
-        button, column, container, row, scrollable, svg, text, Column, Container, Row, Space,
+        button, column, container, pick_list, row, scrollable, svg, text, text_input, Column,
+        Container, Row, Space,
     },
-    Length, Renderer, Theme,
+    Color, Length, Padding, Renderer, Theme,
 };
 use reqwest::header;
+use serde_json::Value;
 
 use crate::{
     constants::DUPLICATE_SVG,
     create_tabs,
     ui::{
-        app_theme::{AppBtn, AppContainer},
+        app_theme::{AppBtn, AppColor, AppContainer, AppInput, AppSelect},
         elements::tabs::Tabs,
-        tokenizer::json::tokenize,
+        tokenizer::{
+            javascript::{self, ScriptLanguage},
+            json::{tokenize, tokenize_value},
+            xml, yaml, ResponseSyntax,
+        },
+    },
+    utils::{
+        json_query::JsonQuery,
+        request::{assertion::AssertionResult, FalconResponse},
     },
-    utils::request::FalconResponse,
 };
 
-use super::HomeEventMessage;
+use super::{events::RequestEvent, HomeEventMessage};
 
 pub fn response_tab_container(
     response: FalconResponse,
     tabs: &Tabs,
+    filter: String,
+    syntax: ResponseSyntax,
+    test_results: &[AssertionResult],
 ) -> Container<'static, HomeEventMessage, Theme, Renderer> {
     let mut response_tab = Column::new()
         .push(row![
@@ -54,32 +66,78 @@ pub fn response_tab_container(
     if let Some(tab) = tabs.get_active() {
         match tab.label.as_str() {
             "Body" => {
-                let is_json = response
+                let content_type = response
                     .headers
                     .get(header::CONTENT_TYPE)
-                    .is_some_and(|h| h.to_str().is_ok_and(|h| h.contains("application/json")));
-
-                if is_json {
-                    match tokenize(&response.body) {
-                        Ok(lines) => {
-                            tab_container = tab_container.push(
-                                Column::from_vec(
-                                    lines.into_iter().map(|line| line.into()).collect(),
-                                )
-                                .width(Length::Fill),
-                            );
+                    .and_then(|h| h.to_str().ok());
+
+                match syntax.resolve(content_type) {
+                    ResponseSyntax::Json => {
+                        tab_container = tab_container.push(filter_input_row(&filter));
+
+                        let lines = if filter.trim().is_empty() {
+                            tokenize(&response.body)
+                        } else {
+                            JsonQuery::from(filter.as_str())
+                                .evaluate_str(&response.body)
+                                .map(|value| tokenize_value(&value.unwrap_or(Value::Null)))
+                        };
+
+                        match lines {
+                            Ok(lines) => {
+                                tab_container = tab_container.push(
+                                    Column::from_vec(
+                                        lines.into_iter().map(|line| line.into()).collect(),
+                                    )
+                                    .width(Length::Fill),
+                                );
+                            }
+                            Err(err) => {
+                                tab_container = tab_container.push(
+                                    container(text(err).style(Into::<Color>::into(AppColor::RED)))
+                                        .padding(10)
+                                        .width(Length::Fill),
+                                );
+                            }
                         }
-                        Err(err) => println!("{}", err),
                     }
-                } else {
-                    tab_container = tab_container.push(
-                        container(text(&response.body))
-                            .padding(10)
-                            .width(Length::Fill),
-                    );
+                    ResponseSyntax::Text | ResponseSyntax::Auto => {
+                        tab_container = tab_container.push(
+                            container(text(&response.body))
+                                .padding(10)
+                                .width(Length::Fill),
+                        );
+                    }
+                    syntax => {
+                        let lines = match syntax {
+                            ResponseSyntax::Xml => xml::tokenize(&response.body, false),
+                            ResponseSyntax::Html => xml::tokenize(&response.body, true),
+                            ResponseSyntax::Yaml => yaml::tokenize(&response.body),
+                            ResponseSyntax::Css => {
+                                javascript::tokenize(&response.body, ScriptLanguage::Css)
+                            }
+                            _ => javascript::tokenize(&response.body, ScriptLanguage::JavaScript),
+                        };
+
+                        tab_container = tab_container.push(
+                            Column::from_vec(lines.into_iter().map(|line| line.into()).collect())
+                                .width(Length::Fill),
+                        );
+                    }
                 }
 
                 tabs_row = tabs_row.push(Space::with_width(Length::Fill)).push(
+                    pick_list(
+                        ResponseSyntax::options(content_type),
+                        Some(syntax.into_option(content_type)),
+                        |option| HomeEventMessage::OnResponseSyntaxChange(option.value),
+                    )
+                    .padding(2)
+                    .text_size(14)
+                    .style(AppSelect::Card),
+                );
+
+                tabs_row = tabs_row.push(Space::with_width(5)).push(
                     button(svg(Handle::from_memory(DUPLICATE_SVG)).width(20).height(20))
                         .padding(5)
                         .style(AppBtn::Basic)
@@ -154,6 +212,44 @@ pub fn response_tab_container(
                     );
                 }
             }
+            "Tests" => {
+                if test_results.is_empty() {
+                    tab_container = tab_container
+                        .push(container(text("No assertions for this request.")).padding(5));
+                } else {
+                    let passed = test_results.iter().filter(|r| r.passed).count();
+
+                    tab_container = tab_container.push(
+                        container(text(format!("{} of {} passed", passed, test_results.len())))
+                            .padding(5),
+                    );
+                }
+
+                for result in test_results {
+                    let (label, color) = if result.passed {
+                        ("PASS", AppColor::GREEN)
+                    } else {
+                        ("FAIL", AppColor::RED)
+                    };
+
+                    tab_container = tab_container.push(
+                        container(column![
+                            container(row![
+                                text(label).style(Into::<Color>::into(color)).width(50),
+                                text(result.name.clone()).width(Length::Fill),
+                                text(result.message.clone()).size(14),
+                            ])
+                            .width(Length::Fill)
+                            .padding(5),
+                            container("")
+                                .width(Length::Fill)
+                                .height(1)
+                                .style(AppContainer::Hr)
+                        ])
+                        .padding(5),
+                    );
+                }
+            }
             _ => {}
         };
     };
@@ -168,3 +264,17 @@ pub fn response_tab_container(
         .width(Length::Fill)
         .style(AppContainer::Rounded)
 }
+
+fn filter_input_row(filter: &str) -> Container<'static, HomeEventMessage, Theme, Renderer> {
+    container(
+        row![
+            text("Filter: ").size(14),
+            text_input("$.items[0].id (JSONPath) or items[0].id (JMESPath)", filter)
+                .style(AppInput)
+                .width(Length::Fill)
+                .on_input(|filter| RequestEvent::ResponseFilterInput(filter).into()),
+        ]
+        .align_items(iced::Alignment::Center),
+    )
+    .padding(Padding::from([5, 0]))
+}
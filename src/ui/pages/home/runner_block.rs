@@ -0,0 +1,317 @@
+use iced::{
+    widget::{
+        button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Column,
+        Space,
+    },
+    Color, Element, Length, Padding, Renderer, Theme,
+};
+
+use crate::{
+    ui::app_theme::{AppBtn, AppColor, AppContainer, AppInput, AppSelect},
+    utils::runner::{data_file::DataFile, RunResult, RunScope},
+};
+
+use super::{HomeEventMessage, HomePage};
+
+/// Rows shown in the data file preview.
+const PREVIEW_ROWS: usize = 5;
+
+pub fn runner_block(page: &HomePage) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let project = page.db.active();
+    let is_running = page
+        .collection_run
+        .as_ref()
+        .is_some_and(|run| !run.is_finished());
+
+    let mut scopes = vec![RunScope::Project];
+    if let Some(project) = project.as_ref() {
+        scopes.extend(
+            project
+                .folders()
+                .into_iter()
+                .map(|folder| RunScope::Folder(folder.clone())),
+        );
+    }
+
+    let action = if is_running {
+        button("Stop")
+            .padding(Padding::from([5, 10]))
+            .style(AppBtn::Secondary)
+            .on_press(HomeEventMessage::StopRun)
+    } else {
+        button("Run")
+            .padding(Padding::from([5, 10]))
+            .style(AppBtn::Primary)
+            .on_press(HomeEventMessage::StartRun)
+    };
+
+    let options_row = row![
+        pick_list(
+            scopes,
+            Some(page.runner_options.scope.clone()),
+            HomeEventMessage::RunnerScopeSelect
+        )
+        .padding(5)
+        .text_size(14)
+        .style(AppSelect::Card),
+        Space::with_width(10),
+        text("Delay (ms)").size(14),
+        Space::with_width(5),
+        text_input("0", &page.runner_delay)
+            .on_input(HomeEventMessage::RunnerDelayInput)
+            .style(AppInput)
+            .width(80),
+        Space::with_width(10),
+        text("Iterations").size(14),
+        Space::with_width(5),
+        match page.runner_data.as_ref() {
+            // one iteration per row of the data file
+            Some(data) => Element::from(text(format!("{} rows", data.rows.len())).size(14)),
+            None => text_input("1", &page.runner_iterations)
+                .on_input(HomeEventMessage::RunnerIterationsInput)
+                .style(AppInput)
+                .width(60)
+                .into(),
+        },
+        Space::with_width(10),
+        checkbox("Stop on failure", page.runner_options.stop_on_failure)
+            .text_size(14)
+            .on_toggle(HomeEventMessage::ToggleRunnerStopOnFailure),
+        Space::with_width(Length::Fill),
+        action,
+    ]
+    .align_items(iced::Alignment::Center);
+
+    let mut content = column![
+        text(format!(
+            "Collection runner: {}",
+            project.map(|p| p.name).unwrap_or_default()
+        )),
+        Space::with_height(10),
+        container(column![
+            options_row,
+            Space::with_height(10),
+            data_file_block(page.runner_data.as_ref()),
+        ])
+        .padding(10)
+        .width(Length::Fill)
+        .style(AppContainer::Rounded),
+        Space::with_height(10),
+    ];
+
+    let Some(run) = page.collection_run.as_ref() else {
+        return container(content.push(text("Run every request of the project or a folder.")))
+            .padding(10)
+            .width(Length::Fill)
+            .into();
+    };
+
+    let summary = run.summary();
+    let progress = if run.is_finished() {
+        text(summary.to_string()).style(Into::<Color>::into(if summary.failed == 0 {
+            AppColor::GREEN
+        } else {
+            AppColor::RED
+        }))
+    } else {
+        text(format!(
+            "Running {} of {}, {} failed so far",
+            run.results.len() + 1,
+            run.total_steps(),
+            summary.failed
+        ))
+    };
+
+    let mut export = button("Export JSON")
+        .padding(Padding::from([5, 10]))
+        .style(AppBtn::Secondary);
+    if !run.results.is_empty() {
+        export = export.on_press(HomeEventMessage::ExportRunReport);
+    }
+
+    content = content.push(
+        row![
+            progress.size(14),
+            Space::with_width(Length::Fill),
+            text(page.runner_notice.clone().unwrap_or_default()).size(14),
+            Space::with_width(10),
+            export,
+        ]
+        .align_items(iced::Alignment::Center),
+    );
+
+    let iterations = run.iterations();
+    if iterations.len() > 1 || run.data_file.is_some() {
+        let mut iterations_column = Column::new().spacing(2);
+
+        for iteration in iterations {
+            iterations_column = iterations_column.push(text(iteration.to_string()).size(14).style(
+                Into::<Color>::into(if iteration.failed == 0 {
+                    AppColor::GREEN
+                } else {
+                    AppColor::RED
+                }),
+            ));
+        }
+
+        content = content.push(Space::with_height(10)).push(
+            container(scrollable(iterations_column).height(Length::Shrink))
+                .max_height(120)
+                .padding(10)
+                .width(Length::Fill)
+                .style(AppContainer::Rounded),
+        );
+    }
+
+    let mut rows = Column::new().push(result_row(
+        ["#", "Request", "Status", "Time", "Tests", "Result"].map(String::from),
+        None,
+    ));
+
+    for result in run.results.iter() {
+        rows = rows.push(results_table_row(result));
+    }
+
+    content
+        .push(Space::with_height(10))
+        .push(
+            container(scrollable(rows).height(Length::Fill))
+                .padding(10)
+                .width(Length::Fill)
+                .style(AppContainer::Rounded),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .into()
+}
+
+fn data_file_block(data: Option<&DataFile>) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let Some(data) = data else {
+        return row![
+            text("Data file: none, iterations use the environment only").size(14),
+            Space::with_width(Length::Fill),
+            button("Choose CSV or JSON")
+                .padding(Padding::from([5, 10]))
+                .style(AppBtn::Secondary)
+                .on_press(HomeEventMessage::PickRunnerDataFile),
+        ]
+        .align_items(iced::Alignment::Center)
+        .into();
+    };
+
+    let mut preview = Column::new().spacing(2).push(
+        data.columns.iter().fold(row![].spacing(10), |row, column| {
+            row.push(text(column.clone()).size(14).width(Length::Fill))
+        }),
+    );
+
+    for index in 0..data.rows.len().min(PREVIEW_ROWS) {
+        preview = preview.push(data.columns.iter().fold(row![].spacing(10), |row, column| {
+            row.push(
+                text(data.value(index, column).to_string())
+                    .size(12)
+                    .width(Length::Fill),
+            )
+        }));
+    }
+
+    if data.rows.len() > PREVIEW_ROWS {
+        preview = preview
+            .push(text(format!("and {} more rows", data.rows.len() - PREVIEW_ROWS)).size(12));
+    }
+
+    column![
+        row![
+            text(format!("Data file: {}", data.file_name())).size(14),
+            Space::with_width(Length::Fill),
+            button("Change")
+                .padding(Padding::from([5, 10]))
+                .style(AppBtn::Secondary)
+                .on_press(HomeEventMessage::PickRunnerDataFile),
+            Space::with_width(5),
+            button("Remove")
+                .padding(Padding::from([5, 10]))
+                .style(AppBtn::Secondary)
+                .on_press(HomeEventMessage::ClearRunnerDataFile),
+        ]
+        .align_items(iced::Alignment::Center),
+        Space::with_height(5),
+        preview,
+    ]
+    .into()
+}
+
+fn results_table_row(result: &RunResult) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let (label, color) = if result.passed() {
+        ("PASS", AppColor::GREEN)
+    } else {
+        ("FAIL", AppColor::RED)
+    };
+
+    let mut details = Column::new().padding(Padding::from([0, 0, 0, 40]));
+
+    if let Some(error) = &result.error {
+        details = details.push(
+            text(error.clone())
+                .size(12)
+                .style(Into::<Color>::into(AppColor::RED)),
+        );
+    }
+
+    for test in result.tests.iter().filter(|test| !test.passed) {
+        details = details.push(
+            text(format!("{}: {}", test.name, test.message))
+                .size(12)
+                .style(Into::<Color>::into(AppColor::RED)),
+        );
+    }
+
+    column![
+        result_row(
+            [
+                result.iteration.to_string(),
+                format!("{} {}", result.method, result.name),
+                result
+                    .status_code
+                    .map(|code| code.to_string())
+                    .unwrap_or("ERR".into()),
+                result
+                    .duration_ms
+                    .map(|ms| format!("{} ms", ms))
+                    .unwrap_or_default(),
+                format!("{}/{}", result.passed_tests(), result.tests.len()),
+                label.to_string(),
+            ],
+            Some(color),
+        ),
+        details,
+        container("")
+            .width(Length::Fill)
+            .height(1)
+            .style(AppContainer::Hr),
+    ]
+    .into()
+}
+
+fn result_row(
+    cells: [String; 6],
+    color: Option<AppColor>,
+) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let [iteration, request, status, time, tests, outcome] = cells;
+    let mut outcome = text(outcome).size(14).width(50);
+
+    if let Some(color) = color {
+        outcome = outcome.style(Into::<Color>::into(color));
+    }
+
+    row![
+        text(iteration).size(14).width(30),
+        text(request).size(14).width(Length::Fill),
+        text(status).size(14).width(60),
+        text(time).size(14).width(80),
+        text(tests).size(14).width(60),
+        outcome,
+    ]
+    .padding(5)
+    .into()
+}
@@ -0,0 +1,113 @@
+use iced::{
+    widget::{
+        button, column, container, row, scrollable, text,
+        text_editor::{Action, Content},
+        Column, Space, TextEditor,
+    },
+    Color, Element, Length, Padding, Renderer, Theme,
+};
+
+use crate::{
+    ui::app_theme::{AppBtn, AppColor, AppContainer, FalconTextarea},
+    utils::script::{ConsoleLevel, ConsoleLine, ScriptStage},
+};
+
+use super::HomeEventMessage;
+
+const SCRIPT_HELP: &str =
+    "Rhai scripts. Use request, response, env.get/env.set, test(name, passed) and console.log.";
+
+/// Editor state of a pre-request and a post-response script.
+pub struct ScriptEditors {
+    pub pre_request: Content,
+    pub post_response: Content,
+}
+
+impl ScriptEditors {
+    pub fn new(pre_request: &str, post_response: &str) -> Self {
+        Self {
+            pre_request: Content::with_text(pre_request),
+            post_response: Content::with_text(post_response),
+        }
+    }
+
+    pub fn get_mut(&mut self, stage: ScriptStage) -> &mut Content {
+        match stage {
+            ScriptStage::PreRequest => &mut self.pre_request,
+            ScriptStage::PostResponse => &mut self.post_response,
+        }
+    }
+
+    fn get(&self, stage: ScriptStage) -> &Content {
+        match stage {
+            ScriptStage::PreRequest => &self.pre_request,
+            ScriptStage::PostResponse => &self.post_response,
+        }
+    }
+}
+
+pub fn script_editors_block<'a>(
+    editors: &'a ScriptEditors,
+    on_action: fn(ScriptStage, Action) -> HomeEventMessage,
+) -> Column<'a, HomeEventMessage, Theme, Renderer> {
+    let mut editors_column = Column::new().push(text(SCRIPT_HELP).size(14));
+
+    for stage in ScriptStage::ALL {
+        editors_column = editors_column
+            .push(Space::with_height(10))
+            .push(text(stage.to_string()))
+            .push(Space::with_height(5))
+            .push(
+                container(
+                    TextEditor::new(editors.get(stage))
+                        .height(120)
+                        .on_action(move |action| on_action(stage, action))
+                        .style(FalconTextarea),
+                )
+                .style(AppContainer::Rounded)
+                .padding(10),
+            );
+    }
+
+    editors_column
+}
+
+pub fn scripts_block<'a>(
+    editors: &'a ScriptEditors,
+    console: &[ConsoleLine],
+) -> Element<'a, HomeEventMessage, Theme, Renderer> {
+    let mut lines = Column::new();
+
+    if console.is_empty() {
+        lines = lines.push(text("Nothing logged yet.").size(14));
+    }
+
+    for line in console {
+        let line_text = text(line.to_string()).size(14);
+
+        lines = lines.push(match line.level {
+            ConsoleLevel::Log => line_text,
+            ConsoleLevel::Warn => line_text.style(Into::<Color>::into(AppColor::YELLOW)),
+            ConsoleLevel::Error => line_text.style(Into::<Color>::into(AppColor::RED)),
+        });
+    }
+
+    column![
+        script_editors_block(editors, HomeEventMessage::OnScriptAction),
+        Space::with_height(10),
+        row![
+            text("Console"),
+            Space::with_width(Length::Fill),
+            button("Clear")
+                .padding(Padding::from([5, 10]))
+                .style(AppBtn::Secondary)
+                .on_press(HomeEventMessage::ClearScriptConsole),
+        ]
+        .align_items(iced::Alignment::Center),
+        Space::with_height(5),
+        container(scrollable(lines).height(120).width(Length::Fill))
+            .style(AppContainer::Rounded)
+            .padding(10),
+    ]
+    .into()
+}
@@ -0,0 +1,117 @@
+use iced::widget::svg::Handle;
+use iced::{
+    widget::{container, mouse_area, row, scrollable, svg, text, text_input, Column, Space},
+    Color, Element, Length, Padding, Renderer, Theme,
+};
+
+use crate::{
+    constants::ANGLE_LEFT_SVG,
+    ui::app_theme::{AppColor, AppContainer, AppInput},
+    utils::helpers::ellipse_text,
+};
+
+use super::{HomeEventMessage, HomePage};
+
+pub fn sidebar_history(page: &HomePage) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let header = Column::new()
+        .push(
+            mouse_area(
+                container(
+                    row![
+                        svg(Handle::from_memory(ANGLE_LEFT_SVG))
+                            .width(15)
+                            .height(15),
+                        Space::with_width(5),
+                        text("Back").vertical_alignment(iced::alignment::Vertical::Center),
+                        Space::with_width(Length::Fill),
+                        text("History").size(14),
+                    ]
+                    .align_items(iced::Alignment::Center),
+                )
+                .align_y(iced::alignment::Vertical::Center)
+                .style(AppContainer::FlatSecondary)
+                .width(Length::Fill)
+                .padding(Padding::from([7, 5])),
+            )
+            .on_press(HomeEventMessage::OnChangePageState(
+                super::HomePageState::Requests,
+            ))
+            .interaction(iced::mouse::Interaction::Pointer),
+        )
+        .push(
+            container(
+                text_input("Search by URL, status (404, 5xx) or date", &page.log_query)
+                    .style(AppInput)
+                    .size(14)
+                    .on_input(HomeEventMessage::RequestLogSearchInput),
+            )
+            .padding(5),
+        )
+        .push(
+            container("")
+                .style(AppContainer::Bg(AppColor::BG_DARKER))
+                .height(1)
+                .width(Length::Fill),
+        );
+
+    let mut items = Column::new();
+
+    for entry in page.request_log.search(&page.log_query) {
+        let status: Element<'static, HomeEventMessage, Theme, Renderer> = match entry.status_code {
+            Some(code) => text(code)
+                .size(14)
+                .style(Into::<Color>::into(if code < 400 {
+                    AppColor::GREEN
+                } else {
+                    AppColor::RED
+                }))
+                .into(),
+            None => text("ERR")
+                .size(14)
+                .style(Into::<Color>::into(AppColor::RED))
+                .into(),
+        };
+
+        items = items
+            .push(
+                mouse_area(
+                    container(
+                        Column::new()
+                            .push(
+                                row![
+                                    text(entry.request.method.clone()).size(14),
+                                    Space::with_width(5),
+                                    text(ellipse_text(&entry.request.url, 28)).size(14),
+                                ]
+                                .align_items(iced::Alignment::Center),
+                            )
+                            .push(
+                                row![
+                                    text(entry.local_time()).size(12),
+                                    Space::with_width(Length::Fill),
+                                    status,
+                                ]
+                                .align_items(iced::Alignment::Center),
+                            ),
+                    )
+                    .style(if page.selected_log_entry == Some(entry.id) {
+                        AppContainer::FlatSecondary
+                    } else {
+                        AppContainer::Flat
+                    })
+                    .width(Length::Fill)
+                    .padding(Padding::from([5, 10])),
+                )
+                .on_press(HomeEventMessage::SelectRequestLogEntry(entry.id))
+                .interaction(iced::mouse::Interaction::Pointer),
+            )
+            .push(
+                container("")
+                    .style(AppContainer::Bg(AppColor::BG_DARKER))
+                    .height(1)
+                    .width(Length::Fill),
+            );
+    }
+
+    header.push(scrollable(items).height(Length::Fill)).into()
+}
@@ -27,6 +27,21 @@ pub fn get_sidebar_projects_items(
                         Space::with_width(5),
                         text("Back").vertical_alignment(iced::alignment::Vertical::Center),
                         Space::with_width(Length::Fill),
+                        button(text("Import").size(14))
+                            .padding(5)
+                            .style(AppBtn::Basic)
+                            .on_press(HomeEventMessage::ImportFile),
+                        Space::with_width(5),
+                        button(text("Import cURL").size(14))
+                            .padding(5)
+                            .style(AppBtn::Basic)
+                            .on_press(HomeEventMessage::ImportCurl),
+                        Space::with_width(5),
+                        button(text("Open .http").size(14))
+                            .padding(5)
+                            .style(AppBtn::Basic)
+                            .on_press(HomeEventMessage::OpenHttpFolder),
+                        Space::with_width(5),
                         button(row![
                             svg(Handle::from_memory(ADD_DOC_SVG)).width(15).height(15),
                             Space::with_width(10),
@@ -48,6 +63,13 @@ pub fn get_sidebar_projects_items(
             ))
             .interaction(iced::mouse::Interaction::Pointer),
         )
+        .push(
+            container("")
+                .style(AppContainer::Bg(AppColor::BG_DARKER))
+                .height(1)
+                .width(Length::Fill),
+        )
+        .push(workspace_row(page))
         .push(
             container("")
                 .style(AppContainer::Bg(AppColor::BG_DARKER))
@@ -74,3 +96,30 @@ pub fn get_sidebar_projects_items(
 
     items.into()
 }
+
+/// Where the projects are stored, with the button to open or close a workspace.
+fn workspace_row(page: &HomePage) -> Element<'static, HomeEventMessage, Theme, Renderer> {
+    let (label, action) = match page.db.workspace() {
+        Some(dir) => (
+            format!("Workspace {}", dir.display()),
+            button(text("Close workspace").size(14)).on_press(HomeEventMessage::CloseWorkspace),
+        ),
+        None => (
+            "App projects".to_string(),
+            button(text("Open workspace").size(14)).on_press(HomeEventMessage::OpenWorkspace),
+        ),
+    };
+
+    container(
+        row![
+            text(label).size(14),
+            Space::with_width(Length::Fill),
+            action.padding(5).style(AppBtn::Basic),
+        ]
+        .align_items(iced::Alignment::Center),
+    )
+    .style(AppContainer::FlatSecondary)
+    .width(Length::Fill)
+    .padding(Padding::from([2, 5]))
+    .into()
+}
@@ -2,7 +2,7 @@ use iced::widget::svg::Handle;
 use iced::widget::{button, container, pick_list, row, svg, text, tooltip, Column, Container};
 use iced::{Element, Length};
 
-use crate::constants::COG_API_SVG;
+use crate::constants::{COG_API_SVG, PLAY_SVG, TIME_PAST_SVG};
 use crate::ui::app_theme::{AppBtn, AppColor, AppContainer, AppSelect};
 use crate::utils::request::{PendingRequest, RequestUrl};
 
@@ -35,6 +35,30 @@ pub fn sidebar_requests(page: &HomePage) -> Element<'static, HomeEventMessage> {
                         .padding(4),
                     tooltip::Position::FollowCursor
                 ),
+                tooltip(
+                    button(svg(Handle::from_memory(TIME_PAST_SVG)).width(15).height(15))
+                        .style(AppBtn::Basic)
+                        .padding(3)
+                        .on_press(HomeEventMessage::OnChangePageState(
+                            super::HomePageState::History
+                        )),
+                    container(text("History").size(10))
+                        .style(AppContainer::Bg(AppColor::BG_DARKEST))
+                        .padding(4),
+                    tooltip::Position::FollowCursor
+                ),
+                tooltip(
+                    button(svg(Handle::from_memory(PLAY_SVG)).width(15).height(15))
+                        .style(AppBtn::Basic)
+                        .padding(3)
+                        .on_press(HomeEventMessage::OnChangePageState(
+                            super::HomePageState::Runner
+                        )),
+                    container(text("Runner").size(10))
+                        .style(AppContainer::Bg(AppColor::BG_DARKEST))
+                        .padding(4),
+                    tooltip::Position::FollowCursor
+                ),
             ])
             .style(AppContainer::FlatSecondary)
             .padding(2),
@@ -68,14 +92,14 @@ pub fn sidebar_requests(page: &HomePage) -> Element<'static, HomeEventMessage> {
                         }
                     },
                     on_click: RequestEvent::Select(req.id).into(),
-                    on_duplicate: RequestEvent::Add(PendingRequest {
+                    on_duplicate: RequestEvent::Add(Box::new(PendingRequest {
                         cookies: req.cookies.clone(),
                         headers: req.headers.clone(),
                         method: req.method.clone(),
                         queries: req.queries.clone(),
                         url: req.url.clone(),
                         ..Default::default()
-                    })
+                    }))
                     .into(),
                     on_remove: RequestEvent::Delete(req.id).into(),
                     method: req.method,
@@ -1 +1,132 @@
-pub mod json;
\ No newline at end of file
+use std::fmt::Display;
+
+use crate::ui::elements::select_options::SelectOption;
+
+pub mod code;
+pub mod javascript;
+pub mod json;
+pub mod xml;
+pub mod yaml;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ResponseSyntax {
+    #[default]
+    Auto,
+    Json,
+    Xml,
+    Html,
+    Yaml,
+    JavaScript,
+    Css,
+    Text,
+}
+
+impl ResponseSyntax {
+    pub const ALL: [ResponseSyntax; 8] = [
+        ResponseSyntax::Auto,
+        ResponseSyntax::Json,
+        ResponseSyntax::Xml,
+        ResponseSyntax::Html,
+        ResponseSyntax::Yaml,
+        ResponseSyntax::JavaScript,
+        ResponseSyntax::Css,
+        ResponseSyntax::Text,
+    ];
+
+    pub fn from_content_type(content_type: &str) -> Self {
+        let content_type = content_type.to_lowercase();
+
+        if content_type.contains("json") {
+            ResponseSyntax::Json
+        } else if content_type.contains("html") {
+            ResponseSyntax::Html
+        } else if content_type.contains("xml") {
+            ResponseSyntax::Xml
+        } else if content_type.contains("yaml") || content_type.contains("yml") {
+            ResponseSyntax::Yaml
+        } else if content_type.contains("javascript") || content_type.contains("ecmascript") {
+            ResponseSyntax::JavaScript
+        } else if content_type.contains("css") {
+            ResponseSyntax::Css
+        } else {
+            ResponseSyntax::Text
+        }
+    }
+
+    /// Resolves `Auto` against the response content type, manual choices are kept.
+    pub fn resolve(self, content_type: Option<&str>) -> Self {
+        match self {
+            ResponseSyntax::Auto => content_type
+                .map(ResponseSyntax::from_content_type)
+                .unwrap_or(ResponseSyntax::Text),
+            syntax => syntax,
+        }
+    }
+
+    pub fn options(content_type: Option<&str>) -> Vec<SelectOption<ResponseSyntax>> {
+        ResponseSyntax::ALL
+            .into_iter()
+            .map(|syntax| syntax.into_option(content_type))
+            .collect()
+    }
+
+    pub fn into_option(self, content_type: Option<&str>) -> SelectOption<ResponseSyntax> {
+        SelectOption {
+            label: match self {
+                ResponseSyntax::Auto => format!("Auto ({})", self.resolve(content_type)),
+                _ => self.to_string(),
+            },
+            value: self,
+        }
+    }
+}
+
+impl Display for ResponseSyntax {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ResponseSyntax::Auto => "Auto",
+            ResponseSyntax::Json => "JSON",
+            ResponseSyntax::Xml => "XML",
+            ResponseSyntax::Html => "HTML",
+            ResponseSyntax::Yaml => "YAML",
+            ResponseSyntax::JavaScript => "JavaScript",
+            ResponseSyntax::Css => "CSS",
+            ResponseSyntax::Text => "Text",
+        };
+
+        write!(f, "{}", label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syntax_from_content_type() {
+        let cases = [
+            ("application/json; charset=utf-8", ResponseSyntax::Json),
+            ("application/problem+json", ResponseSyntax::Json),
+            ("text/html", ResponseSyntax::Html),
+            ("application/xhtml+xml", ResponseSyntax::Html),
+            ("application/soap+xml", ResponseSyntax::Xml),
+            ("application/x-yaml", ResponseSyntax::Yaml),
+            ("text/javascript", ResponseSyntax::JavaScript),
+            ("text/css", ResponseSyntax::Css),
+            ("text/plain", ResponseSyntax::Text),
+        ];
+
+        for (content_type, syntax) in cases {
+            assert_eq!(ResponseSyntax::from_content_type(content_type), syntax);
+        }
+    }
+
+    #[test]
+    fn test_manual_override_wins() {
+        assert_eq!(
+            ResponseSyntax::Yaml.resolve(Some("application/json")),
+            ResponseSyntax::Yaml
+        );
+        assert_eq!(ResponseSyntax::Auto.resolve(None), ResponseSyntax::Text);
+    }
+}
@@ -0,0 +1,105 @@
+use iced::widget::{column, container, text, Row, Space};
+use iced::{Color, Element, Length, Padding};
+
+use crate::ui::app_theme::{AppColor, AppContainer};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CodeTokenKind {
+    Plain,
+    Punctuation,
+    Tag,
+    Attribute,
+    Key,
+    String,
+    Number,
+    Keyword,
+    Comment,
+}
+
+impl CodeTokenKind {
+    pub fn color(&self) -> Option<Color> {
+        match self {
+            CodeTokenKind::Plain | CodeTokenKind::Punctuation => None,
+            CodeTokenKind::Tag => Some(Color::from_rgb8(117, 0, 172)),
+            CodeTokenKind::Attribute => Some(Color::from_rgb8(150, 95, 0)),
+            CodeTokenKind::Key => Some(Color::from_rgb8(0, 80, 160)),
+            CodeTokenKind::String => Some(Color::from_rgb8(0, 125, 0)),
+            CodeTokenKind::Number => Some(Color::from_rgb8(172, 0, 0)),
+            CodeTokenKind::Keyword => Some(Color::from_rgb8(0, 110, 130)),
+            CodeTokenKind::Comment => Some(Color::from_rgb8(125, 125, 125)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeToken {
+    pub kind: CodeTokenKind,
+    pub value: String,
+}
+
+impl CodeToken {
+    pub fn new(kind: CodeTokenKind, value: impl Into<String>) -> Self {
+        Self {
+            kind,
+            value: value.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeLine {
+    pub line: usize,
+    pub indent: usize,
+    pub elements: Vec<CodeToken>,
+}
+
+/// Splits the source into lines, the leading whitespace becomes the line indent.
+pub fn source_lines(source: &str) -> Vec<(usize, &str)> {
+    source
+        .lines()
+        .map(|line| {
+            let content = line.trim_start();
+            let indent = line[..line.len() - content.len()]
+                .chars()
+                .map(|c| if c == '\t' { 4 } else { 1 })
+                .sum();
+
+            (indent, content.trim_end())
+        })
+        .collect()
+}
+
+impl<'a, Message: 'a + Clone> From<CodeLine> for Element<'a, Message> {
+    fn from(line: CodeLine) -> Self {
+        let mut items: Vec<Element<'a, Message>> = vec![
+            // line number
+            container(text(format!("{}", line.line)))
+                .padding(Padding::from([2, 5]))
+                .style(AppContainer::FlatBg(AppColor::BG_DARKER))
+                .into(),
+            // space + indent
+            Space::with_width(Length::Fixed(((line.indent * 2) + 10) as f32)).into(),
+        ];
+
+        for token in line.elements {
+            let elm = text(token.value);
+
+            items.push(match token.kind.color() {
+                Some(color) => elm.style(color).into(),
+                None => elm.into(),
+            });
+        }
+
+        column![
+            // actual line
+            container(Row::from_vec(items).width(Length::Fill)).style(AppContainer::FlatSecondary),
+            // border bottom
+            container("")
+                .height(1)
+                .width(Length::Fill)
+                .style(AppContainer::FlatBg(AppColor::BG_DARKER_12)),
+        ]
+        .width(Length::Fill)
+        .into()
+    }
+}
@@ -0,0 +1,272 @@
+use super::code::{source_lines, CodeLine, CodeToken, CodeTokenKind};
+
+const JS_KEYWORDS: [&str; 42] = [
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "null",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScriptLanguage {
+    JavaScript,
+    Css,
+}
+
+#[derive(Default)]
+struct LexerState {
+    in_block_comment: bool,
+    in_template: bool,
+    css_depth: usize,
+}
+
+/// Highlights JavaScript or CSS sources line by line, keeping the original layout.
+pub fn tokenize(source: &str, language: ScriptLanguage) -> Vec<CodeLine> {
+    let mut state = LexerState::default();
+
+    source_lines(source)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (indent, content))| CodeLine {
+            line: index + 1,
+            indent,
+            elements: tokenize_line(content, language, &mut state),
+        })
+        .collect()
+}
+
+fn tokenize_line(line: &str, language: ScriptLanguage, state: &mut LexerState) -> Vec<CodeToken> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens: Vec<CodeToken> = Vec::new();
+    let mut i = 0;
+
+    let collect = |from: usize, to: usize| chars[from..to].iter().collect::<String>();
+
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if state.in_block_comment {
+            i = skip_block_comment(&chars, i, state);
+            tokens.push(CodeToken::new(CodeTokenKind::Comment, collect(start, i)));
+        } else if state.in_template {
+            let (end, terminated) = skip_string(&chars, i, '`');
+            i = end;
+            state.in_template = !terminated;
+            tokens.push(CodeToken::new(CodeTokenKind::String, collect(start, i)));
+        } else if c == '/' && next == Some('*') {
+            state.in_block_comment = true;
+            i = skip_block_comment(&chars, i + 2, state);
+            tokens.push(CodeToken::new(CodeTokenKind::Comment, collect(start, i)));
+        } else if c == '/' && next == Some('/') && language == ScriptLanguage::JavaScript {
+            tokens.push(CodeToken::new(
+                CodeTokenKind::Comment,
+                collect(start, chars.len()),
+            ));
+            i = chars.len();
+        } else if c == '"' || c == '\'' || c == '`' {
+            let (end, terminated) = skip_string(&chars, i + 1, c);
+            i = end;
+            state.in_template = c == '`' && !terminated;
+            tokens.push(CodeToken::new(CodeTokenKind::String, collect(start, i)));
+        } else if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || "._%".contains(chars[i]))
+            {
+                i += 1;
+            }
+            tokens.push(CodeToken::new(CodeTokenKind::Number, collect(start, i)));
+        } else if is_ident_char(c, language) || is_css_prefixed_word(c, next, language) {
+            i += 1;
+            while i < chars.len() && is_ident_char(chars[i], language) {
+                i += 1;
+            }
+            let word = collect(start, i);
+            let kind = match language {
+                ScriptLanguage::JavaScript => js_word_kind(&word, &chars[i..]),
+                ScriptLanguage::Css => css_word_kind(&word, &chars[i..], state.css_depth),
+            };
+            tokens.push(CodeToken::new(kind, word));
+        } else if c.is_whitespace() {
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            tokens.push(CodeToken::new(CodeTokenKind::Plain, collect(start, i)));
+        } else {
+            match c {
+                '{' => state.css_depth += 1,
+                '}' => state.css_depth = state.css_depth.saturating_sub(1),
+                _ => {}
+            }
+            i += 1;
+            tokens.push(CodeToken::new(CodeTokenKind::Punctuation, c.to_string()));
+        }
+    }
+
+    tokens
+}
+
+fn skip_block_comment(chars: &[char], mut i: usize, state: &mut LexerState) -> usize {
+    while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+        i += 1;
+    }
+    if i < chars.len() {
+        state.in_block_comment = false;
+        return i + 2;
+    }
+    i
+}
+
+/// Returns the index right after the closing quote and whether the string was terminated.
+fn skip_string(chars: &[char], mut i: usize, quote: char) -> (usize, bool) {
+    while i < chars.len() {
+        if chars[i] == '\\' {
+            i += 2;
+            continue;
+        }
+        if chars[i] == quote {
+            return (i + 1, true);
+        }
+        i += 1;
+    }
+    (chars.len(), false)
+}
+
+fn is_ident_char(c: char, language: ScriptLanguage) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$' || (c == '-' && language == ScriptLanguage::Css)
+}
+
+fn is_css_prefixed_word(c: char, next: Option<char>, language: ScriptLanguage) -> bool {
+    language == ScriptLanguage::Css
+        && "@#.!".contains(c)
+        && next.is_some_and(|n| is_ident_char(n, language))
+}
+
+fn next_non_space(rest: &[char]) -> Option<char> {
+    rest.iter().find(|c| !c.is_whitespace()).copied()
+}
+
+fn js_word_kind(word: &str, rest: &[char]) -> CodeTokenKind {
+    if JS_KEYWORDS.contains(&word) {
+        CodeTokenKind::Keyword
+    } else if next_non_space(rest) == Some(':') {
+        CodeTokenKind::Key
+    } else if next_non_space(rest) == Some('(') {
+        CodeTokenKind::Attribute
+    } else {
+        CodeTokenKind::Plain
+    }
+}
+
+fn css_word_kind(word: &str, rest: &[char], depth: usize) -> CodeTokenKind {
+    if word.starts_with('@') || word == "!important" {
+        CodeTokenKind::Keyword
+    } else if depth == 0 || rest.contains(&'{') {
+        if word.starts_with('.') || word.starts_with('#') {
+            CodeTokenKind::Attribute
+        } else {
+            CodeTokenKind::Tag
+        }
+    } else if next_non_space(rest) == Some(':') {
+        CodeTokenKind::Key
+    } else if word.starts_with('#') {
+        CodeTokenKind::Number
+    } else {
+        CodeTokenKind::Plain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(line: &CodeLine) -> Vec<(CodeTokenKind, &str)> {
+        line.elements
+            .iter()
+            .filter(|t| t.kind != CodeTokenKind::Plain || !t.value.trim().is_empty())
+            .map(|t| (t.kind, t.value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_javascript_tokenization() {
+        let js = "/* multi\n line */\nconst total = sum(1.5, 'a'); // done";
+        let lines = tokenize(js, ScriptLanguage::JavaScript);
+
+        assert_eq!(kinds(&lines[0]), vec![(CodeTokenKind::Comment, "/* multi")]);
+        assert_eq!(kinds(&lines[1]), vec![(CodeTokenKind::Comment, "line */")]);
+        assert_eq!(
+            kinds(&lines[2]),
+            vec![
+                (CodeTokenKind::Keyword, "const"),
+                (CodeTokenKind::Plain, "total"),
+                (CodeTokenKind::Punctuation, "="),
+                (CodeTokenKind::Attribute, "sum"),
+                (CodeTokenKind::Punctuation, "("),
+                (CodeTokenKind::Number, "1.5"),
+                (CodeTokenKind::Punctuation, ","),
+                (CodeTokenKind::String, "'a'"),
+                (CodeTokenKind::Punctuation, ")"),
+                (CodeTokenKind::Punctuation, ";"),
+                (CodeTokenKind::Comment, "// done"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_css_tokenization() {
+        let css = "@media print {\n  .btn, a {\n    color: #fff;\n    margin: 0 2px;\n  }\n}";
+        let lines = tokenize(css, ScriptLanguage::Css);
+
+        assert_eq!(kinds(&lines[0])[0], (CodeTokenKind::Keyword, "@media"));
+        assert_eq!(kinds(&lines[1])[0], (CodeTokenKind::Attribute, ".btn"));
+        assert_eq!(
+            kinds(&lines[2]),
+            vec![
+                (CodeTokenKind::Key, "color"),
+                (CodeTokenKind::Punctuation, ":"),
+                (CodeTokenKind::Number, "#fff"),
+                (CodeTokenKind::Punctuation, ";"),
+            ]
+        );
+        assert_eq!(kinds(&lines[3])[3], (CodeTokenKind::Number, "2px"));
+    }
+}
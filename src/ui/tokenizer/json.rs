@@ -3,19 +3,25 @@ use serde_json::Value;
 
 use std::borrow::Cow;
 
+mod json_highlighter;
 mod json_line;
 mod json_token;
 
+pub use json_highlighter::JsonHighlighter;
 pub use json_line::Line;
 pub use json_token::JsonToken;
 
 pub fn tokenize(json: &str) -> Result<Vec<Line>, String> {
     match serde_json::from_str(json) {
-        Ok(value) => Ok(format_tokens(process_value(&value))),
+        Ok(value) => Ok(tokenize_value(&value)),
         Err(err) => Err(err.to_string()),
     }
 }
 
+pub fn tokenize_value(value: &Value) -> Vec<Line> {
+    format_tokens(process_value(value))
+}
+
 fn process_value(value: &Value) -> Vec<JsonToken> {
     let mut tokens = Vec::new();
     match value {
@@ -27,7 +33,9 @@ fn process_value(value: &Value) -> Vec<JsonToken> {
                 tokens.extend(process_value(val));
                 tokens.push(JsonToken::Comma);
             }
-            tokens.pop(); // Remove the last comma
+            if !obj.is_empty() {
+                tokens.pop(); // Remove the last comma
+            }
             tokens.push(JsonToken::EndObject);
         }
         Value::Array(arr) => {
@@ -36,7 +44,9 @@ fn process_value(value: &Value) -> Vec<JsonToken> {
                 tokens.extend(process_value(val));
                 tokens.push(JsonToken::Comma);
             }
-            tokens.pop(); // Remove the last comma
+            if !arr.is_empty() {
+                tokens.pop(); // Remove the last comma
+            }
             tokens.push(JsonToken::EndArray);
         }
         Value::String(s) => tokens.push(JsonToken::String(Cow::Owned(s.clone()))),
@@ -48,15 +58,14 @@ fn process_value(value: &Value) -> Vec<JsonToken> {
 }
 
 fn format_tokens(tokens: Vec<JsonToken>) -> Vec<Line> {
-    let total = tokens.len();
+    let mut tokens = tokens.into_iter().peekable();
     let mut lines = Vec::new();
     let mut current_line_vec = Vec::new();
 
     let mut indent = 0;
     let mut current_line = 1;
-    let mut skip_comma = false;
 
-    for (index, token) in tokens.into_iter().enumerate() {
+    while let Some(token) = tokens.next() {
         match token {
             JsonToken::BeginObject | JsonToken::BeginArray => {
                 current_line_vec.push(token);
@@ -70,18 +79,14 @@ fn format_tokens(tokens: Vec<JsonToken>) -> Vec<Line> {
                 current_line_vec.clear();
             }
             JsonToken::Comma => {
-                if skip_comma {
-                    skip_comma = false;
-                } else {
-                    current_line_vec.push(token);
-                    lines.push(Line {
-                        elements: current_line_vec.clone(),
-                        indent,
-                        line: current_line,
-                    });
-                    current_line_vec.clear();
-                    current_line += 1;
-                }
+                current_line_vec.push(token);
+                lines.push(Line {
+                    elements: current_line_vec.clone(),
+                    indent,
+                    line: current_line,
+                });
+                current_line_vec.clear();
+                current_line += 1;
             }
             JsonToken::EndObject | JsonToken::EndArray => {
                 if !current_line_vec.is_empty() {
@@ -94,12 +99,11 @@ fn format_tokens(tokens: Vec<JsonToken>) -> Vec<Line> {
                     current_line_vec.clear();
                 }
 
-                let elements = if index == total - 1 {
-                    vec![token]
-                } else {
-                    skip_comma = true;
-                    vec![token, JsonToken::Comma]
-                };
+                // the comma separating it from the next sibling ends the same line
+                let mut elements = vec![token];
+                if let Some(comma) = tokens.next_if(|next| matches!(next, JsonToken::Comma)) {
+                    elements.push(comma);
+                }
 
                 indent = indent.checked_sub(4).unwrap_or(indent);
                 lines.push(Line {
@@ -168,7 +172,7 @@ mod tests {
             Line::begin_obj(1, 0),
             Line {
                 elements: vec![
-                    JsonToken::String(Cow::Owned("hello".to_string())),
+                    JsonToken::Key(Cow::Owned("hello".to_string())),
                     JsonToken::Colon,
                     JsonToken::String(Cow::Owned("world".to_string())),
                 ],
@@ -191,7 +195,7 @@ mod tests {
             Line::begin_obj(2, 4),
             Line {
                 elements: vec![
-                    JsonToken::String(Cow::Borrowed("items")),
+                    JsonToken::Key(Cow::Borrowed("items")),
                     JsonToken::Colon,
                     JsonToken::BeginArray,
                 ],
@@ -227,4 +231,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_commas_only_between_siblings() -> Result<(), String> {
+        let json = r#"[{}, [], {"a": {}}]"#;
+        let result = [
+            Line::begin_arr(1, 0),
+            Line::begin_obj(2, 4),
+            Line {
+                elements: vec![JsonToken::EndObject, JsonToken::Comma],
+                line: 3,
+                indent: 4,
+            },
+            Line::begin_arr(4, 4),
+            Line {
+                elements: vec![JsonToken::EndArray, JsonToken::Comma],
+                line: 5,
+                indent: 4,
+            },
+            Line::begin_obj(6, 4),
+            Line {
+                elements: vec![
+                    JsonToken::Key(Cow::Borrowed("a")),
+                    JsonToken::Colon,
+                    JsonToken::BeginObject,
+                ],
+                line: 7,
+                indent: 8,
+            },
+            Line::end_obj(8, 8),
+            Line::end_obj(9, 4),
+            Line::end_arr(10, 0),
+        ];
+
+        assert_eq!(format!("{:?}", tokenize(json)?), format!("{:?}", result));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,183 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use iced::advanced::text::highlighter::{Format, Highlighter};
+use iced::{Font, Theme};
+
+use crate::ui::tokenizer::code::CodeTokenKind;
+
+use super::JsonToken;
+
+/// Line based JSON highlighter for the request body editor.
+/// JSON strings can't span lines, so every line is highlighted on its own.
+pub struct JsonHighlighter {
+    current_line: usize,
+}
+
+impl JsonHighlighter {
+    pub fn format(kind: &CodeTokenKind, _theme: &Theme) -> Format<Font> {
+        Format {
+            color: kind.color(),
+            font: None,
+        }
+    }
+}
+
+impl Highlighter for JsonHighlighter {
+    type Settings = ();
+    type Highlight = CodeTokenKind;
+    type Iterator<'a> = std::vec::IntoIter<(Range<usize>, CodeTokenKind)>;
+
+    fn new(_settings: &Self::Settings) -> Self {
+        Self { current_line: 0 }
+    }
+
+    fn update(&mut self, _new_settings: &Self::Settings) {
+        self.current_line = 0;
+    }
+
+    fn change_line(&mut self, line: usize) {
+        self.current_line = self.current_line.min(line);
+    }
+
+    fn highlight_line(&mut self, line: &str) -> Self::Iterator<'_> {
+        self.current_line += 1;
+
+        tokenize_line(line)
+            .into_iter()
+            .map(|(range, token)| (range, CodeTokenKind::from(&token)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn current_line(&self) -> usize {
+        self.current_line
+    }
+}
+
+impl From<&JsonToken> for CodeTokenKind {
+    fn from(token: &JsonToken) -> Self {
+        match token {
+            JsonToken::String(_) => CodeTokenKind::String,
+            JsonToken::Key(_) => CodeTokenKind::Key,
+            JsonToken::Number(_) => CodeTokenKind::Number,
+            JsonToken::Bool(_) | JsonToken::Null => CodeTokenKind::Keyword,
+            _ => CodeTokenKind::Punctuation,
+        }
+    }
+}
+
+/// Splits a single line of (possibly invalid) JSON into tokens with their byte ranges.
+/// `{{VAR}}` placeholders and unknown characters are left unhighlighted.
+pub fn tokenize_line(line: &str) -> Vec<(Range<usize>, JsonToken)> {
+    let bytes = line.as_bytes();
+    let mut tokens: Vec<(Range<usize>, JsonToken)> = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+
+        match bytes[index] {
+            b'"' => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'"' {
+                    index += if bytes[index] == b'\\' { 2 } else { 1 };
+                }
+                index = (index + 1).min(bytes.len());
+
+                let value = Cow::Owned(line[start..index].trim_matches('"').to_string());
+                let is_key = line[index..].trim_start().starts_with(':');
+
+                tokens.push((
+                    start..index,
+                    if is_key {
+                        JsonToken::Key(value)
+                    } else {
+                        JsonToken::String(value)
+                    },
+                ));
+            }
+            b'{' if bytes.get(index + 1) == Some(&b'{') => {
+                index = line[index..]
+                    .find("}}")
+                    .map(|end| index + end + 2)
+                    .unwrap_or(bytes.len());
+            }
+            b'{' | b'}' | b'[' | b']' | b',' | b':' => {
+                index += 1;
+                let token = match bytes[start] {
+                    b'{' => JsonToken::BeginObject,
+                    b'}' => JsonToken::EndObject,
+                    b'[' => JsonToken::BeginArray,
+                    b']' => JsonToken::EndArray,
+                    b',' => JsonToken::Comma,
+                    _ => JsonToken::Colon,
+                };
+                tokens.push((start..index, token));
+            }
+            b'-' | b'0'..=b'9' => {
+                while index < bytes.len()
+                    && matches!(bytes[index], b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')
+                {
+                    index += 1;
+                }
+                tokens.push((
+                    start..index,
+                    JsonToken::Number(Cow::Owned(line[start..index].to_string())),
+                ));
+            }
+            b if b.is_ascii_alphabetic() => {
+                while index < bytes.len() && bytes[index].is_ascii_alphabetic() {
+                    index += 1;
+                }
+                match &line[start..index] {
+                    "true" => tokens.push((start..index, JsonToken::Bool(true))),
+                    "false" => tokens.push((start..index, JsonToken::Bool(false))),
+                    "null" => tokens.push((start..index, JsonToken::Null)),
+                    _ => {}
+                }
+            }
+            _ => {
+                // skip whitespace and any other (possibly multi byte) character
+                index += line[index..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_line() {
+        let line = r#"  "id": {{USER_ID}}, "tags": ["a\"b", -1.5e3, true, null],"#;
+        let tokens = tokenize_line(line);
+
+        let kinds: Vec<CodeTokenKind> = tokens.iter().map(|(_, t)| t.into()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                CodeTokenKind::Key,
+                CodeTokenKind::Punctuation,
+                CodeTokenKind::Punctuation,
+                CodeTokenKind::Key,
+                CodeTokenKind::Punctuation,
+                CodeTokenKind::Punctuation,
+                CodeTokenKind::String,
+                CodeTokenKind::Punctuation,
+                CodeTokenKind::Number,
+                CodeTokenKind::Punctuation,
+                CodeTokenKind::Keyword,
+                CodeTokenKind::Punctuation,
+                CodeTokenKind::Keyword,
+                CodeTokenKind::Punctuation,
+                CodeTokenKind::Punctuation,
+            ]
+        );
+        assert_eq!(&line[tokens[6].0.clone()], r#""a\"b""#);
+        assert_eq!(&line[tokens[8].0.clone()], "-1.5e3");
+    }
+}
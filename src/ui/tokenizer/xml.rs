@@ -0,0 +1,381 @@
+use super::code::{CodeLine, CodeToken, CodeTokenKind};
+
+const HTML_VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+const HTML_RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+#[derive(Debug, Clone, PartialEq)]
+enum XmlNode {
+    Open {
+        name: String,
+        attributes: Vec<(String, Option<String>)>,
+        self_closing: bool,
+    },
+    Close(String),
+    Text(String),
+    Comment(String),
+    Declaration(String),
+}
+
+/// Pretty prints XML (or HTML when `is_html` is set) into indented, highlighted lines.
+pub fn tokenize(source: &str, is_html: bool) -> Vec<CodeLine> {
+    format_nodes(parse_nodes(source, is_html), is_html)
+}
+
+fn parse_nodes(source: &str, is_html: bool) -> Vec<XmlNode> {
+    let mut nodes = Vec::new();
+    let mut rest = source;
+
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            push_text(&mut nodes, &rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let (node, consumed) = if rest.starts_with("<!--") {
+            take_until(rest, "-->")
+                .map(|(inner, len)| (XmlNode::Comment(inner[4..].to_string()), len))
+        } else if rest.starts_with("<![CDATA[") {
+            take_until(rest, "]]>").map(|(inner, len)| (XmlNode::Text(inner[9..].to_string()), len))
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            take_until(rest, ">").map(|(inner, len)| (XmlNode::Declaration(inner.to_string()), len))
+        } else if let Some(name) = rest.strip_prefix("</") {
+            take_until(name, ">")
+                .map(|(inner, len)| (XmlNode::Close(inner.trim().to_string()), len + 2))
+        } else {
+            parse_open_tag(rest)
+        }
+        .unwrap_or_else(|| (XmlNode::Text(rest.to_string()), rest.len()));
+
+        rest = &rest[consumed..];
+
+        // script and style contents are not markup, keep them as they are
+        if let XmlNode::Open {
+            name,
+            self_closing: false,
+            ..
+        } = &node
+        {
+            let lower = name.to_lowercase();
+            if is_html && HTML_RAW_TEXT_ELEMENTS.contains(&lower.as_str()) {
+                nodes.push(node);
+                let end = find_ignore_ascii_case(rest, &format!("</{}", lower))
+                    .unwrap_or(rest.len());
+                push_text(&mut nodes, &rest[..end]);
+                rest = &rest[end..];
+                continue;
+            }
+        }
+
+        nodes.push(node);
+    }
+
+    nodes
+}
+
+/// Byte offset of the ASCII `needle` in `haystack`, on a char boundary of `haystack`.
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+fn push_text(nodes: &mut Vec<XmlNode>, text: &str) {
+    if !text.trim().is_empty() {
+        nodes.push(XmlNode::Text(text.to_string()));
+    }
+}
+
+/// Returns the content before `end` and the number of bytes consumed including `end`.
+fn take_until<'a>(source: &'a str, end: &str) -> Option<(&'a str, usize)> {
+    source
+        .find(end)
+        .map(|index| (&source[..index], index + end.len()))
+}
+
+fn parse_open_tag(source: &str) -> Option<(XmlNode, usize)> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let mut index = 1;
+
+    let read_while = |index: &mut usize, predicate: &dyn Fn(char) -> bool| -> String {
+        let start = *index;
+        while *index < chars.len() && predicate(chars[*index].1) {
+            *index += 1;
+        }
+        chars[start..*index].iter().map(|(_, c)| c).collect()
+    };
+
+    let name = read_while(&mut index, &|c| !c.is_whitespace() && c != '>' && c != '/');
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut attributes = Vec::new();
+
+    loop {
+        read_while(&mut index, &|c| c.is_whitespace());
+
+        match chars.get(index).map(|(_, c)| *c) {
+            None => return None,
+            Some('>') => {
+                return Some((
+                    XmlNode::Open {
+                        name,
+                        attributes,
+                        self_closing: false,
+                    },
+                    chars[index].0 + 1,
+                ));
+            }
+            Some('/') if chars.get(index + 1).is_some_and(|(_, c)| *c == '>') => {
+                return Some((
+                    XmlNode::Open {
+                        name,
+                        attributes,
+                        self_closing: true,
+                    },
+                    chars[index + 1].0 + 1,
+                ));
+            }
+            Some('/') => index += 1,
+            Some(_) => {
+                let attr = read_while(&mut index, &|c| {
+                    !c.is_whitespace() && c != '=' && c != '>' && c != '/'
+                });
+                read_while(&mut index, &|c| c.is_whitespace());
+
+                if chars.get(index).is_some_and(|(_, c)| *c == '=') {
+                    index += 1;
+                    read_while(&mut index, &|c| c.is_whitespace());
+
+                    let value = match chars.get(index).map(|(_, c)| *c) {
+                        Some(quote) if quote == '"' || quote == '\'' => {
+                            index += 1;
+                            let value = read_while(&mut index, &|c| c != quote);
+                            index += 1;
+                            value
+                        }
+                        _ => read_while(&mut index, &|c| !c.is_whitespace() && c != '>'),
+                    };
+
+                    attributes.push((attr, Some(value)));
+                } else {
+                    attributes.push((attr, None));
+                }
+            }
+        }
+    }
+}
+
+fn format_nodes(nodes: Vec<XmlNode>, is_html: bool) -> Vec<CodeLine> {
+    let mut lines = Vec::new();
+    let mut indent = 0;
+    let mut index = 0;
+
+    let mut push_line = |indent: usize, elements: Vec<CodeToken>| {
+        let line = lines.len() + 1;
+        lines.push(CodeLine {
+            line,
+            indent,
+            elements,
+        });
+    };
+
+    while index < nodes.len() {
+        match &nodes[index] {
+            XmlNode::Open {
+                name,
+                attributes,
+                self_closing,
+            } => {
+                let mut elements = open_tag_tokens(name, attributes, *self_closing);
+                let is_void = is_html && HTML_VOID_ELEMENTS.contains(&name.to_lowercase().as_str());
+
+                if *self_closing || is_void {
+                    push_line(indent, elements);
+                    index += 1;
+                    continue;
+                }
+
+                // keep short text only elements on a single line
+                if let (Some(XmlNode::Text(text)), Some(XmlNode::Close(close))) =
+                    (nodes.get(index + 1), nodes.get(index + 2))
+                {
+                    if close == name && !text.trim().contains('\n') {
+                        elements.push(CodeToken::new(CodeTokenKind::Plain, text.trim()));
+                        elements.extend(close_tag_tokens(close));
+                        push_line(indent, elements);
+                        index += 3;
+                        continue;
+                    }
+                }
+
+                push_line(indent, elements);
+                indent += 4;
+            }
+            XmlNode::Close(name) => {
+                indent = indent.checked_sub(4).unwrap_or(indent);
+                push_line(indent, close_tag_tokens(name));
+            }
+            XmlNode::Text(text) => {
+                for line in text.lines().filter(|l| !l.trim().is_empty()) {
+                    push_line(
+                        indent,
+                        vec![CodeToken::new(CodeTokenKind::Plain, line.trim())],
+                    );
+                }
+            }
+            XmlNode::Comment(comment) => {
+                push_line(
+                    indent,
+                    vec![CodeToken::new(
+                        CodeTokenKind::Comment,
+                        format!("<!--{}-->", comment),
+                    )],
+                );
+            }
+            XmlNode::Declaration(declaration) => {
+                push_line(
+                    indent,
+                    vec![CodeToken::new(
+                        CodeTokenKind::Keyword,
+                        format!("{}>", declaration),
+                    )],
+                );
+            }
+        }
+
+        index += 1;
+    }
+
+    lines
+}
+
+fn open_tag_tokens(
+    name: &str,
+    attributes: &[(String, Option<String>)],
+    self_closing: bool,
+) -> Vec<CodeToken> {
+    let mut tokens = vec![
+        CodeToken::new(CodeTokenKind::Punctuation, "<"),
+        CodeToken::new(CodeTokenKind::Tag, name),
+    ];
+
+    for (attr, value) in attributes {
+        tokens.push(CodeToken::new(CodeTokenKind::Plain, " "));
+        tokens.push(CodeToken::new(CodeTokenKind::Attribute, attr));
+
+        if let Some(value) = value {
+            tokens.push(CodeToken::new(CodeTokenKind::Punctuation, "="));
+            tokens.push(CodeToken::new(
+                CodeTokenKind::String,
+                format!("\"{}\"", value),
+            ));
+        }
+    }
+
+    tokens.push(CodeToken::new(
+        CodeTokenKind::Punctuation,
+        if self_closing { " />" } else { ">" },
+    ));
+
+    tokens
+}
+
+fn close_tag_tokens(name: &str) -> Vec<CodeToken> {
+    vec![
+        CodeToken::new(CodeTokenKind::Punctuation, "</"),
+        CodeToken::new(CodeTokenKind::Tag, name),
+        CodeToken::new(CodeTokenKind::Punctuation, ">"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(lines: &[CodeLine]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                let text: String = line.elements.iter().map(|t| t.value.as_str()).collect();
+                format!("{}{}", " ".repeat(line.indent), text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pretty_prints_xml() {
+        let xml = r#"<?xml version="1.0"?><users><user id="1"><name>Ada</name><!-- admin --></user><empty/></users>"#;
+
+        assert_eq!(
+            render(&tokenize(xml, false)),
+            vec![
+                r#"<?xml version="1.0"?>"#,
+                "<users>",
+                r#"    <user id="1">"#,
+                "        <name>Ada</name>",
+                "        <!-- admin -->",
+                "    </user>",
+                "    <empty />",
+                "</users>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_html_void_and_raw_text_elements() {
+        let html = "<html><head><meta charset=utf-8><script>if (a < b) { go(); }</script></head><body>Hi<br></body></html>";
+
+        assert_eq!(
+            render(&tokenize(html, true)),
+            vec![
+                "<html>",
+                "    <head>",
+                r#"        <meta charset="utf-8">"#,
+                "        <script>if (a < b) { go(); }</script>",
+                "    </head>",
+                "    <body>",
+                "        Hi",
+                "        <br>",
+                "    </body>",
+                "</html>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_raw_text_with_non_ascii() {
+        let html = "<style>/* İstanbul */</STYLE><p>ok</p>";
+
+        assert_eq!(
+            render(&tokenize(html, true)),
+            vec!["<style>", "    /* İstanbul */", "</STYLE>", "<p>ok</p>"]
+        );
+    }
+
+    #[test]
+    fn test_highlights_tag_parts() {
+        let lines = tokenize(r#"<a href="/x">link</a>"#, true);
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            lines[0].elements[1],
+            CodeToken::new(CodeTokenKind::Tag, "a")
+        );
+        assert_eq!(
+            lines[0].elements[3],
+            CodeToken::new(CodeTokenKind::Attribute, "href")
+        );
+        assert_eq!(
+            lines[0].elements[5],
+            CodeToken::new(CodeTokenKind::String, "\"/x\"")
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::code::{source_lines, CodeLine, CodeToken, CodeTokenKind};
+
+/// Highlights YAML line by line, the original layout is kept untouched.
+pub fn tokenize(source: &str) -> Vec<CodeLine> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let key_re =
+        RE.get_or_init(|| Regex::new(r#"^("[^"]*"|'[^']*'|[^\s#'"{\[][^#]*?):(\s|$)"#).unwrap());
+
+    source_lines(source)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (indent, content))| CodeLine {
+            line: index + 1,
+            indent,
+            elements: tokenize_line(content, key_re),
+        })
+        .collect()
+}
+
+fn tokenize_line(content: &str, key_re: &Regex) -> Vec<CodeToken> {
+    let mut tokens = Vec::new();
+    let mut rest = content;
+
+    if rest.starts_with('#') {
+        return vec![CodeToken::new(CodeTokenKind::Comment, rest)];
+    }
+
+    if rest == "---" || rest == "..." {
+        return vec![CodeToken::new(CodeTokenKind::Punctuation, rest)];
+    }
+
+    // nested sequence markers, e.g. `- - item`
+    while rest == "-" || rest.starts_with("- ") {
+        tokens.push(CodeToken::new(CodeTokenKind::Punctuation, "- "));
+        rest = rest[1..].trim_start();
+    }
+
+    if let Some(cap) = key_re.captures(rest) {
+        let key = cap.get(1).unwrap().as_str();
+        tokens.push(CodeToken::new(CodeTokenKind::Key, key));
+        tokens.push(CodeToken::new(CodeTokenKind::Punctuation, ": "));
+        rest = rest[key.len() + 1..].trim_start();
+    }
+
+    if rest.is_empty() {
+        return tokens;
+    }
+
+    let (value, comment) = split_comment(rest);
+
+    if !value.is_empty() {
+        tokens.push(CodeToken::new(scalar_kind(value), value));
+    }
+
+    if let Some(comment) = comment {
+        tokens.push(CodeToken::new(CodeTokenKind::Plain, " "));
+        tokens.push(CodeToken::new(CodeTokenKind::Comment, comment));
+    }
+
+    tokens
+}
+
+/// Splits a trailing ` # comment` which is not part of a quoted scalar.
+fn split_comment(value: &str) -> (&str, Option<&str>) {
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+
+    for (index, c) in value.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && prev.is_whitespace() => {
+                return (value[..index].trim_end(), Some(&value[index..]));
+            }
+            None => {}
+        }
+        prev = c;
+    }
+
+    (value, None)
+}
+
+fn scalar_kind(value: &str) -> CodeTokenKind {
+    match value {
+        "true" | "false" | "True" | "False" | "yes" | "no" | "null" | "Null" | "~" => {
+            CodeTokenKind::Keyword
+        }
+        "|" | ">" | "|-" | ">-" | "|+" | ">+" => CodeTokenKind::Punctuation,
+        _ if value.starts_with('&') || value.starts_with('*') || value.starts_with('!') => {
+            CodeTokenKind::Tag
+        }
+        _ if value.starts_with('{') || value.starts_with('[') => CodeTokenKind::Plain,
+        _ if value.parse::<f64>().is_ok() => CodeTokenKind::Number,
+        _ => CodeTokenKind::String,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_tokenization() {
+        let yaml = "# users\nusers:\n  - name: \"Ada # not a comment\"\n    age: 36 # years\n    admin: true\n";
+        let lines = tokenize(yaml);
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[0].elements,
+            vec![CodeToken::new(CodeTokenKind::Comment, "# users")]
+        );
+        assert_eq!(lines[2].indent, 2);
+        assert_eq!(
+            lines[2].elements,
+            vec![
+                CodeToken::new(CodeTokenKind::Punctuation, "- "),
+                CodeToken::new(CodeTokenKind::Key, "name"),
+                CodeToken::new(CodeTokenKind::Punctuation, ": "),
+                CodeToken::new(CodeTokenKind::String, "\"Ada # not a comment\""),
+            ]
+        );
+        assert_eq!(
+            lines[3].elements,
+            vec![
+                CodeToken::new(CodeTokenKind::Key, "age"),
+                CodeToken::new(CodeTokenKind::Punctuation, ": "),
+                CodeToken::new(CodeTokenKind::Number, "36"),
+                CodeToken::new(CodeTokenKind::Plain, " "),
+                CodeToken::new(CodeTokenKind::Comment, "# years"),
+            ]
+        );
+        assert_eq!(lines[4].elements[2].kind, CodeTokenKind::Keyword);
+    }
+}
@@ -1,6 +1,14 @@
 pub mod app;
+pub mod codegen;
 pub mod color;
 pub mod db;
+pub mod diff;
 pub mod falcon_duration;
 pub mod helpers;
+pub mod http_file;
+pub mod import;
+pub mod json_query;
+pub mod load_test;
 pub mod request;
+pub mod runner;
+pub mod script;
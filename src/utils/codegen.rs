@@ -0,0 +1,469 @@
+use std::fmt::{Display, Write};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use reqwest::header;
+
+use super::db::env::Env;
+use super::request::{PendingRequest, ResolvedRequest};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum CodeLanguage {
+    #[default]
+    Curl,
+    Reqwest,
+    Python,
+    JavaScript,
+    Go,
+    HTTPie,
+}
+
+impl CodeLanguage {
+    pub const ALL: [CodeLanguage; 6] = [
+        CodeLanguage::Curl,
+        CodeLanguage::Reqwest,
+        CodeLanguage::Python,
+        CodeLanguage::JavaScript,
+        CodeLanguage::Go,
+        CodeLanguage::HTTPie,
+    ];
+}
+
+impl Display for CodeLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            CodeLanguage::Curl => "cURL",
+            CodeLanguage::Reqwest => "Rust reqwest",
+            CodeLanguage::Python => "Python requests",
+            CodeLanguage::JavaScript => "JavaScript fetch",
+            CodeLanguage::Go => "Go net/http",
+            CodeLanguage::HTTPie => "HTTPie",
+        };
+
+        write!(f, "{}", label)
+    }
+}
+
+/// What a snippet sends, the authorization is kept apart so languages can use their own helpers.
+struct Snippet {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    basic_auth: Option<(String, String)>,
+    body: String,
+}
+
+impl From<&ResolvedRequest> for Snippet {
+    fn from(request: &ResolvedRequest) -> Self {
+        let basic_auth = request.basic_auth();
+
+        let headers = request
+            .headers
+            .iter()
+            .filter(|(name, _)| {
+                // every request is resolved with a JSON content type, even without a body
+                let empty_content_type = name.eq_ignore_ascii_case(header::CONTENT_TYPE.as_str())
+                    && request.body.is_empty();
+                let basic = name.eq_ignore_ascii_case(header::AUTHORIZATION.as_str())
+                    && basic_auth.is_some();
+
+                !empty_content_type && !basic
+            })
+            .cloned()
+            .collect();
+
+        Snippet {
+            method: request.method.0.to_string(),
+            url: request.url.clone(),
+            headers,
+            basic_auth,
+            body: request.body.clone(),
+        }
+    }
+}
+
+/// Renders the request in the language, resolved with the env.
+/// With `keep_variables` the `{{VARS}}` of the request are left as they are.
+pub fn generate(
+    request: &PendingRequest,
+    env: &Env,
+    base_url: &str,
+    language: CodeLanguage,
+    keep_variables: bool,
+) -> Result<String, String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+
+    if !keep_variables {
+        let resolved = request
+            .resolve(env, base_url)
+            .map_err(|err| format!("Unable to resolve the request, {}", err))?;
+        return Ok(render(&Snippet::from(&resolved), language));
+    }
+
+    // variables are resolved to markers surviving url encoding and base64,
+    // then turned back into placeholders in the generated code
+    let serialized = serde_json::to_string(request).unwrap_or_default();
+    let re = RE.get_or_init(|| Regex::new(r"\{\{([A-Z0-9_]+)").unwrap());
+    let mut names: Vec<String> = vec![];
+    for cap in re.captures_iter(&serialized) {
+        if !names.contains(&cap[1].to_string()) {
+            names.push(cap[1].to_string());
+        }
+    }
+
+    let markers = Env {
+        items: names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.clone(), marker(index)))
+            .collect(),
+        ..Default::default()
+    };
+    let resolved = request
+        .resolve(&markers, base_url)
+        .map_err(|err| format!("Unable to resolve the request, {}", err))?;
+
+    let mut code = render(&Snippet::from(&resolved), language);
+    for (index, name) in names.iter().enumerate() {
+        code = code.replace(&marker(index), &format!("{{{{{}}}}}", name));
+    }
+
+    Ok(code)
+}
+
+/// Lower case, hosts are lower cased by the url parser.
+fn marker(index: usize) -> String {
+    format!("falcon-var-{}-end", index)
+}
+
+fn render(snippet: &Snippet, language: CodeLanguage) -> String {
+    match language {
+        CodeLanguage::Curl => curl(snippet),
+        CodeLanguage::Reqwest => reqwest(snippet),
+        CodeLanguage::Python => python(snippet),
+        CodeLanguage::JavaScript => javascript(snippet),
+        CodeLanguage::Go => go(snippet),
+        CodeLanguage::HTTPie => httpie(snippet),
+    }
+}
+
+/// Single quoted for POSIX shells.
+fn shell(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// A double quoted literal, JSON escapes are valid in Python, JavaScript and Go.
+fn quoted(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn curl(snippet: &Snippet) -> String {
+    let mut lines = vec![match (snippet.method.as_str(), snippet.body.is_empty()) {
+        ("GET", true) => format!("curl {}", shell(&snippet.url)),
+        (method, _) => format!("curl -X {} {}", method, shell(&snippet.url)),
+    }];
+
+    for (name, value) in snippet.headers.iter() {
+        lines.push(format!("-H {}", shell(&format!("{}: {}", name, value))));
+    }
+    if let Some((username, password)) = &snippet.basic_auth {
+        lines.push(format!(
+            "-u {}",
+            shell(&format!("{}:{}", username, password))
+        ));
+    }
+    if !snippet.body.is_empty() {
+        lines.push(format!("--data-raw {}", shell(&snippet.body)));
+    }
+
+    lines.join(" \\\n  ")
+}
+
+fn httpie(snippet: &Snippet) -> String {
+    let mut parts = vec!["http".to_string()];
+
+    if let Some((username, password)) = &snippet.basic_auth {
+        parts.push(format!(
+            "-a {}",
+            shell(&format!("{}:{}", username, password))
+        ));
+    }
+    if !snippet.body.is_empty() {
+        parts.push(format!("--raw {}", shell(&snippet.body)));
+    }
+    parts.push(snippet.method.clone());
+    parts.push(shell(&snippet.url));
+    for (name, value) in snippet.headers.iter() {
+        parts.push(shell(&format!("{}:{}", name, value)));
+    }
+
+    // a single line for the method and url alone
+    match parts.len() {
+        3 => parts.join(" "),
+        _ => parts.join(" \\\n  "),
+    }
+}
+
+fn reqwest(snippet: &Snippet) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "let client = reqwest::Client::new();");
+    let _ = writeln!(out, "let response = client");
+
+    let method = match snippet.method.as_str() {
+        "GET" | "POST" | "PUT" | "DELETE" | "HEAD" | "OPTIONS" | "CONNECT" | "PATCH" | "TRACE" => {
+            format!("reqwest::Method::{}", snippet.method)
+        }
+        method => format!("reqwest::Method::from_bytes(b{:?})?", method),
+    };
+    let _ = writeln!(out, "    .request({}, {:?})", method, snippet.url);
+
+    for (name, value) in snippet.headers.iter() {
+        let _ = writeln!(out, "    .header({:?}, {:?})", name, value);
+    }
+    if let Some((username, password)) = &snippet.basic_auth {
+        let _ = writeln!(out, "    .basic_auth({:?}, Some({:?}))", username, password);
+    }
+    if !snippet.body.is_empty() {
+        let _ = writeln!(out, "    .body({})", rust_raw(&snippet.body));
+    }
+
+    let _ = writeln!(out, "    .send()");
+    let _ = writeln!(out, "    .await?;");
+    let _ = writeln!(out);
+    let _ = write!(out, "println!(\"{{}}\", response.text().await?);");
+
+    out
+}
+
+/// A raw string literal with enough `#` to hold the value.
+fn rust_raw(value: &str) -> String {
+    let mut hashes = String::from("#");
+    while value.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+
+    format!("r{}\"{}\"{}", hashes, value, hashes)
+}
+
+fn python(snippet: &Snippet) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "import requests");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "response = requests.request(");
+    let _ = writeln!(out, "    {},", quoted(&snippet.method));
+    let _ = writeln!(out, "    {},", quoted(&snippet.url));
+
+    if !snippet.headers.is_empty() {
+        let _ = writeln!(out, "    headers={{");
+        for (name, value) in snippet.headers.iter() {
+            let _ = writeln!(out, "        {}: {},", quoted(name), quoted(value));
+        }
+        let _ = writeln!(out, "    }},");
+    }
+    if let Some((username, password)) = &snippet.basic_auth {
+        let _ = writeln!(
+            out,
+            "    auth=({}, {}),",
+            quoted(username),
+            quoted(password)
+        );
+    }
+    if !snippet.body.is_empty() {
+        let _ = writeln!(out, "    data={},", quoted(&snippet.body));
+    }
+
+    let _ = writeln!(out, ")");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "print(response.status_code)");
+    let _ = write!(out, "print(response.text)");
+
+    out
+}
+
+fn javascript(snippet: &Snippet) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "const response = await fetch({}, {{",
+        quoted(&snippet.url)
+    );
+    let _ = writeln!(out, "  method: {},", quoted(&snippet.method));
+
+    if !snippet.headers.is_empty() || snippet.basic_auth.is_some() {
+        let _ = writeln!(out, "  headers: {{");
+        for (name, value) in snippet.headers.iter() {
+            let _ = writeln!(out, "    {}: {},", quoted(name), quoted(value));
+        }
+        if let Some((username, password)) = &snippet.basic_auth {
+            let _ = writeln!(
+                out,
+                "    \"Authorization\": \"Basic \" + btoa({}),",
+                quoted(&format!("{}:{}", username, password))
+            );
+        }
+        let _ = writeln!(out, "  }},");
+    }
+    if !snippet.body.is_empty() {
+        let _ = writeln!(out, "  body: {},", quoted(&snippet.body));
+    }
+
+    let _ = writeln!(out, "}});");
+    let _ = writeln!(out);
+    let _ = write!(out, "console.log(response.status, await response.text());");
+
+    out
+}
+
+fn go(snippet: &Snippet) -> String {
+    let has_body = !snippet.body.is_empty();
+    let mut out = String::new();
+
+    let _ = writeln!(out, "package main");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "import (");
+    let _ = writeln!(out, "\t\"fmt\"");
+    let _ = writeln!(out, "\t\"io\"");
+    let _ = writeln!(out, "\t\"net/http\"");
+    if has_body {
+        let _ = writeln!(out, "\t\"strings\"");
+    }
+    let _ = writeln!(out, ")");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "func main() {{");
+
+    let body = match has_body {
+        true => {
+            let _ = writeln!(
+                out,
+                "\tbody := strings.NewReader({})",
+                quoted(&snippet.body)
+            );
+            "body"
+        }
+        false => "nil",
+    };
+    let _ = writeln!(
+        out,
+        "\treq, err := http.NewRequest({}, {}, {})",
+        quoted(&snippet.method),
+        quoted(&snippet.url),
+        body
+    );
+    let _ = writeln!(out, "\tif err != nil {{\n\t\tpanic(err)\n\t}}");
+
+    for (name, value) in snippet.headers.iter() {
+        let _ = writeln!(out, "\treq.Header.Add({}, {})", quoted(name), quoted(value));
+    }
+    if let Some((username, password)) = &snippet.basic_auth {
+        let _ = writeln!(
+            out,
+            "\treq.SetBasicAuth({}, {})",
+            quoted(username),
+            quoted(password)
+        );
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "\tres, err := http.DefaultClient.Do(req)");
+    let _ = writeln!(out, "\tif err != nil {{\n\t\tpanic(err)\n\t}}");
+    let _ = writeln!(out, "\tdefer res.Body.Close()");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "\tcontent, _ := io.ReadAll(res.Body)");
+    let _ = writeln!(out, "\tfmt.Println(res.Status)");
+    let _ = writeln!(out, "\tfmt.Println(string(content))");
+    let _ = write!(out, "}}");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::request::{FalconAuthorization, FlBody};
+
+    use super::*;
+
+    fn request() -> PendingRequest {
+        PendingRequest {
+            url: "{{%PROJECT_BASE_URL%}}/users/{{USER_ID}}".into(),
+            method: "POST".into(),
+            headers: vec![("X-Trace".into(), "{{TRACE}}".into())],
+            queries: vec![("verbose".into(), "true".into())],
+            authorization: FalconAuthorization::Basic {
+                username: "ada".into(),
+                password: "{{PASSWORD}}".into(),
+            },
+            body: FlBody::ApplicationJson(r#"{"name":"it's"}"#.into()),
+            ..Default::default()
+        }
+    }
+
+    fn env() -> Env {
+        Env {
+            items: vec![
+                ("USER_ID".into(), "7".into()),
+                ("TRACE".into(), "abc".into()),
+                ("PASSWORD".into(), "secret".into()),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_generates_resolved_curl() {
+        let code = generate(
+            &request(),
+            &env(),
+            "https://api.test",
+            CodeLanguage::Curl,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            code,
+            [
+                "curl -X POST 'https://api.test/users/7?verbose=true'",
+                "-H 'X-Trace: abc'",
+                "-H 'content-type: application/json'",
+                "-u 'ada:secret'",
+                r#"--data-raw '{"name":"it'\''s"}'"#,
+            ]
+            .join(" \\\n  ")
+        );
+    }
+
+    #[test]
+    fn test_keeps_variables() {
+        for language in CodeLanguage::ALL {
+            let code = generate(&request(), &env(), "https://api.test", language, true).unwrap();
+
+            assert!(code.contains("{{USER_ID}}"), "{}: {}", language, code);
+            assert!(code.contains("{{TRACE}}"), "{}: {}", language, code);
+            assert!(code.contains("{{PASSWORD}}"), "{}: {}", language, code);
+            assert!(!code.contains("falcon-var"), "{}: {}", language, code);
+        }
+    }
+
+    #[test]
+    fn test_generates_languages() {
+        let get = PendingRequest {
+            url: "https://api.test/health".into(),
+            ..Default::default()
+        };
+        let render = |language| generate(&get, &Env::default(), "", language, false).unwrap();
+
+        assert_eq!(render(CodeLanguage::Curl), "curl 'https://api.test/health'");
+        assert_eq!(
+            render(CodeLanguage::HTTPie),
+            "http GET 'https://api.test/health'"
+        );
+        assert!(render(CodeLanguage::Reqwest)
+            .contains(".request(reqwest::Method::GET, \"https://api.test/health\")"));
+        assert!(render(CodeLanguage::Python).contains("    \"GET\",\n"));
+        assert!(render(CodeLanguage::JavaScript).contains("method: \"GET\""));
+
+        let go = render(CodeLanguage::Go);
+        assert!(go.contains("http.NewRequest(\"GET\", \"https://api.test/health\", nil)"));
+        assert!(!go.contains("strings"));
+    }
+}
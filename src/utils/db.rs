@@ -1,6 +1,4 @@
-use std::fs::File;
-use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fs;
 
 use env::Env;
@@ -11,14 +9,31 @@ use uuid::Uuid;
 use crate::ui::elements::select_options::{SelectItems, SelectOption};
 
 use super::app::app_config;
+use super::import;
+use migration::SCHEMA_VERSION;
+use storage::Backup;
 
 pub mod env;
+pub mod merge;
+pub mod migration;
 pub mod project;
+pub mod request_log;
+pub mod response_history;
+pub mod storage;
+pub mod watcher;
+pub mod workspace;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DB {
     #[serde(rename = "projects")]
     items: Vec<Project>,
     envs: Vec<Env>,
+    /// Directory the DB syncs to as a workspace, instead of the projects file of the app.
+    #[serde(skip)]
+    workspace: Option<PathBuf>,
+    /// Why the projects file could not be read, it is not overwritten while this is set.
+    #[serde(skip)]
+    load_error: Option<String>,
 }
 
 impl Into<SelectItems<Uuid>> for &DB {
@@ -34,10 +49,118 @@ impl Into<SelectItems<Uuid>> for &DB {
 
 impl DB {
     pub fn new() -> Self {
-        get_projects(&format!("{}/falcon_projects.toml", app_config().DATA_DIR)).unwrap_or(Self {
+        if let Some(dir) = workspace::current() {
+            match workspace::load(&dir) {
+                Ok(db) => return db,
+                Err(err) => {
+                    println!("{:<10}[FALCON]: (DB) Failed to open workspace, {}", "ERROR", err)
+                }
+            }
+        }
+
+        Self::app_projects()
+    }
+
+    /// The projects of the projects file of the app, a fresh DB when there is none yet.
+    /// An unreadable file gives a fresh DB that refuses to save over it.
+    fn app_projects() -> Self {
+        let fresh = Self {
             items: vec![Project::default()],
             envs: vec![Env::default()],
-        })
+            workspace: None,
+            load_error: None,
+        };
+
+        match get_projects(Path::new(&Self::default_path())) {
+            Ok(Some(db)) => db,
+            Ok(None) => fresh,
+            Err(err) => {
+                println!("{:<10}[FALCON]: (DB) {}", "ERROR", err);
+                Self {
+                    load_error: Some(err),
+                    ..fresh
+                }
+            }
+        }
+    }
+
+    pub fn load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
+    }
+
+    /// Backups of the projects file, newest first.
+    pub fn backups() -> Vec<Backup> {
+        storage::backups(Path::new(&Self::default_path()))
+    }
+
+    /// Replaces the unreadable projects file with the backup and reads it.
+    pub fn restore_backup(backup: &Path) -> Result<DB, String> {
+        let path = Self::default_path();
+        storage::restore(Path::new(&path), backup)?;
+
+        get_projects(Path::new(&path))?.ok_or(format!("{} was not restored", path))
+    }
+
+    /// Starts over from the fresh DB, the unreadable file is kept aside for recovery.
+    pub fn discard_unreadable(&mut self) -> Result<PathBuf, String> {
+        let aside = storage::set_aside(Path::new(&Self::default_path()))?;
+        self.load_error = None;
+
+        Ok(aside)
+    }
+
+    pub fn workspace(&self) -> Option<&Path> {
+        self.workspace.as_deref()
+    }
+
+    /// Where the DB is saved, the workspace directory or the projects file of the app.
+    pub fn files(&self) -> PathBuf {
+        self.workspace
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(Self::default_path()))
+    }
+
+    /// Reads the files the DB is saved to again, `None` when they are gone.
+    pub fn reload(&self) -> Result<Option<DB>, String> {
+        match self.workspace.as_ref() {
+            Some(dir) if workspace::is_workspace(dir) => workspace::load(dir).map(Some),
+            Some(_) => Ok(None),
+            None => get_projects(Path::new(&Self::default_path())),
+        }
+    }
+
+    /// Opens the directory as the workspace of the app. An empty directory becomes a
+    /// workspace with a copy of the current projects and envs, other directories are
+    /// refused so their files are never mixed with the workspace.
+    pub fn open_workspace(&self, dir: &Path) -> Result<DB, String> {
+        let is_empty = fs::read_dir(dir).map_or(true, |mut entries| entries.next().is_none());
+        let db = match workspace::is_workspace(dir) {
+            true => workspace::load(dir)?,
+            false if !is_empty => {
+                return Err(format!(
+                    "{} is not a workspace and not empty, pick an empty directory",
+                    dir.display()
+                ))
+            }
+            false => {
+                let db = DB {
+                    workspace: Some(dir.to_path_buf()),
+                    ..self.clone()
+                };
+                workspace::save(&db, dir)?;
+                db
+            }
+        };
+        workspace::remember(Some(dir))?;
+
+        Ok(db)
+    }
+
+    /// Goes back to the projects file of the app, the workspace is left as it is.
+    pub fn close_workspace() -> Result<DB, String> {
+        workspace::remember(None)?;
+
+        Ok(Self::app_projects())
     }
 
     pub fn active(&self) -> Option<Project> {
@@ -170,11 +293,107 @@ impl DB {
         self.envs.push(env);
     }
 
+    /// Reads a projects file, a workspace directory, an exported file holding a single
+    /// project, or a Hurl file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if path.is_dir() {
+            return workspace::load(path);
+        }
+
+        // a Hurl file runs as a project named after the file
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("hurl")) {
+            let mut db = Self {
+                items: vec![],
+                envs: vec![],
+                workspace: None,
+                load_error: None,
+            };
+            import::import_file(path)?.apply(&mut db);
+            return Ok(db);
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read {}, cause {}", path.display(), err))?;
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+        let value: Result<serde_json::Value, String> = match is_json {
+            true => serde_json::from_str(&contents).map_err(|err| err.to_string()),
+            false => toml::from_str(&contents).map_err(|err| err.to_string()),
+        };
+        let value =
+            value.map_err(|err| format!("Unable to parse {}, cause {}", path.display(), err))?;
+
+        // a file of a single project runs as a DB of its own, upgraded the same way
+        let value = match value.get("projects") {
+            Some(_) => value,
+            None => serde_json::json!({ "projects": [value], "envs": [] }),
+        };
+        migration::read::<DB>(value)
+            .map_err(|err| format!("Unable to parse {}, cause {}", path.display(), err))
+    }
+
+    /// Path of the projects file used by the app.
+    pub fn default_path() -> String {
+        format!("{}/falcon_projects.toml", app_config().DATA_DIR)
+    }
+
+    pub fn projects(&self) -> &[Project] {
+        &self.items
+    }
+
+    pub fn envs(&self) -> &[Env] {
+        &self.envs
+    }
+
+    /// Finds a project by name, ignoring case.
+    pub fn project_by_name(&self, name: &str) -> Option<&Project> {
+        self.items
+            .iter()
+            .find(|project| project.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Finds an env by name, ignoring case.
+    pub fn env_by_name(&self, name: &str) -> Option<&Env> {
+        self.envs
+            .iter()
+            .find(|env| env.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn project_by_name_mut(&mut self, name: &str) -> Option<&mut Project> {
+        self.items
+            .iter_mut()
+            .find(|project| project.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn env_by_name_mut(&mut self, name: &str) -> Option<&mut Env> {
+        self.envs
+            .iter_mut()
+            .find(|env| env.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn env_by_id(&self, id: Uuid) -> Option<&Env> {
+        self.envs.iter().find(|env| env.id == id)
+    }
+
+    pub fn env_by_id_mut(&mut self, id: Uuid) -> Option<&mut Env> {
+        self.envs.iter_mut().find(|env| env.id == id)
+    }
+
+    pub fn project_by_id_mut(&mut self, id: Uuid) -> Option<&mut Project> {
+        self.items.iter_mut().find(|project| project.id == id)
+    }
+
     pub fn sync(&self) -> Result<(), String> {
-        set_projects(
-            &format!("{}/falcon_projects.toml", app_config().DATA_DIR),
-            self,
-        )?;
+        if let Some(err) = self.load_error.as_ref() {
+            return Err(format!("Not saved, the projects file is unreadable: {}", err));
+        }
+
+        match self.workspace.as_ref() {
+            Some(dir) => workspace::save(self, dir)?,
+            None => set_projects(Path::new(&Self::default_path()), self)?,
+        }
         Ok(())
     }
 
@@ -218,8 +437,10 @@ impl DB {
     pub fn duplicate_project(&mut self, id: Uuid) -> Option<Project> {
         for proj in self.items.iter() {
             if proj.id == id {
+                // the copy is not written back to the `.http` files of the original
                 let project = Project {
                     id: Uuid::now_v7(),
+                    http_dir: None,
                     ..proj.clone()
                 };
 
@@ -255,40 +476,34 @@ impl DB {
     }
 }
 
-fn get_projects(path: &str) -> Result<DB, String> {
-    let path = Path::new(path);
-
+/// `None` when there is no projects file yet.
+fn get_projects(path: &Path) -> Result<Option<DB>, String> {
     if !path.exists() {
-        return Err("File not found".to_string());
+        return Ok(None);
     }
 
-    // Open the file
-    let mut file = File::open(path).expect("Unable to open file");
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Unable to read {}, cause {}", path.display(), err))?;
 
-    // Read the content of the file
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .expect("Unable to read file");
+    let parse_error = |err: String| format!("Unable to parse {}, cause {}", path.display(), err);
+    let mut value: serde_json::Value =
+        toml::from_str(&contents).map_err(|err| parse_error(err.to_string()))?;
+    let version = migration::migrate(&mut value).map_err(parse_error)?;
+    let db = serde_json::from_value(value).map_err(|err| parse_error(err.to_string()))?;
 
-    // Parse the content using toml::from_str
-    match toml::from_str(&contents) {
-        Ok(items) => Ok(items),
-        Err(_) => Err(String::from("Unable to parse toml")),
+    // the file as it was before the upgrade, older Falcon versions can still open it
+    if version < SCHEMA_VERSION {
+        storage::keep_version(path, version)?;
     }
-}
 
-fn set_projects(path: &str, projects: &DB) -> Result<(), String> {
-    let path = Path::new(path);
+    Ok(Some(db))
+}
 
-    if !path.exists() {
-        fs::create_dir_all(path.parent().unwrap()).expect("Unable to create directory");
-    }
+fn set_projects(path: &Path, projects: &DB) -> Result<(), String> {
+    let contents = migration::versioned(projects)
+        .and_then(|table| toml::to_string(&table).map_err(|err| err.to_string()))
+        .map_err(|err| format!("Toml save failed, cause {}", err))?;
 
-    match toml::to_string(&projects) {
-        Ok(contents) => match fs::write(path, contents) {
-            Ok(_) => Ok(()),
-            Err(err) => Err(format!("Failed to save file, cause {}", err)),
-        },
-        Err(err) => Err(format!("Toml save failed, cause {}", err)),
-    }
+    storage::backup(path)?;
+    storage::write_atomic(path, &contents)
 }
@@ -68,6 +68,27 @@ impl Env {
     pub fn add_item(&mut self, key: impl Into<String>, value: impl Into<String>) {
         self.items.push((key.into(), value.into()));
     }
+    pub fn variable(&self, key: &str) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+    /// Updates the variable or inserts it before the trailing empty row.
+    pub fn set_variable(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let (key, value) = (key.into(), value.into());
+
+        if let Some(item) = self.items.iter_mut().find(|(name, _)| *name == key) {
+            item.1 = value;
+            return;
+        }
+
+        let index = match self.items.last() {
+            Some((name, value)) if name.is_empty() && value.is_empty() => self.items.len() - 1,
+            _ => self.items.len(),
+        };
+        self.items.insert(index, (key, value));
+    }
     pub fn replace_variables(&self, input: impl Into<String>) -> String {
         let mut result: String = input.into();
 
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::env::Env;
+use super::project::Project;
+use super::DB;
+use crate::utils::request::PendingRequest;
+
+/// Changes made to the files outside of the app, merged with the edits of the app since
+/// the files were last saved or read.
+#[derive(Debug, Clone)]
+pub struct Merged {
+    /// Conflicting items keep the edits of the app.
+    pub mine: DB,
+    /// Conflicting items take the files.
+    pub theirs: DB,
+    /// Items changed by both, like `Request Get users`.
+    pub conflicts: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Project(Uuid),
+    Request(Uuid, Uuid),
+    Env(Uuid),
+}
+
+#[derive(Debug, Clone)]
+enum Item {
+    /// A project without its requests.
+    Project(Project),
+    /// A request with the project and folder it is in.
+    Request(Uuid, String, PendingRequest),
+    Env(Env),
+}
+
+/// Merges item by item, an item changed on one side only takes that change, an item
+/// changed on both sides is a conflict. The selected project, env and request always
+/// stay as they are in the app.
+pub fn merge(base: &DB, mine: &DB, theirs: &DB) -> Merged {
+    let base: HashMap<Key, Value> = items(base)
+        .into_iter()
+        .map(|(key, item)| (key, content(&item)))
+        .collect();
+    let mine_items = items(mine);
+    let theirs_items = items(theirs);
+    let mine_by_key: HashMap<&Key, &Item> = mine_items.iter().map(|(k, i)| (k, i)).collect();
+    let theirs_by_key: HashMap<&Key, &Item> = theirs_items.iter().map(|(k, i)| (k, i)).collect();
+
+    // the order of the files, items only the app has go last
+    let mut keys: Vec<&Key> = theirs_items.iter().map(|(key, _)| key).collect();
+    keys.extend(
+        mine_items
+            .iter()
+            .map(|(key, _)| key)
+            .filter(|key| !theirs_by_key.contains_key(key)),
+    );
+
+    let mut merged_mine = vec![];
+    let mut merged_theirs = vec![];
+    let mut conflicts = vec![];
+    for key in keys {
+        let mine_item = mine_by_key.get(key).copied();
+        let theirs_item = theirs_by_key.get(key).copied();
+        let base = base.get(key);
+        let mine_content = mine_item.map(content);
+        let theirs_content = theirs_item.map(content);
+
+        let (for_mine, for_theirs) = if mine_content.as_ref() == base {
+            (theirs_item, theirs_item)
+        } else if theirs_content.as_ref() == base || mine_content == theirs_content {
+            (mine_item, mine_item)
+        } else {
+            conflicts.push(label(mine_item.or(theirs_item)));
+            (mine_item, theirs_item)
+        };
+
+        merged_mine.extend(for_mine.map(|item| with_selection(item, mine_item)));
+        merged_theirs.extend(for_theirs.map(|item| with_selection(item, mine_item)));
+    }
+
+    Merged {
+        mine: build(merged_mine, mine),
+        theirs: build(merged_theirs, mine),
+        conflicts,
+    }
+}
+
+/// Whether both have the same projects and envs, the selection aside.
+pub fn same(db: &DB, other: &DB) -> bool {
+    let contents = |db: &DB| -> Vec<(Key, Value)> {
+        items(db)
+            .into_iter()
+            .map(|(key, item)| (key, content(&item)))
+            .collect()
+    };
+
+    contents(db) == contents(other)
+}
+
+fn items(db: &DB) -> Vec<(Key, Item)> {
+    let mut items = vec![];
+
+    for project in db.items.iter() {
+        let mut meta = project.clone();
+        meta.requests = HashMap::new();
+        items.push((Key::Project(project.id), Item::Project(meta)));
+
+        for folder in project.folders() {
+            for request in project.requests[folder].iter() {
+                let key = Key::Request(project.id, request.id);
+                items.push((
+                    key,
+                    Item::Request(project.id, folder.clone(), request.clone()),
+                ));
+            }
+        }
+    }
+    for env in db.envs.iter() {
+        items.push((Key::Env(env.id), Item::Env(env.clone())));
+    }
+
+    items
+}
+
+/// What is compared, without the selection which changes with every click.
+fn content(item: &Item) -> Value {
+    let (mut value, selection): (Value, &[&str]) = match item {
+        Item::Project(project) => (
+            serde_json::to_value(project).unwrap_or_default(),
+            &["is_active", "active_request_id"],
+        ),
+        Item::Request(_, folder, request) => (
+            serde_json::json!({ "folder": folder, "request": request }),
+            &[],
+        ),
+        Item::Env(env) => (
+            serde_json::to_value(env).unwrap_or_default(),
+            &["is_active"],
+        ),
+    };
+
+    if let Some(fields) = value.as_object_mut() {
+        for field in selection {
+            fields.remove(*field);
+        }
+    }
+
+    value
+}
+
+fn with_selection(item: &Item, mine: Option<&Item>) -> Item {
+    let mut item = item.clone();
+
+    match (&mut item, mine) {
+        (Item::Project(project), Some(Item::Project(mine))) => {
+            project.is_active = mine.is_active;
+            project.active_request_id = mine.active_request_id;
+        }
+        (Item::Env(env), Some(Item::Env(mine))) => env.is_active = mine.is_active,
+        _ => {}
+    }
+
+    item
+}
+
+fn label(item: Option<&Item>) -> String {
+    match item {
+        Some(Item::Project(project)) => format!("Project {}", project.name),
+        Some(Item::Request(_, _, request)) => {
+            format!(
+                "Request {}",
+                request.name.as_deref().unwrap_or(&request.url)
+            )
+        }
+        Some(Item::Env(env)) => format!("Env {}", env.name),
+        None => String::new(),
+    }
+}
+
+fn build(items: Vec<Item>, like: &DB) -> DB {
+    let mut db = DB {
+        items: vec![],
+        envs: vec![],
+        workspace: like.workspace.clone(),
+        load_error: like.load_error.clone(),
+    };
+
+    let mut requests = vec![];
+    for item in items {
+        match item {
+            Item::Project(project) => db.items.push(project),
+            Item::Env(env) => db.envs.push(env),
+            Item::Request(project, folder, request) => requests.push((project, folder, request)),
+        }
+    }
+
+    // requests of removed projects are dropped with them
+    for (id, folder, request) in requests {
+        if let Some(project) = db.items.iter_mut().find(|project| project.id == id) {
+            project.add_request(&folder, request);
+        }
+    }
+
+    db
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> DB {
+        let mut project = Project::default();
+        for name in ["Get users", "Create user"] {
+            project.add_request(
+                "root",
+                PendingRequest {
+                    name: Some(name.into()),
+                    ..Default::default()
+                },
+            );
+        }
+
+        DB {
+            items: vec![project],
+            envs: vec![Env::default()],
+            workspace: None,
+            load_error: None,
+        }
+    }
+
+    fn request<'a>(db: &'a DB, name: &str) -> &'a PendingRequest {
+        db.items[0].requests["root"]
+            .iter()
+            .find(|req| req.name.as_deref() == Some(name))
+            .unwrap()
+    }
+
+    fn request_mut<'a>(db: &'a mut DB, name: &str) -> &'a mut PendingRequest {
+        db.items[0]
+            .requests
+            .get_mut("root")
+            .unwrap()
+            .iter_mut()
+            .find(|req| req.name.as_deref() == Some(name))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_merges_changes_of_both_sides() {
+        let base = db();
+        let mut mine = base.clone();
+        let mut theirs = base.clone();
+
+        request_mut(&mut mine, "Get users").url = "https://app.example.com".into();
+        mine.items[0].is_active = true;
+        request_mut(&mut theirs, "Create user").url = "https://file.example.com".into();
+        theirs.envs[0].name = "Staging".into();
+        theirs.items[0].requests.get_mut("root").unwrap().remove(0);
+
+        let merged = merge(&base, &mine, &theirs);
+        assert!(merged.conflicts.is_empty());
+        assert_eq!(
+            request(&merged.mine, "Get users").url,
+            "https://app.example.com"
+        );
+        assert_eq!(
+            request(&merged.mine, "Create user").url,
+            "https://file.example.com"
+        );
+        assert_eq!(merged.mine.envs[0].name, "Staging");
+        assert!(merged.mine.items[0].is_active);
+        assert!(same(&merged.mine, &merged.theirs));
+    }
+
+    #[test]
+    fn test_reports_conflicts() {
+        let base = db();
+        let mut mine = base.clone();
+        let mut theirs = base.clone();
+
+        request_mut(&mut mine, "Get users").url = "https://app.example.com".into();
+        request_mut(&mut theirs, "Get users").url = "https://file.example.com".into();
+        // a selection made in the app only is not a change
+        theirs.items[0].is_active = true;
+
+        let merged = merge(&base, &mine, &theirs);
+        assert_eq!(merged.conflicts, vec!["Request Get users".to_string()]);
+        assert_eq!(
+            request(&merged.mine, "Get users").url,
+            "https://app.example.com"
+        );
+        assert_eq!(
+            request(&merged.theirs, "Get users").url,
+            "https://file.example.com"
+        );
+        assert!(!merged.theirs.items[0].is_active);
+        assert!(!same(&merged.mine, &merged.theirs));
+        assert!(same(&merge(&base, &base, &theirs).mine, &theirs));
+    }
+}
@@ -0,0 +1,246 @@
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+/// Version of the format this build writes, a change to the persisted structs bumps it
+/// and adds the step upgrading files of the previous version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+struct Migration {
+    /// Version of the file after the step.
+    version: u32,
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// Steps in order, files run every step past their version. Files without a version
+/// were written before versioning and are version 0.
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    apply: write_out_defaults,
+}];
+
+/// Upgrades the projects and envs to the current version step by step and returns the
+/// version the file had. Files of a newer Falcon are refused, saving them would drop
+/// what this build does not know about.
+pub fn migrate(db: &mut Value) -> Result<u32, String> {
+    let db = db
+        .as_object_mut()
+        .ok_or("Expected a table of projects and envs".to_string())?;
+    let from = match db.get("version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or(format!("Invalid schema version {}", version))?,
+    };
+
+    if from > SCHEMA_VERSION {
+        return Err(format!(
+            "It was saved by a newer Falcon with schema version {}, this one reads up to {}",
+            from, SCHEMA_VERSION
+        ));
+    }
+
+    for migration in MIGRATIONS.iter().filter(|step| step.version > from) {
+        (migration.apply)(db);
+    }
+    db.insert("version".into(), SCHEMA_VERSION.into());
+
+    Ok(from)
+}
+
+/// Upgrades and parses the projects and envs.
+pub fn read<T: DeserializeOwned>(mut db: Value) -> Result<T, String> {
+    migrate(&mut db)?;
+    serde_json::from_value(db).map_err(|err| err.to_string())
+}
+
+/// The projects and envs as saved, with the schema version.
+pub fn versioned(db: &impl Serialize) -> Result<toml::Table, String> {
+    let mut table = toml::Table::try_from(db).map_err(|err| err.to_string())?;
+    table.insert("version".into(), i64::from(SCHEMA_VERSION).into());
+
+    Ok(table)
+}
+
+/// Files of the first release have no scripts in projects and no assertions, extractions
+/// or scripts in requests, the structs require them since version 1.
+fn write_out_defaults(db: &mut Map<String, Value>) {
+    for project in tables(db.get_mut("projects")) {
+        for key in ["pre_request_script", "post_response_script"] {
+            project.entry(key).or_insert("".into());
+        }
+
+        let folders = project.get_mut("requests").and_then(Value::as_object_mut);
+        for requests in folders.into_iter().flat_map(|folders| folders.values_mut()) {
+            for request in tables(Some(requests)) {
+                for key in ["assertions", "extractions"] {
+                    request.entry(key).or_insert(Value::Array(vec![]));
+                }
+                for key in ["pre_request_script", "post_response_script"] {
+                    request.entry(key).or_insert("".into());
+                }
+            }
+        }
+    }
+}
+
+fn tables(items: Option<&mut Value>) -> impl Iterator<Item = &mut Map<String, Value>> {
+    items
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::db::DB;
+
+    use super::*;
+
+    /// Written by the first release, before scripts, assertions and extractions.
+    const ORIGINAL_RELEASE: &str = r#"
+[[projects]]
+name = "Users API"
+is_active = true
+id = "01a1517c-b099-768a-8173-2cb7510ab4ac"
+active_request_id = "01a1517c-b099-7ae6-b60f-4be8146cd9fd"
+
+[[projects.requests.root]]
+id = "01a1517c-b099-7ae6-b60f-4be8146cd9fd"
+name = "Get users"
+url = "{{BASE_URL}}/users"
+method = "GET"
+headers = [["Accept", "application/json"]]
+cookies = [["", ""]]
+queries = [["page", "1"]]
+
+[projects.requests.root.authorization.Bearer]
+prefix = "Bearer"
+token = "{{TOKEN}}"
+
+[projects.requests.root.body]
+ApplicationJson = ""
+
+[[envs]]
+id = "01a1517c-b099-7347-9387-d4df11117bd5"
+name = "Default env"
+items = [["TOKEN", "secret"]]
+is_active = true
+base_url = "https://api.example.com"
+"#;
+
+    /// Written after assertions and scripts were added, before extractions and before
+    /// versioning.
+    const BEFORE_EXTRACTIONS: &str = r#"
+[[projects]]
+name = "Users API"
+is_active = true
+id = "01a1517c-b099-768a-8173-2cb7510ab4ac"
+pre_request_script = 'env.set("RUN", "1")'
+post_response_script = ""
+
+[[projects.requests.root]]
+id = "01a1517c-b099-7ae6-b60f-4be8146cd9fd"
+url = "https://api.example.com/users"
+method = "POST"
+headers = [["", ""]]
+cookies = [["", ""]]
+queries = [["", ""]]
+response_filter = "$.users"
+pre_request_script = ""
+post_response_script = 'test("created", response.status == 201)'
+
+[[projects.requests.root.assertions]]
+kind = "StatusEquals"
+target = ""
+expected = "201"
+
+[projects.requests.root.authorization.Basic]
+username = "admin"
+password = "secret"
+
+[projects.requests.root.body]
+ApplicationJson = '{"name": "falcon"}'
+
+[[envs]]
+id = "01a1517c-b099-7347-9387-d4df11117bd5"
+name = "Default env"
+items = [["", ""]]
+is_active = false
+"#;
+
+    fn upgrade(contents: &str) -> (u32, DB) {
+        let mut value: Value = toml::from_str(contents).unwrap();
+        let from = migrate(&mut value).unwrap();
+        (from, serde_json::from_value(value).unwrap())
+    }
+
+    #[test]
+    fn test_upgrades_original_release() {
+        // the fields added since are required, the file only parses once upgraded
+        let err = toml::from_str::<DB>(ORIGINAL_RELEASE).unwrap_err();
+        assert!(err.to_string().contains("missing field"));
+
+        let (from, db) = upgrade(ORIGINAL_RELEASE);
+        assert_eq!(from, 0);
+
+        let project = &db.items[0];
+        assert_eq!(project.name, "Users API");
+        assert_eq!(project.pre_request_script, "");
+        let request = &project.requests["root"][0];
+        assert_eq!(request.url, "{{BASE_URL}}/users");
+        assert_eq!(request.queries, vec![("page".into(), "1".into())]);
+        assert!(request.assertions.is_empty() && request.extractions.is_empty());
+        assert_eq!(
+            db.envs[0].base_url.as_deref(),
+            Some("https://api.example.com")
+        );
+    }
+
+    #[test]
+    fn test_upgrades_file_before_extractions() {
+        let err = toml::from_str::<DB>(BEFORE_EXTRACTIONS).unwrap_err();
+        assert!(err.to_string().contains("extractions"));
+
+        let (from, db) = upgrade(BEFORE_EXTRACTIONS);
+        assert_eq!(from, 0);
+
+        let project = &db.items[0];
+        assert_eq!(project.pre_request_script, r#"env.set("RUN", "1")"#);
+        let request = &project.requests["root"][0];
+        assert_eq!(request.response_filter.as_deref(), Some("$.users"));
+        assert_eq!(request.assertions[0].expected, "201");
+        assert_eq!(
+            request.post_response_script,
+            r#"test("created", response.status == 201)"#
+        );
+        assert!(request.extractions.is_empty());
+    }
+
+    #[test]
+    fn test_current_version_round_trips() {
+        let (_, db) = upgrade(ORIGINAL_RELEASE);
+        let saved = toml::to_string(&versioned(&db).unwrap()).unwrap();
+        assert!(saved.contains(&format!("version = {}", SCHEMA_VERSION)));
+
+        let (from, reloaded) = upgrade(&saved);
+        assert_eq!(from, SCHEMA_VERSION);
+        assert_eq!(
+            serde_json::to_value(&reloaded.items).unwrap(),
+            serde_json::to_value(&db.items).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_refuses_newer_version() {
+        let mut value: Value = toml::from_str(&format!(
+            "version = {}\nprojects = []\nenvs = []",
+            SCHEMA_VERSION + 1
+        ))
+        .unwrap();
+
+        assert!(migrate(&mut value).unwrap_err().contains("newer Falcon"));
+        assert!(read::<DB>(value).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{collections::HashMap, fmt::Display, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -16,6 +16,13 @@ pub struct Project {
     pub requests: HashMap<String, Vec<PendingRequest>>,
     pub active_request_id: Option<Uuid>,
     pub default_env: Option<Uuid>,
+    /// Runs before the pre-request script of every request in the project.
+    pub pre_request_script: String,
+    /// Runs before the post-response script of every request in the project.
+    pub post_response_script: String,
+    /// Directory of `.http` files the project was opened from, edits are written back to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_dir: Option<PathBuf>,
 }
 
 impl Project {
@@ -90,6 +97,19 @@ impl Project {
         }
     }
 
+    /// Replaces what the current request sends, its name, tests and scripts are kept.
+    pub fn replace_current_request(&mut self, request: PendingRequest, base_url: &str) {
+        if let Some(active_req) = self.current_request_mut() {
+            active_req.set_url(RequestUrl::from(request.url).extract(base_url));
+            active_req.method = request.method;
+            active_req.headers = request.headers;
+            active_req.cookies = request.cookies;
+            active_req.queries = request.queries;
+            active_req.authorization = request.authorization;
+            active_req.body = request.body;
+        }
+    }
+
     pub fn update_request_method(&mut self, method: HttpMethod) {
         if let Some(active_req) = self.current_request_mut() {
             active_req.set_method(method);
@@ -142,6 +162,39 @@ impl Project {
     pub fn remove_default_env(&mut self) {
         self.default_env = None
     }
+
+    /// Folder names in a stable order, `root` first and the rest alphabetically.
+    pub fn folders(&self) -> Vec<&String> {
+        let mut folders: Vec<&String> = self.requests.keys().collect();
+        folders.sort_by_key(|folder| (folder.as_str() != "root", folder.to_lowercase()));
+        folders
+    }
+
+    /// Requests of one folder, or of every folder when `None`, in run order.
+    pub fn ordered_requests(&self, folder: Option<&str>) -> Vec<(&String, &PendingRequest)> {
+        self.folders()
+            .into_iter()
+            .filter(|name| folder.is_none_or(|folder| folder == name.as_str()))
+            .flat_map(|name| self.requests[name].iter().map(move |req| (name, req)))
+            .collect()
+    }
+
+    /// Finds a request by name, `path` is `name` or `folder/name`, ignoring case.
+    pub fn find_request(&self, path: &str) -> Option<(&String, &PendingRequest)> {
+        let (folder, name) = match path.split_once('/') {
+            Some((folder, name)) => (Some(folder.trim()), name.trim()),
+            None => (None, path.trim()),
+        };
+
+        self.ordered_requests(None)
+            .into_iter()
+            .filter(|(req_folder, _)| folder.is_none_or(|f| req_folder.eq_ignore_ascii_case(f)))
+            .find(|(_, req)| {
+                req.name
+                    .as_deref()
+                    .is_some_and(|req_name| req_name.trim().eq_ignore_ascii_case(name))
+            })
+    }
 }
 
 impl Default for Project {
@@ -156,6 +209,9 @@ impl Default for Project {
             requests,
             active_request_id: None,
             default_env: None,
+            pre_request_script: String::new(),
+            post_response_script: String::new(),
+            http_dir: None,
         }
     }
 }
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::utils::app::app_config;
+use crate::utils::db::storage;
+use crate::utils::request::FalconResponse;
+
+/// Number of responses kept for every request, older entries are dropped.
+pub const MAX_RESPONSES_PER_REQUEST: usize = 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResponseRecord {
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    pub duration_ms: u64,
+    pub size_kb: f64,
+    pub env_name: Option<String>,
+}
+
+impl ResponseRecord {
+    pub fn new(response: &FalconResponse, env_name: Option<String>) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            timestamp: Utc::now(),
+            status_code: response.status_code.as_u16(),
+            headers: response
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        name.to_string(),
+                        String::from_utf8_lossy(value.as_bytes()).to_string(),
+                    )
+                })
+                .collect(),
+            body: response.body.clone(),
+            duration_ms: response.duration.as_duration().as_millis() as u64,
+            size_kb: response.size_kb,
+            env_name,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "{} - {}",
+            self.timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            self.status_code
+        )
+    }
+}
+
+impl From<&ResponseRecord> for FalconResponse {
+    fn from(record: &ResponseRecord) -> Self {
+        let mut headers = HeaderMap::new();
+
+        for (name, value) in record.headers.iter() {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.append(name, value);
+            }
+        }
+
+        FalconResponse {
+            status_code: StatusCode::from_u16(record.status_code).unwrap_or_default(),
+            body: record.body.clone(),
+            headers,
+            cookies: vec![],
+            duration: Duration::from_millis(record.duration_ms).into(),
+            size_kb: record.size_kb,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ResponseHistory {
+    #[serde(default)]
+    requests: HashMap<Uuid, Vec<ResponseRecord>>,
+    /// Why the file could not be read.
+    #[serde(skip)]
+    load_error: Option<String>,
+    /// The unreadable file could not be set aside, it is not saved over.
+    #[serde(skip)]
+    read_only: bool,
+}
+
+impl ResponseHistory {
+    /// Reads the history, an unreadable file is set aside and the history starts empty.
+    pub fn new() -> Self {
+        let path = format!("{}/falcon_history.toml", app_config().DATA_DIR);
+
+        match storage::read_or_set_aside::<Self>(Path::new(&path)) {
+            Ok((history, None)) => history,
+            Ok((history, Some(err))) => {
+                println!("{:<10}[FALCON]: (HISTORY) {}", "ERROR", err);
+                Self {
+                    load_error: Some(err),
+                    ..history
+                }
+            }
+            Err(err) => {
+                println!("{:<10}[FALCON]: (HISTORY) {}", "ERROR", err);
+                Self {
+                    load_error: Some(err),
+                    read_only: true,
+                    ..Default::default()
+                }
+            }
+        }
+    }
+
+    pub fn load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
+    }
+
+    /// Stores the record as the newest entry of the request.
+    pub fn record(&mut self, request_id: Uuid, record: ResponseRecord) {
+        let entries = self.requests.entry(request_id).or_default();
+
+        entries.insert(0, record);
+        entries.truncate(MAX_RESPONSES_PER_REQUEST);
+    }
+
+    /// Entries of the request, newest first.
+    pub fn entries(&self, request_id: &Uuid) -> &[ResponseRecord] {
+        self.requests
+            .get(request_id)
+            .map(|entries| entries.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn find(&self, request_id: &Uuid, id: &Uuid) -> Option<&ResponseRecord> {
+        self.entries(request_id)
+            .iter()
+            .find(|entry| entry.id == *id)
+    }
+
+    pub fn clear(&mut self, request_id: &Uuid) {
+        self.requests.remove(request_id);
+    }
+
+    pub fn sync(&self) -> Result<(), String> {
+        if let Some(err) = self.load_error.as_ref().filter(|_| self.read_only) {
+            return Err(format!(
+                "Not saved, the history file is unreadable: {}",
+                err
+            ));
+        }
+
+        let path = format!("{}/falcon_history.toml", app_config().DATA_DIR);
+        let contents =
+            toml::to_string(self).map_err(|err| format!("Toml save failed, cause {}", err))?;
+
+        storage::write_atomic(Path::new(&path), &contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(status_code: u16) -> ResponseRecord {
+        ResponseRecord {
+            id: Uuid::now_v7(),
+            timestamp: Utc::now(),
+            status_code,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: "{}".into(),
+            duration_ms: 12,
+            size_kb: 1.0,
+            env_name: None,
+        }
+    }
+
+    #[test]
+    fn test_keeps_latest_entries_first() {
+        let mut history = ResponseHistory::default();
+        let request_id = Uuid::now_v7();
+
+        for status in 0..MAX_RESPONSES_PER_REQUEST + 5 {
+            history.record(request_id, record(200 + status as u16));
+        }
+
+        let entries = history.entries(&request_id);
+        assert_eq!(entries.len(), MAX_RESPONSES_PER_REQUEST);
+        assert_eq!(entries[0].status_code, 224);
+        assert!(history.entries(&Uuid::now_v7()).is_empty());
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let mut history = ResponseHistory::default();
+        let request_id = Uuid::now_v7();
+        history.record(request_id, record(404));
+
+        let contents = toml::to_string(&history).unwrap();
+        let parsed: ResponseHistory = toml::from_str(&contents).unwrap();
+
+        assert_eq!(parsed.entries(&request_id), history.entries(&request_id));
+    }
+    #[test]
+    fn test_unreadable_file_is_not_saved_over() {
+        let history = ResponseHistory {
+            load_error: Some("Unable to read falcon_history.toml".into()),
+            read_only: true,
+            ..Default::default()
+        };
+
+        assert!(history.sync().unwrap_err().contains("unreadable"));
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+use serde::de::DeserializeOwned;
+
+/// Backups kept of the projects file, the oldest is dropped past this.
+pub const MAX_BACKUPS: usize = 5;
+/// A new backup is taken at most this often, saves run on every edit.
+const BACKUP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Hash of what the app last wrote to each file, `None` once it removed the file.
+static WRITES: OnceLock<Mutex<HashMap<PathBuf, Option<u64>>>> = OnceLock::new();
+
+/// A previous version of a file, `index` 1 is the newest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub index: usize,
+    pub modified: SystemTime,
+}
+
+/// Writes to a temp file next to the target and renames it over the target, a crash
+/// leaves either the old or the new file but never a truncated one.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Unable to create directory, cause {}", err))?;
+    }
+
+    // recorded before the rename, the watcher may see the new file right away
+    record_write(path, Some(contents));
+    let temp = sibling(path, ".tmp");
+    let written = File::create(&temp).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(err) = written.and_then(|_| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(format!("Failed to save file, cause {}", err));
+    }
+
+    Ok(())
+}
+
+/// Removes a file the app wrote, like a request deleted from a workspace.
+pub fn remove(path: &Path) -> Result<(), String> {
+    record_write(path, None);
+
+    fs::remove_file(path).map_err(|err| format!("Failed to remove {}, {}", path.display(), err))
+}
+
+/// Whether the file is as the app last wrote or removed it, a change of the watched
+/// files made by a save of the app is not a change made outside of it.
+pub fn is_own_write(path: &Path) -> bool {
+    let current = fs::read_to_string(path).ok();
+    let writes = WRITES.get_or_init(Default::default).lock();
+
+    writes.is_ok_and(|writes| writes.get(path) == Some(&current.as_deref().map(hash)))
+}
+
+fn record_write(path: &Path, contents: Option<&str>) {
+    if let Ok(mut writes) = WRITES.get_or_init(Default::default).lock() {
+        writes.insert(path.to_path_buf(), contents.map(hash));
+    }
+}
+
+fn hash(contents: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Keeps a copy of the file before it is overwritten, rotating the older copies.
+pub fn backup(path: &Path) -> Result<(), String> {
+    rotate(path, BACKUP_INTERVAL)
+}
+
+fn rotate(path: &Path, interval: Duration) -> Result<(), String> {
+    if !path.is_file() {
+        return Ok(());
+    }
+
+    let newest = backup_path(path, 1);
+    let recent = fs::metadata(&newest)
+        .and_then(|meta| meta.modified())
+        .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age < interval));
+    if recent {
+        return Ok(());
+    }
+
+    let failed =
+        |err: std::io::Error| format!("Failed to back up {}, cause {}", path.display(), err);
+    for index in (1..MAX_BACKUPS).rev() {
+        let from = backup_path(path, index);
+        if from.exists() {
+            fs::rename(&from, backup_path(path, index + 1)).map_err(failed)?;
+        }
+    }
+    fs::copy(path, &newest).map_err(failed)?;
+
+    Ok(())
+}
+
+/// Backups of the file, newest first.
+pub fn backups(path: &Path) -> Vec<Backup> {
+    (1..=MAX_BACKUPS)
+        .filter_map(|index| {
+            let path = backup_path(path, index);
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok()?;
+            Some(Backup {
+                path,
+                index,
+                modified,
+            })
+        })
+        .collect()
+}
+
+/// Replaces the file with the backup, the replaced file is set aside first.
+pub fn restore(path: &Path, backup: &Path) -> Result<(), String> {
+    let contents = fs::read_to_string(backup)
+        .map_err(|err| format!("Unable to read {}, cause {}", backup.display(), err))?;
+
+    set_aside(path)?;
+    write_atomic(path, &contents)
+}
+
+/// Copies the file as written by an older schema version, `falcon_projects.toml.v0.bak`.
+/// The first copy is kept, it is the one older Falcon versions can open.
+pub fn keep_version(path: &Path, version: u32) -> Result<(), String> {
+    let copy = sibling(path, &format!(".v{}.bak", version));
+    if copy.exists() {
+        return Ok(());
+    }
+
+    fs::copy(path, &copy)
+        .map(|_| ())
+        .map_err(|err| format!("Failed to copy {}, cause {}", path.display(), err))
+}
+
+/// Reads a toml file of the app, a missing file is the default. A file that cannot be
+/// parsed is set aside so the next save does not replace it, the message says where it
+/// went. `Err` when the file is still in place unread, it must not be saved over.
+pub fn read_or_set_aside<T: DeserializeOwned + Default>(
+    path: &Path,
+) -> Result<(T, Option<String>), String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok((T::default(), None)),
+        Err(err) => return Err(format!("Unable to read {}, cause {}", path.display(), err)),
+    };
+
+    match toml::from_str(&contents) {
+        Ok(value) => Ok((value, None)),
+        Err(err) => {
+            let parse_error = format!("Unable to parse {}, cause {}", path.display(), err);
+            let aside = set_aside(path).map_err(|moved| format!("{}, {}", parse_error, moved))?;
+
+            Ok((
+                T::default(),
+                Some(format!(
+                    "{}, it was moved to {}",
+                    parse_error,
+                    aside.display()
+                )),
+            ))
+        }
+    }
+}
+
+/// Moves an unreadable file out of the way so it can be recovered by hand, it is
+/// never overwritten by a save.
+pub fn set_aside(path: &Path) -> Result<PathBuf, String> {
+    let aside = sibling(path, ".unreadable");
+    if path.exists() {
+        fs::rename(path, &aside)
+            .map_err(|err| format!("Failed to move {}, cause {}", path.display(), err))?;
+    }
+
+    Ok(aside)
+}
+
+fn backup_path(path: &Path, index: usize) -> PathBuf {
+    sibling(path, &format!(".{}.bak", index))
+}
+
+/// `falcon_projects.toml` with a suffix, `falcon_projects.toml.1.bak`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_rotates_and_restores_backups() {
+        let dir = std::env::temp_dir().join(format!("falcon-storage-{}", Uuid::now_v7()));
+        let path = dir.join("projects.toml");
+
+        for version in 1..=MAX_BACKUPS + 2 {
+            rotate(&path, Duration::ZERO).unwrap();
+            write_atomic(&path, &format!("version {}", version)).unwrap();
+        }
+        let backups = backups(&path);
+        assert_eq!(backups.len(), MAX_BACKUPS);
+        assert_eq!(fs::read_to_string(&backups[0].path).unwrap(), "version 6");
+        assert_eq!(fs::read_to_string(&backups[4].path).unwrap(), "version 2");
+        assert!(!sibling(&path, ".tmp").exists());
+
+        // a recent backup is not replaced on every save
+        backup(&path).unwrap();
+        assert_eq!(fs::read_to_string(&backups[0].path).unwrap(), "version 6");
+
+        restore(&path, &backups[1].path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "version 5");
+        assert_eq!(
+            fs::read_to_string(sibling(&path, ".unreadable")).unwrap(),
+            "version 7"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn test_sets_unreadable_files_aside() {
+        let dir = std::env::temp_dir().join(format!("falcon-storage-{}", Uuid::now_v7()));
+        let path = dir.join("history.toml");
+
+        let (missing, err) = read_or_set_aside::<toml::Table>(&path).unwrap();
+        assert!(missing.is_empty() && err.is_none());
+
+        write_atomic(&path, "entries = [").unwrap();
+        let (table, err) = read_or_set_aside::<toml::Table>(&path).unwrap();
+        assert!(table.is_empty());
+        assert!(err.unwrap().contains("history.toml.unreadable"));
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(sibling(&path, ".unreadable")).unwrap(),
+            "entries = ["
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,152 @@
+use std::path::Path;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+use super::storage;
+
+/// A save touches several files, a change is reported once they are quiet for this long.
+const QUIET: Duration = Duration::from_millis(300);
+
+/// Watches the files of a DB, the projects file of the app or a workspace directory.
+/// Saves of the app itself are not reported.
+pub struct FileWatcher {
+    _watcher: RecommendedWatcher,
+    changes: UnboundedReceiver<()>,
+}
+
+impl FileWatcher {
+    /// Watches a single file, or the files of a directory with one of the extensions,
+    /// like the toml files of a workspace or the `.http` files of a project.
+    pub fn new(path: &Path, extensions: &'static [&'static str]) -> Result<Self, String> {
+        let is_dir = path.is_dir();
+        // the projects file is replaced on every save, its directory is watched to see the
+        // new file
+        let (watched, mode) = match (is_dir, path.parent()) {
+            (true, _) => (path, RecursiveMode::Recursive),
+            (false, Some(parent)) if parent.as_os_str().is_empty() => {
+                (Path::new("."), RecursiveMode::NonRecursive)
+            }
+            (false, Some(parent)) => (parent, RecursiveMode::NonRecursive),
+            (false, None) => {
+                return Err(format!(
+                    "Unable to watch {}, it is not a file or a directory",
+                    path.display()
+                ))
+            }
+        };
+
+        let (sender, changes) = unbounded_channel();
+        let target = path.to_path_buf();
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            let is_change = !matches!(event.kind, EventKind::Access(_));
+            if is_change
+                && event.paths.iter().any(|path| {
+                    is_watched(&target, is_dir, extensions, path)
+                        && !is_own_write(&target, is_dir, path)
+                })
+            {
+                let _ = sender.send(());
+            }
+        })
+        .map_err(|err| format!("Unable to watch {}, cause {}", path.display(), err))?;
+
+        let _ = std::fs::create_dir_all(watched);
+        watcher
+            .watch(watched, mode)
+            .map_err(|err| format!("Unable to watch {}, cause {}", watched.display(), err))?;
+
+        Ok(Self {
+            _watcher: watcher,
+            changes,
+        })
+    }
+
+    /// Waits for the next change, `None` once the watcher stopped.
+    pub async fn changed(&mut self) -> Option<()> {
+        self.changes.recv().await?;
+        while let Ok(Some(_)) = tokio::time::timeout(QUIET, self.changes.recv()).await {}
+
+        Some(())
+    }
+}
+
+/// The files of a directory with one of the extensions, temp files and backups aside,
+/// or the single file.
+fn is_watched(target: &Path, is_dir: bool, extensions: &[&str], path: &Path) -> bool {
+    match is_dir {
+        true => path
+            .extension()
+            .is_some_and(|ext| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e))),
+        false => path.file_name() == target.file_name(),
+    }
+}
+
+/// The file as the app saved it, the projects file is named as the app writes it and
+/// not as the event has it, `./falcon_projects.toml`.
+fn is_own_write(target: &Path, is_dir: bool, path: &Path) -> bool {
+    match is_dir {
+        true => storage::is_own_write(path),
+        false => storage::is_own_write(target),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::utils::db::storage;
+
+    #[test]
+    fn test_reports_changes_made_outside_of_the_app() {
+        let dir = std::env::temp_dir().join(format!("falcon-watcher-{}", Uuid::now_v7()));
+        let path = dir.join("falcon_projects.toml");
+        fs::create_dir_all(&dir).unwrap();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let mut watcher = FileWatcher::new(&path, &["toml"]).unwrap();
+            fs::write(dir.join("other.toml"), "").unwrap();
+            storage::write_atomic(&path, "projects = []").unwrap();
+
+            // a save of the app is not a change
+            let changed = tokio::time::timeout(Duration::from_secs(1), watcher.changed());
+            assert!(changed.await.is_err());
+
+            fs::write(&path, "projects = []\nenvs = []").unwrap();
+            let changed = tokio::time::timeout(Duration::from_secs(5), watcher.changed());
+            assert_eq!(changed.await, Ok(Some(())));
+        });
+        assert!(FileWatcher::new(Path::new(""), &["toml"])
+            .err()
+            .is_some_and(|err| err.contains("not a file or a directory")));
+        assert!(!is_watched(
+            &path,
+            false,
+            &["toml"],
+            &dir.join("falcon_projects.toml.tmp")
+        ));
+        assert!(!is_watched(
+            &dir,
+            true,
+            &["toml"],
+            &dir.join("falcon_projects.toml.1.bak")
+        ));
+        assert!(is_watched(
+            &dir,
+            true,
+            &["http", "rest"],
+            &dir.join("users/admin.HTTP")
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::migration::{self, SCHEMA_VERSION};
+use super::storage;
+use super::DB;
+use crate::utils::app::app_config;
+
+/// Marks a directory as a workspace and keeps the order of its projects and envs.
+pub const WORKSPACE_FILE: &str = "falcon_workspace.toml";
+const PROJECT_FILE: &str = "project.toml";
+const FOLDER_FILE: &str = "folder.toml";
+const PROJECTS_DIR: &str = "projects";
+const ENVS_DIR: &str = "envs";
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct WorkspaceFile {
+    /// Schema version of the files, see `migration`.
+    #[serde(default)]
+    version: u32,
+    /// Directory names of the projects, in order.
+    projects: Vec<String>,
+    /// File names of the envs, in order.
+    envs: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct FolderFile {
+    name: String,
+    /// File names of the requests, in order.
+    requests: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct CurrentWorkspace {
+    path: Option<PathBuf>,
+}
+
+pub fn is_workspace(dir: &Path) -> bool {
+    dir.join(WORKSPACE_FILE).is_file()
+}
+
+/// The workspace the app opened last, `None` when it uses its own projects file.
+pub fn current() -> Option<PathBuf> {
+    let contents = fs::read_to_string(current_path()).ok()?;
+    toml::from_str::<CurrentWorkspace>(&contents).ok()?.path
+}
+
+/// Remembers the workspace to open on the next start.
+pub fn remember(dir: Option<&Path>) -> Result<(), String> {
+    let current = CurrentWorkspace {
+        path: dir.map(Path::to_path_buf),
+    };
+    let contents = toml::to_string(&current).map_err(|err| err.to_string())?;
+
+    storage::write_atomic(&current_path(), &contents)
+}
+
+fn current_path() -> PathBuf {
+    Path::new(&app_config().DATA_DIR).join("falcon_current_workspace.toml")
+}
+
+/// Writes one directory per project with a file per folder and request, and a file per
+/// env. Files are only written when their content changed, and files of removed
+/// projects, folders, requests and envs are deleted. Only files the workspace listed
+/// before are deleted, anything else in the directory is left alone.
+pub fn save(db: &DB, dir: &Path) -> Result<(), String> {
+    let files = render(db)?;
+    let previous = listed_files(dir);
+
+    for (path, contents) in files.iter() {
+        let path = dir.join(path);
+        if fs::read_to_string(&path).ok().as_deref() != Some(contents.as_str()) {
+            storage::write_atomic(&path, contents)?;
+        }
+    }
+
+    remove_stale(dir, previous, &files)
+}
+
+/// Reads the workspace, the returned DB syncs back to it. Files of older versions are
+/// upgraded like the projects file of the app.
+pub fn load(dir: &Path) -> Result<DB, String> {
+    let workspace: WorkspaceFile = read_toml(&dir.join(WORKSPACE_FILE))?;
+
+    let mut envs = vec![];
+    for name in workspace.envs.iter() {
+        let path = dir.join(ENVS_DIR).join(format!("{}.toml", name));
+        envs.push(read_toml::<toml::Table>(&path)?);
+    }
+
+    let mut projects = vec![];
+    for name in workspace.projects.iter() {
+        let project_dir = dir.join(PROJECTS_DIR).join(name);
+        let mut project: toml::Table = read_toml(&project_dir.join(PROJECT_FILE))?;
+        let mut requests = toml::Table::new();
+
+        for folder_dir in sub_dirs(&project_dir)? {
+            let folder: FolderFile = read_toml(&folder_dir.join(FOLDER_FILE))?;
+            let mut folder_requests = vec![];
+            for request in folder.requests.iter() {
+                let path = folder_dir.join(format!("{}.toml", request));
+                folder_requests.push(toml::Value::Table(read_toml(&path)?));
+            }
+            requests.insert(folder.name, folder_requests.into());
+        }
+
+        project.insert("requests".into(), requests.into());
+        projects.push(project);
+    }
+
+    let db = serde_json::json!({
+        "version": workspace.version,
+        "projects": projects,
+        "envs": envs,
+    });
+    let mut db: DB = migration::read(db)
+        .map_err(|err| format!("Unable to read workspace {}, cause {}", dir.display(), err))?;
+    db.workspace = Some(dir.to_path_buf());
+
+    Ok(db)
+}
+
+/// Every file of the workspace by its path relative to the workspace directory.
+fn render(db: &DB) -> Result<BTreeMap<PathBuf, String>, String> {
+    let mut files = BTreeMap::new();
+    let mut workspace = WorkspaceFile {
+        version: SCHEMA_VERSION,
+        ..Default::default()
+    };
+
+    let mut env_names = HashSet::new();
+    for env in db.envs.iter() {
+        let name = unique_slug(&env.name, &mut env_names);
+        let path = Path::new(ENVS_DIR).join(format!("{}.toml", name));
+        files.insert(path, to_toml(env)?);
+        workspace.envs.push(name);
+    }
+
+    let mut project_names = HashSet::new();
+    for project in db.items.iter() {
+        let name = unique_slug(&project.name, &mut project_names);
+        let project_dir = Path::new(PROJECTS_DIR).join(&name);
+
+        // requests are stored in their folder, the rest of the project in its own file
+        let mut meta = toml::Table::try_from(project).map_err(|err| err.to_string())?;
+        meta.remove("requests");
+        files.insert(project_dir.join(PROJECT_FILE), to_toml(&meta)?);
+
+        let mut folder_names = HashSet::new();
+        for folder in project.folders() {
+            let folder_dir = project_dir.join(unique_slug(folder, &mut folder_names));
+            let mut folder_file = FolderFile {
+                name: folder.clone(),
+                requests: vec![],
+            };
+
+            let mut request_names = HashSet::from([slug(FOLDER_FILE.trim_end_matches(".toml"))]);
+            for request in project.requests[folder].iter() {
+                let name = request.name.as_deref().unwrap_or_default();
+                let name = unique_slug(name, &mut request_names);
+                files.insert(folder_dir.join(format!("{}.toml", name)), to_toml(request)?);
+                folder_file.requests.push(name);
+            }
+            files.insert(folder_dir.join(FOLDER_FILE), to_toml(&folder_file)?);
+        }
+
+        workspace.projects.push(name);
+    }
+
+    files.insert(PathBuf::from(WORKSPACE_FILE), to_toml(&workspace)?);
+
+    Ok(files)
+}
+
+/// Files of the workspace as its workspace and folder files list them, relative to the
+/// workspace directory.
+fn listed_files(dir: &Path) -> BTreeSet<PathBuf> {
+    let mut files = BTreeSet::new();
+    let Ok(workspace) = read_toml::<WorkspaceFile>(&dir.join(WORKSPACE_FILE)) else {
+        return files;
+    };
+
+    files.insert(PathBuf::from(WORKSPACE_FILE));
+    for name in workspace.envs.iter() {
+        files.insert(Path::new(ENVS_DIR).join(format!("{}.toml", name)));
+    }
+    for name in workspace.projects.iter() {
+        let project_dir = Path::new(PROJECTS_DIR).join(name);
+        files.insert(project_dir.join(PROJECT_FILE));
+
+        for folder_dir in sub_dirs(&dir.join(&project_dir)).unwrap_or_default() {
+            let Ok(folder) = read_toml::<FolderFile>(&folder_dir.join(FOLDER_FILE)) else {
+                continue;
+            };
+            let folder_dir = project_dir.join(folder_dir.file_name().unwrap_or_default());
+            files.insert(folder_dir.join(FOLDER_FILE));
+            for request in folder.requests.iter() {
+                files.insert(folder_dir.join(format!("{}.toml", request)));
+            }
+        }
+    }
+
+    files
+}
+
+/// Removes the listed files the workspace no longer has, and the directories they leave
+/// empty.
+fn remove_stale(
+    root: &Path,
+    previous: BTreeSet<PathBuf>,
+    files: &BTreeMap<PathBuf, String>,
+) -> Result<(), String> {
+    for relative in previous.iter().filter(|path| !files.contains_key(*path)) {
+        let path = root.join(relative);
+        if path.is_file() {
+            storage::remove(&path)?;
+        }
+
+        // the folder and project directories, never the workspace directory itself
+        for parent in path.ancestors().skip(1) {
+            let is_empty = fs::read_dir(parent).is_ok_and(|mut entries| entries.next().is_none());
+            if parent == root || !parent.starts_with(root) || !is_empty {
+                break;
+            }
+            fs::remove_dir(parent)
+                .map_err(|err| format!("Failed to remove {}, {}", parent.display(), err))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn sub_dirs(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|err| format!("Unable to read {}, {}", dir.display(), err))?;
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(FOLDER_FILE).is_file())
+        .collect();
+    dirs.sort();
+
+    Ok(dirs)
+}
+
+/// File name for a name, `Get users` is `get-users`.
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+
+    for c in name.trim().chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    match slug.trim_matches('-') {
+        "" => "untitled".into(),
+        slug => slug.to_string(),
+    }
+}
+
+/// The slug of the name, numbered when another item of the same directory has it.
+fn unique_slug(name: &str, taken: &mut HashSet<String>) -> String {
+    let base = slug(name);
+    let mut slug = base.clone();
+    let mut index = 1;
+
+    while taken.contains(&slug) {
+        index += 1;
+        slug = format!("{}-{}", base, index);
+    }
+    taken.insert(slug.clone());
+
+    slug
+}
+
+fn to_toml(value: &impl Serialize) -> Result<String, String> {
+    toml::to_string_pretty(value).map_err(|err| format!("Toml save failed, cause {}", err))
+}
+
+fn read_toml<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Unable to read {}, cause {}", path.display(), err))?;
+
+    toml::from_str(&contents)
+        .map_err(|err| format!("Unable to parse {}, cause {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::utils::db::env::Env;
+    use crate::utils::db::project::Project;
+    use crate::utils::request::PendingRequest;
+
+    use super::*;
+
+    fn db() -> DB {
+        let mut project = Project {
+            name: "Users API".into(),
+            ..Default::default()
+        };
+        project.add_request(
+            "Admin users",
+            PendingRequest {
+                name: Some("Get users".into()),
+                ..Default::default()
+            },
+        );
+        project.add_request(
+            "Admin users",
+            PendingRequest {
+                name: Some("Get users".into()),
+                ..Default::default()
+            },
+        );
+
+        DB {
+            items: vec![project, Project::default()],
+            envs: vec![Env::default()],
+            workspace: None,
+            load_error: None,
+        }
+    }
+
+    #[test]
+    fn test_renders_one_file_per_item() {
+        let db = db();
+        let files = render(&db).unwrap();
+        let paths: Vec<&str> = files.keys().filter_map(|path| path.to_str()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "envs/default-env.toml",
+                WORKSPACE_FILE,
+                "projects/unknown-project/project.toml",
+                "projects/unknown-project/root/folder.toml",
+                "projects/unknown-project/root/untitled.toml",
+                "projects/users-api/admin-users/folder.toml",
+                "projects/users-api/admin-users/get-users-2.toml",
+                "projects/users-api/admin-users/get-users.toml",
+                "projects/users-api/project.toml",
+                "projects/users-api/root/folder.toml",
+                "projects/users-api/root/untitled.toml",
+            ]
+        );
+        assert!(!files[Path::new("projects/users-api/project.toml")].contains("requests"));
+        // rendering twice gives the same files, diffs only show real changes
+        assert_eq!(files, render(&db).unwrap());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("falcon-workspace-{}", Uuid::now_v7()));
+        let mut db = db();
+        save(&db, &dir).unwrap();
+
+        let loaded = load(&dir).unwrap();
+        assert_eq!(loaded.workspace.as_deref(), Some(dir.as_path()));
+        for (loaded, saved) in [
+            (
+                serde_json::to_value(&loaded.items),
+                serde_json::to_value(&db.items),
+            ),
+            (
+                serde_json::to_value(&loaded.envs),
+                serde_json::to_value(&db.envs),
+            ),
+        ] {
+            assert_eq!(loaded.unwrap(), saved.unwrap());
+        }
+
+        // workspaces saved before versioning have no version
+        let workspace_file = dir.join(WORKSPACE_FILE);
+        let contents = fs::read_to_string(&workspace_file).unwrap();
+        assert!(contents.contains(&format!("version = {}", SCHEMA_VERSION)));
+        fs::write(&workspace_file, contents.replace("version = 1\n", "")).unwrap();
+        assert_eq!(load(&dir).unwrap().items.len(), 2);
+
+        // files the workspace did not write are never removed
+        let own_file = dir.join("projects/unknown-project/Cargo.toml");
+        fs::write(&own_file, "").unwrap();
+        fs::write(dir.join("envs/pyproject.toml"), "").unwrap();
+
+        db.items.remove(1);
+        save(&db, &dir).unwrap();
+        assert!(!dir.join("projects/unknown-project/root").exists());
+        assert!(!dir.join("projects/unknown-project/project.toml").exists());
+        assert!(own_file.exists());
+        assert!(dir.join("envs/pyproject.toml").exists());
+        assert_eq!(load(&dir).unwrap().items.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
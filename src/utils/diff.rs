@@ -0,0 +1,330 @@
+use serde_json::Value;
+
+/// Above this many line pairs the bodies are compared line by line instead of
+/// computing the longest common subsequence.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffKind {
+    Same,
+    Changed,
+    Removed,
+    Added,
+}
+
+/// A row of a side by side diff, `None` means the side has no line there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffRow {
+    pub kind: DiffKind,
+    pub left: Option<(usize, String)>,
+    pub right: Option<(usize, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonChange {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Changed {
+        path: String,
+        from: Value,
+        to: Value,
+    },
+}
+
+impl JsonChange {
+    pub fn kind(&self) -> DiffKind {
+        match self {
+            JsonChange::Added { .. } => DiffKind::Added,
+            JsonChange::Removed { .. } => DiffKind::Removed,
+            JsonChange::Changed { .. } => DiffKind::Changed,
+        }
+    }
+}
+
+impl std::fmt::Display for JsonChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonChange::Added { path, value } => write!(f, "+ {}: {}", path, value),
+            JsonChange::Removed { path, value } => write!(f, "- {}: {}", path, value),
+            JsonChange::Changed { path, from, to } => write!(f, "~ {}: {} -> {}", path, from, to),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyDiff {
+    pub rows: Vec<DiffRow>,
+    /// Structural changes, only set when both bodies are JSON.
+    pub json_changes: Option<Vec<JsonChange>>,
+}
+
+/// Diffs two response bodies, JSON bodies are normalised (sorted keys, pretty printed)
+/// before the side by side comparison and diffed structurally as well.
+pub fn diff_bodies(left: &str, right: &str) -> BodyDiff {
+    match (
+        serde_json::from_str::<Value>(left),
+        serde_json::from_str::<Value>(right),
+    ) {
+        (Ok(left_value), Ok(right_value)) => {
+            let pretty = |value: &Value| serde_json::to_string_pretty(value).unwrap_or_default();
+
+            BodyDiff {
+                rows: diff_lines(&pretty(&left_value), &pretty(&right_value)),
+                json_changes: Some(diff_json(&left_value, &right_value)),
+            }
+        }
+        _ => BodyDiff {
+            rows: diff_lines(left, right),
+            json_changes: None,
+        },
+    }
+}
+
+pub fn diff_json(left: &Value, right: &Value) -> Vec<JsonChange> {
+    let mut changes = Vec::new();
+    collect_json_changes("$", left, right, &mut changes);
+    changes
+}
+
+fn collect_json_changes(path: &str, left: &Value, right: &Value, changes: &mut Vec<JsonChange>) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            for (key, value) in left.iter() {
+                let path = format!("{}.{}", path, key);
+                match right.get(key) {
+                    Some(other) => collect_json_changes(&path, value, other, changes),
+                    None => changes.push(JsonChange::Removed {
+                        path,
+                        value: value.clone(),
+                    }),
+                }
+            }
+
+            for (key, value) in right.iter().filter(|(key, _)| !left.contains_key(*key)) {
+                changes.push(JsonChange::Added {
+                    path: format!("{}.{}", path, key),
+                    value: value.clone(),
+                });
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for index in 0..left.len().max(right.len()) {
+                let path = format!("{}[{}]", path, index);
+                match (left.get(index), right.get(index)) {
+                    (Some(a), Some(b)) => collect_json_changes(&path, a, b, changes),
+                    (Some(a), None) => changes.push(JsonChange::Removed {
+                        path,
+                        value: a.clone(),
+                    }),
+                    (None, Some(b)) => changes.push(JsonChange::Added {
+                        path,
+                        value: b.clone(),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if left != right => changes.push(JsonChange::Changed {
+            path: path.to_string(),
+            from: left.clone(),
+            to: right.clone(),
+        }),
+        _ => {}
+    }
+}
+
+pub fn diff_lines(left: &str, right: &str) -> Vec<DiffRow> {
+    let left: Vec<&str> = left.lines().collect();
+    let right: Vec<&str> = right.lines().collect();
+
+    let ops = if left.len() * right.len() > MAX_LCS_CELLS {
+        positional_ops(&left, &right)
+    } else {
+        lcs_ops(&left, &right)
+    };
+
+    let mut rows = Vec::new();
+    let mut removed: Vec<(usize, String)> = Vec::new();
+    let mut added: Vec<(usize, String)> = Vec::new();
+
+    // pairs up the pending removed and added lines as changed rows
+    let flush = |rows: &mut Vec<DiffRow>,
+                 removed: &mut Vec<(usize, String)>,
+                 added: &mut Vec<(usize, String)>| {
+        let mut removed = removed.drain(..);
+        let mut added = added.drain(..);
+
+        loop {
+            let row = match (removed.next(), added.next()) {
+                (Some(l), Some(r)) => DiffRow {
+                    kind: DiffKind::Changed,
+                    left: Some(l),
+                    right: Some(r),
+                },
+                (Some(l), None) => DiffRow {
+                    kind: DiffKind::Removed,
+                    left: Some(l),
+                    right: None,
+                },
+                (None, Some(r)) => DiffRow {
+                    kind: DiffKind::Added,
+                    left: None,
+                    right: Some(r),
+                },
+                (None, None) => break,
+            };
+            rows.push(row);
+        }
+    };
+
+    for op in ops {
+        match op {
+            LineOp::Same(l, r) => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push(DiffRow {
+                    kind: DiffKind::Same,
+                    left: Some((l + 1, left[l].to_string())),
+                    right: Some((r + 1, right[r].to_string())),
+                });
+            }
+            LineOp::Removed(l) => removed.push((l + 1, left[l].to_string())),
+            LineOp::Added(r) => added.push((r + 1, right[r].to_string())),
+        }
+    }
+
+    flush(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+enum LineOp {
+    Same(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+fn lcs_ops(left: &[&str], right: &[&str]) -> Vec<LineOp> {
+    let (n, m) = (left.len(), right.len());
+    let mut table = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if left[i] == right[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < n && j < m {
+        if left[i] == right[j] {
+            ops.push(LineOp::Same(i, j));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            ops.push(LineOp::Removed(i));
+            i += 1;
+        } else {
+            ops.push(LineOp::Added(j));
+            j += 1;
+        }
+    }
+
+    ops.extend((i..n).map(LineOp::Removed));
+    ops.extend((j..m).map(LineOp::Added));
+    ops
+}
+
+fn positional_ops(left: &[&str], right: &[&str]) -> Vec<LineOp> {
+    let mut ops = Vec::new();
+
+    for index in 0..left.len().max(right.len()) {
+        match (left.get(index), right.get(index)) {
+            (Some(l), Some(r)) if l == r => ops.push(LineOp::Same(index, index)),
+            (l, r) => {
+                if l.is_some() {
+                    ops.push(LineOp::Removed(index));
+                }
+                if r.is_some() {
+                    ops.push(LineOp::Added(index));
+                }
+            }
+        }
+    }
+
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_lines_pairs_changes() {
+        let rows = diff_lines("a\nb\nc\nd", "a\nB\nc\nd\ne");
+        let kinds: Vec<DiffKind> = rows.iter().map(|row| row.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                DiffKind::Same,
+                DiffKind::Changed,
+                DiffKind::Same,
+                DiffKind::Same,
+                DiffKind::Added
+            ]
+        );
+        assert_eq!(rows[1].left, Some((2, "b".to_string())));
+        assert_eq!(rows[1].right, Some((2, "B".to_string())));
+        assert_eq!(rows[4].left, None);
+    }
+
+    #[test]
+    fn test_diff_json_paths() {
+        let changes = diff_json(
+            &json!({"id": 1, "tags": ["a"], "old": true}),
+            &json!({"id": 2, "tags": ["a", "b"], "new": null}),
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                JsonChange::Changed {
+                    path: "$.id".into(),
+                    from: json!(1),
+                    to: json!(2)
+                },
+                JsonChange::Removed {
+                    path: "$.old".into(),
+                    value: json!(true)
+                },
+                JsonChange::Added {
+                    path: "$.tags[1]".into(),
+                    value: json!("b")
+                },
+                JsonChange::Added {
+                    path: "$.new".into(),
+                    value: json!(null)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_bodies_ignore_key_order_and_formatting() {
+        let diff = diff_bodies(r#"{"b":1,"a":2}"#, "{\n  \"a\": 2,\n  \"b\": 1\n}");
+
+        assert_eq!(diff.json_changes, Some(vec![]));
+        assert!(diff.rows.iter().all(|row| row.kind == DiffKind::Same));
+    }
+}
@@ -12,6 +12,12 @@ impl From<Duration> for FalconDuration {
     }
 }
 
+impl FalconDuration {
+    pub fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
+
 impl Display for FalconDuration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", format_duration(self.0))
@@ -0,0 +1,904 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use base64::Engine;
+use regex::Regex;
+use reqwest::header;
+use uuid::Uuid;
+
+use super::db::env::Env;
+use super::db::project::Project;
+use super::db::storage;
+use super::db::DB;
+use super::import::{convert_placeholders, variable_name};
+use super::request::{FalconAuthorization, PendingRequest, ResolvedRequest};
+
+/// Extensions of the VS Code REST Client and JetBrains HTTP client files.
+pub const EXTENSIONS: [&str; 2] = ["http", "rest"];
+
+/// Requests of the `root` folder live in `root.http`.
+const ROOT_FOLDER: &str = "root";
+
+const METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+/// A directory of `.http` files opened as a project. Every file is a folder, the parsed
+/// files are kept so edits are written back in place with comments and untouched
+/// requests left as they are.
+#[derive(Debug, Clone)]
+pub struct HttpProject {
+    pub dir: PathBuf,
+    pub project_id: Uuid,
+    pub env_id: Uuid,
+    documents: Vec<Document>,
+    /// Names used in the files by env name, `host` for `HOST`.
+    variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+struct Document {
+    /// Relative to the directory.
+    path: PathBuf,
+    folder: String,
+    blocks: Vec<Block>,
+    crlf: bool,
+    final_newline: bool,
+    /// Content last read or written, the file is only written when it changes.
+    content: String,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Block {
+    /// The `###` line starting the block, `None` at the top of the file.
+    separator: Option<String>,
+    /// Comments, variables and blank lines before the request.
+    leading: Vec<String>,
+    request: Option<BlockRequest>,
+    /// Blank lines and response handlers after the request.
+    trailing: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct BlockRequest {
+    id: Uuid,
+    name: String,
+    /// Lines as read, written back while the request is unchanged.
+    lines: Vec<String>,
+    /// The request as read rendered again, tells whether it was edited.
+    rendered: Vec<String>,
+}
+
+impl HttpProject {
+    /// Parses the `.http` files of the directory. Requests found at the same place in
+    /// `previous` keep their id, tests and scripts.
+    pub fn open(dir: &Path, previous: Option<&Project>) -> Result<(Self, Project, Env), String> {
+        let mut files = vec![];
+        find_files(dir, &mut files)?;
+        files.sort();
+
+        let name = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or("HTTP files".into());
+        let mut http = HttpProject {
+            dir: dir.to_path_buf(),
+            project_id: previous.map(|p| p.id).unwrap_or(Uuid::now_v7()),
+            env_id: previous
+                .and_then(|p| p.default_env)
+                .unwrap_or(Uuid::now_v7()),
+            documents: vec![],
+            variables: HashMap::new(),
+        };
+        let mut project = Project {
+            name: name.clone(),
+            is_active: true,
+            id: http.project_id,
+            requests: HashMap::new(),
+            active_request_id: None,
+            default_env: Some(http.env_id),
+            http_dir: Some(dir.to_path_buf()),
+            ..Default::default()
+        };
+        if let Some(previous) = previous {
+            project.name = previous.name.clone();
+            project.is_active = previous.is_active;
+            project.pre_request_script = previous.pre_request_script.clone();
+            project.post_response_script = previous.post_response_script.clone();
+        }
+        let mut env = Env {
+            id: http.env_id,
+            name: format!("{} variables", name),
+            items: vec![],
+            ..Default::default()
+        };
+
+        for path in files {
+            let relative = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+            let content = fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read {}, {}", path.display(), err))?;
+            let folder = folder_name(&relative);
+            let (mut document, requests) =
+                http.parse_document(relative, folder.clone(), &content, &mut env);
+
+            let kept = previous.and_then(|p| p.requests.get(&folder));
+            let requests = requests
+                .into_iter()
+                .enumerate()
+                .map(|(index, mut request)| {
+                    if let Some(old) = kept.and_then(|reqs| reqs.get(index)) {
+                        request.id = old.id;
+                        request.response_filter = old.response_filter.clone();
+                        request.assertions = old.assertions.clone();
+                        request.extractions = old.extractions.clone();
+                        request.pre_request_script = old.pre_request_script.clone();
+                        request.post_response_script = old.post_response_script.clone();
+                    }
+                    request
+                })
+                .collect::<Vec<_>>();
+
+            let mut ids = requests.iter().map(|req| req.id);
+            for block in document.blocks.iter_mut() {
+                if let (Some(request), Some(id)) = (block.request.as_mut(), ids.next()) {
+                    request.id = id;
+                }
+            }
+
+            http.documents.push(document);
+            project.requests.entry(folder).or_default().extend(requests);
+        }
+
+        if project.requests.is_empty() {
+            project.requests.insert(ROOT_FOLDER.into(), vec![]);
+        }
+        project.active_request_id = previous
+            .and_then(|p| p.active_request_id)
+            .filter(|id| project.requests.values().flatten().any(|req| req.id == *id));
+        env.items.push(("".into(), "".into()));
+
+        Ok((http, project, env))
+    }
+
+    /// Writes the edits of the project and its env back to the files.
+    pub fn write(&mut self, db: &DB) -> Result<(), String> {
+        let Some(project) = db.projects().iter().find(|p| p.id == self.project_id) else {
+            return Ok(());
+        };
+        self.update(project, db.env_by_id(self.env_id));
+
+        for document in self.documents.iter_mut() {
+            let content = document.render();
+            if content == document.content {
+                continue;
+            }
+
+            storage::write_atomic(&self.dir.join(&document.path), &content)?;
+            document.content = content;
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self, project: &Project, env: Option<&Env>) {
+        let requests: HashMap<Uuid, (&String, &PendingRequest)> = project
+            .ordered_requests(None)
+            .into_iter()
+            .map(|(folder, req)| (req.id, (folder, req)))
+            .collect();
+        let variables = &self.variables;
+        let mut written = HashSet::new();
+
+        for document in self.documents.iter_mut() {
+            document.blocks.retain_mut(|block| {
+                let Some(block_request) = block.request.as_mut() else {
+                    return true;
+                };
+
+                match requests.get(&block_request.id) {
+                    Some((folder, request)) if **folder == document.folder => {
+                        written.insert(block_request.id);
+                        let rendered = render_request(request, variables);
+                        if rendered != block_request.rendered {
+                            block_request.lines = rendered.clone();
+                            block_request.rendered = rendered;
+                        }
+
+                        let name = request_name(request);
+                        if name != block_request.name {
+                            rename(block, &name);
+                        }
+                        true
+                    }
+                    _ => {
+                        // variables defined above a deleted request are still used by others
+                        block.request = None;
+                        block.trailing.retain(|line| line.trim().is_empty());
+                        match block.leading.iter().rposition(|l| definition(l).is_some()) {
+                            Some(last) => {
+                                block.leading.truncate(last + 1);
+                                true
+                            }
+                            None => false,
+                        }
+                    }
+                }
+            });
+        }
+
+        for (folder, request) in project.ordered_requests(None) {
+            if written.contains(&request.id) {
+                continue;
+            }
+
+            let index = match self.documents.iter().position(|d| d.folder == *folder) {
+                Some(index) => index,
+                None => {
+                    self.documents.push(Document::new(folder));
+                    self.documents.len() - 1
+                }
+            };
+            let name = request_name(request);
+            let lines = render_request(request, &self.variables);
+            self.documents[index].append(Block {
+                separator: Some(original_names(&format!("### {}", name), &self.variables)),
+                leading: vec![],
+                request: Some(BlockRequest {
+                    id: request.id,
+                    name,
+                    lines: lines.clone(),
+                    rendered: lines,
+                }),
+                trailing: vec![],
+            });
+        }
+
+        if let Some(env) = env {
+            self.update_variables(env);
+        }
+    }
+
+    /// Updates the `@name = value` lines, removed env items are removed from the files
+    /// and new ones are defined at the top of the first file.
+    fn update_variables(&mut self, env: &Env) {
+        let items: HashMap<&str, &str> = env
+            .items
+            .iter()
+            .filter(|(name, _)| !name.is_empty())
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        let mut defined = HashSet::new();
+        let variables = self.variables.clone();
+
+        for block in self
+            .documents
+            .iter_mut()
+            .flat_map(|doc| doc.blocks.iter_mut())
+        {
+            block.leading.retain_mut(|line| {
+                let Some((name, value)) = definition(line) else {
+                    return true;
+                };
+                let key = variable_name(&name);
+                defined.insert(key.clone());
+
+                match items.get(key.as_str()) {
+                    Some(new_value) if convert_placeholders(&value) == *new_value => true,
+                    Some(new_value) => {
+                        *line = format!("@{} = {}", name, original_names(new_value, &variables));
+                        true
+                    }
+                    None => false,
+                }
+            });
+        }
+
+        let added: Vec<(&str, &str)> = env
+            .items
+            .iter()
+            .filter(|(name, _)| !name.is_empty() && !defined.contains(name))
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        if added.is_empty() {
+            return;
+        }
+
+        if self.documents.is_empty() {
+            self.documents.push(Document::new(ROOT_FOLDER));
+        }
+        let document = &mut self.documents[0];
+        if document
+            .blocks
+            .first()
+            .is_none_or(|b| b.separator.is_some())
+        {
+            document.blocks.insert(0, Block::default());
+        }
+        let leading = &mut document.blocks[0].leading;
+        let index = leading
+            .iter()
+            .rposition(|line| definition(line).is_some())
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        if index == 0 && !leading.first().is_some_and(|line| line.trim().is_empty()) {
+            leading.insert(0, String::new());
+        }
+
+        let lines: Vec<String> = added
+            .iter()
+            .map(|(name, value)| format!("@{} = {}", name, original_names(value, &variables)))
+            .collect();
+        leading.splice(index..index, lines);
+        for (name, _) in added {
+            self.variables.insert(name.to_string(), name.to_string());
+        }
+    }
+
+    fn parse_document(
+        &mut self,
+        path: PathBuf,
+        folder: String,
+        content: &str,
+        env: &mut Env,
+    ) -> (Document, Vec<PendingRequest>) {
+        let crlf = content.contains("\r\n");
+        let final_newline = content.ends_with('\n');
+        let mut lines: Vec<String> = content
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+            .collect();
+        if final_newline {
+            lines.pop();
+        }
+
+        let mut chunks: Vec<(Option<String>, Vec<String>)> = vec![(None, vec![])];
+        for line in lines {
+            if line.trim_start().starts_with("###") {
+                chunks.push((Some(line), vec![]));
+            } else if let Some(chunk) = chunks.last_mut() {
+                chunk.1.push(line);
+            }
+        }
+        if chunks[0].1.is_empty() && chunks.len() > 1 {
+            chunks.remove(0);
+        }
+
+        let mut requests = vec![];
+        let blocks = chunks
+            .into_iter()
+            .map(|(separator, lines)| {
+                let (block, request) = self.parse_block(separator, lines, env);
+                requests.extend(request);
+                block
+            })
+            .collect();
+
+        let document = Document {
+            path,
+            folder,
+            blocks,
+            crlf,
+            final_newline,
+            content: content.to_string(),
+        };
+
+        (document, requests)
+    }
+
+    fn parse_block(
+        &mut self,
+        separator: Option<String>,
+        lines: Vec<String>,
+        env: &mut Env,
+    ) -> (Block, Option<PendingRequest>) {
+        let mut name = separator
+            .as_deref()
+            .map(|line| line.trim_start().trim_start_matches('#').trim().to_string())
+            .filter(|name| !name.is_empty());
+        let mut block = Block {
+            separator,
+            ..Default::default()
+        };
+
+        let mut lines = lines.into_iter();
+        let mut request_line = None;
+        for line in lines.by_ref() {
+            let trimmed = line.trim();
+
+            if let Some((var, value)) = definition(&line) {
+                self.remember(&var);
+                env.set_variable(variable_name(&var), convert_placeholders(&value));
+            } else if is_comment(trimmed) {
+                if let Some(request_name) = comment_name(trimmed) {
+                    name = Some(request_name);
+                }
+            } else if !trimmed.is_empty() {
+                request_line = Some(line);
+                break;
+            }
+            block.leading.push(line);
+        }
+
+        let Some(request_line) = request_line else {
+            return (block, None);
+        };
+
+        let mut rest: Vec<String> = lines.collect();
+        let headers_end = rest
+            .iter()
+            .position(|line| line.trim().is_empty())
+            .unwrap_or(rest.len());
+        let handlers = rest[headers_end..]
+            .iter()
+            .position(|line| is_handler(line.trim()))
+            .map(|index| headers_end + index)
+            .unwrap_or(rest.len());
+        block.trailing = rest.split_off(handlers);
+        while rest.len() > headers_end && rest.last().is_some_and(|l| l.trim().is_empty()) {
+            block.trailing.insert(0, rest.pop().unwrap_or_default());
+        }
+
+        let (method, mut url) = request_target(&request_line);
+        let mut headers = vec![];
+        for line in rest[..headers_end].iter().map(|line| line.trim()) {
+            if line.starts_with('?') || line.starts_with('&') {
+                url.push_str(line);
+            } else if let Some((key, value)) = line.split_once(':').filter(|_| !is_comment(line)) {
+                headers.push((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+        let body = rest.get(headers_end + 1..).unwrap_or_default().join("\n");
+
+        for text in [&url, &body]
+            .into_iter()
+            .chain(headers.iter().map(|(_, value)| value))
+        {
+            for var in references(text) {
+                self.remember(&var);
+            }
+        }
+
+        let mut request = to_request(&method, &url, headers, &body);
+        let name = name.unwrap_or(default_name(&request));
+        request.name = Some(name.clone());
+
+        let mut lines = vec![request_line];
+        lines.extend(rest);
+        block.request = Some(BlockRequest {
+            id: request.id,
+            name,
+            lines,
+            rendered: render_request(&request, &self.variables),
+        });
+
+        (block, Some(request))
+    }
+
+    fn remember(&mut self, name: &str) {
+        self.variables
+            .entry(variable_name(name))
+            .or_insert(name.to_string());
+    }
+}
+
+impl Document {
+    fn new(folder: &str) -> Self {
+        let mut path: PathBuf = folder.split(" > ").collect();
+        path.set_extension(EXTENSIONS[0]);
+
+        Self {
+            path,
+            folder: folder.to_string(),
+            blocks: vec![],
+            crlf: false,
+            final_newline: true,
+            content: String::new(),
+        }
+    }
+
+    fn append(&mut self, block: Block) {
+        if let Some(last) = self.blocks.last_mut() {
+            let ends_blank = last
+                .lines()
+                .last()
+                .is_some_and(|line: &String| line.trim().is_empty());
+            if !ends_blank {
+                last.trailing.push(String::new());
+            }
+        }
+        self.blocks.push(block);
+    }
+
+    fn render(&self) -> String {
+        let lines: Vec<&String> = self.blocks.iter().flat_map(|block| block.lines()).collect();
+        let newline = if self.crlf { "\r\n" } else { "\n" };
+
+        let mut content = lines
+            .iter()
+            .map(|line| line.as_str())
+            .collect::<Vec<_>>()
+            .join(newline);
+        if self.final_newline && !lines.is_empty() {
+            content.push_str(newline);
+        }
+        content
+    }
+}
+
+impl Block {
+    fn lines(&self) -> impl Iterator<Item = &String> {
+        self.separator
+            .iter()
+            .chain(self.leading.iter())
+            .chain(self.request.iter().flat_map(|request| request.lines.iter()))
+            .chain(self.trailing.iter())
+    }
+}
+
+/// Renames a request in its `@name` comment if it has one, or in its `###` separator.
+fn rename(block: &mut Block, name: &str) {
+    let line = block
+        .leading
+        .iter_mut()
+        .find(|line| comment_name(line.trim()).is_some());
+
+    match line {
+        Some(line) => {
+            if let Some(index) = line.find("@name") {
+                *line = format!("{}@name {}", &line[..index], name);
+            }
+        }
+        None => block.separator = Some(format!("### {}", name)),
+    }
+    if let Some(request) = block.request.as_mut() {
+        request.name = name.to_string();
+    }
+}
+
+fn find_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|err| format!("Failed to read {}, {}", dir.display(), err))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+
+        if path.is_dir() && !hidden {
+            find_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| EXTENSIONS.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// `users.http` is the folder `users`, `admin/users.http` the folder `admin > users`.
+fn folder_name(relative: &Path) -> String {
+    relative
+        .with_extension("")
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join(" > ")
+}
+
+fn is_comment(line: &str) -> bool {
+    line.starts_with('#') || line.starts_with("//")
+}
+
+/// Lines running scripts or saving the response once the request is done.
+fn is_handler(line: &str) -> bool {
+    line.starts_with("> ") || line.starts_with("<> ") || line.starts_with(">> ")
+}
+
+/// The name of `# @name login` and `// @name login` comments.
+fn comment_name(line: &str) -> Option<String> {
+    let rest = line.trim_start_matches(['#', '/']).trim_start();
+    rest.strip_prefix("@name")
+        .filter(|name| name.starts_with([' ', '=']))
+        .map(|name| name.trim_start_matches([' ', '=']).trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// A `@name = value` variable definition.
+fn definition(line: &str) -> Option<(String, String)> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"^@([A-Za-z0-9_.\-]+)\s*=\s*(.*)$").unwrap());
+
+    re.captures(line.trim())
+        .map(|caps| (caps[1].to_string(), caps[2].trim().to_string()))
+}
+
+/// The variables a text refers to, system variables like `{{$guid}}` aside.
+fn references(text: &str) -> Vec<String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.\-]+)\s*\}\}").unwrap());
+
+    re.captures_iter(text)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Puts the names used in the files back in place of the env names.
+fn original_names(text: &str, variables: &HashMap<String, String>) -> String {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"\{\{([A-Z0-9_]+)\}\}").unwrap());
+
+    re.replace_all(text, |caps: &regex::Captures| {
+        match variables.get(&caps[1]) {
+            Some(name) => format!("{{{{{}}}}}", name),
+            None => caps[0].to_string(),
+        }
+    })
+    .to_string()
+}
+
+/// Splits `POST https://api.test HTTP/1.1`, a line with only a URL is a GET.
+fn request_target(line: &str) -> (String, String) {
+    let mut parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() > 1 && parts.last().is_some_and(|part| part.starts_with("HTTP/")) {
+        parts.pop();
+    }
+
+    match parts.split_first() {
+        Some((method, url)) if METHODS.contains(method) && !url.is_empty() => {
+            (method.to_string(), url.join(" "))
+        }
+        _ => ("GET".into(), parts.join(" ")),
+    }
+}
+
+fn to_request(
+    method: &str,
+    url: &str,
+    headers: Vec<(String, String)>,
+    body: &str,
+) -> PendingRequest {
+    let url = convert_placeholders(url);
+    let (url, query) = match url.split_once('?') {
+        Some((url, query)) => (url.to_string(), Some(query.to_string())),
+        None => (url, None),
+    };
+
+    let headers: Vec<(String, String)> = headers
+        .into_iter()
+        .map(|(name, value)| {
+            let value = convert_placeholders(&value);
+            if !name.eq_ignore_ascii_case(header::AUTHORIZATION.as_str()) {
+                return (name, value);
+            }
+
+            // `Basic user:password` and `Basic user password` are written in clear
+            let value = match value.split_once(' ') {
+                Some((scheme, credentials))
+                    if scheme.eq_ignore_ascii_case("basic")
+                        && (credentials.contains(':') || credentials.trim().contains(' ')) =>
+                {
+                    let credentials = credentials.trim().replacen(' ', ":", 1);
+                    let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+                    format!("Basic {}", encoded)
+                }
+                _ => value,
+            };
+            (name, value)
+        })
+        .collect();
+
+    let resolved = ResolvedRequest {
+        method: method.into(),
+        url,
+        headers,
+        body: convert_placeholders(body),
+    };
+    let mut request = PendingRequest::from(&resolved);
+
+    if let Some(query) = query {
+        request.queries = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((name, value)) => (name.to_string(), value.to_string()),
+                None => (pair.to_string(), String::new()),
+            })
+            .collect();
+        request.queries.push(("".into(), "".into()));
+    }
+
+    request
+}
+
+fn request_name(request: &PendingRequest) -> String {
+    request
+        .name
+        .clone()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or(default_name(request))
+}
+
+fn default_name(request: &PendingRequest) -> String {
+    let path = url::Url::parse(&request.url)
+        .map(|url| url.path().to_string())
+        .unwrap_or(request.url.clone());
+
+    format!("{} {}", request.method.0, path)
+}
+
+/// The request in `.http` syntax, with the variable names of the files.
+fn render_request(request: &PendingRequest, variables: &HashMap<String, String>) -> Vec<String> {
+    let filled = |items: &[(String, String)]| {
+        items
+            .iter()
+            .filter(|(name, _)| !name.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let mut url = request.url.clone();
+    let queries: Vec<String> = filled(&request.queries)
+        .into_iter()
+        .map(|(name, value)| match value.is_empty() {
+            true => name,
+            false => format!("{}={}", name, value),
+        })
+        .collect();
+    if !queries.is_empty() {
+        url = format!("{}?{}", url, queries.join("&"));
+    }
+
+    let mut lines = vec![format!("{} {}", request.method.0, url)];
+    for (name, value) in filled(&request.headers) {
+        lines.push(format!("{}: {}", name, value));
+    }
+    match &request.authorization {
+        FalconAuthorization::Bearer { prefix, token } if !token.is_empty() => {
+            lines.push(format!("Authorization: {} {}", prefix, token));
+        }
+        FalconAuthorization::Basic { username, password } if !username.is_empty() => {
+            lines.push(format!("Authorization: Basic {}:{}", username, password));
+        }
+        _ => {}
+    }
+    let cookies: Vec<String> = filled(&request.cookies)
+        .into_iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    if !cookies.is_empty() {
+        lines.push(format!("Cookie: {}", cookies.join("; ")));
+    }
+
+    let body = request.body.to_string();
+    if !body.trim().is_empty() {
+        if !request.has_header(header::CONTENT_TYPE.as_str()) {
+            lines.push("Content-Type: application/json".into());
+        }
+        lines.push(String::new());
+        lines.extend(body.lines().map(String::from));
+    }
+
+    lines
+        .into_iter()
+        .map(|line| original_names(&line, variables))
+        .collect()
+}
+
+/// Opens the directory as a project of the database, or reloads the project that was
+/// opened from it before.
+pub fn open_in(db: &mut DB, dir: &Path) -> Result<HttpProject, String> {
+    let previous = db
+        .projects()
+        .iter()
+        .find(|project| project.http_dir.as_deref() == Some(dir))
+        .cloned();
+    let (http, project, env) = HttpProject::open(dir, previous.as_ref())?;
+
+    match db.env_by_id_mut(env.id) {
+        Some(existing) => existing.items = env.items,
+        None => db.add_env(env),
+    }
+    match db.project_by_id_mut(project.id) {
+        Some(existing) => *existing = project,
+        None => db.add(project),
+    }
+
+    Ok(http)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::request::FlBody;
+
+    use super::*;
+
+    const USERS: &str = "@host = https://api.test
+@token = abc
+
+# Lists the users
+GET {{host}}/users
+    ?page=2
+Accept: application/json
+Authorization: Bearer {{token}}
+
+### Create user
+// creates Ada
+POST {{host}}/users HTTP/1.1
+Content-Type: application/vnd.api+json
+
+{\"name\": \"Ada\"}
+
+> {% client.global.set(\"id\", response.body.id); %}
+
+###
+# @name remove
+DELETE {{host}}/users/1
+";
+
+    fn open_users() -> (HttpProject, Project, Env) {
+        let dir = std::env::temp_dir().join(format!("falcon-http-{}", Uuid::now_v7()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("users.http"), USERS).unwrap();
+        let opened = HttpProject::open(&dir, None).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        opened
+    }
+
+    #[test]
+    fn test_parses_requests_and_variables() {
+        let (_, project, env) = open_users();
+        let users = &project.requests["users"];
+
+        assert_eq!(users.len(), 3);
+        assert_eq!(env.variable("HOST"), Some("https://api.test"));
+        assert_eq!(users[0].name.as_deref(), Some("GET {{HOST}}/users"));
+        assert_eq!(users[0].url, "{{HOST}}/users");
+        assert_eq!(users[0].queries[0], ("page".into(), "2".into()));
+        assert_eq!(
+            users[0].headers[0],
+            ("Accept".into(), "application/json".into())
+        );
+        assert!(matches!(
+            users[0].authorization,
+            FalconAuthorization::Bearer { ref token, .. } if token == "{{TOKEN}}"
+        ));
+        assert_eq!(users[1].name.as_deref(), Some("Create user"));
+        assert_eq!(users[1].method.0, reqwest::Method::POST);
+        assert_eq!(users[1].body.to_string(), "{\"name\": \"Ada\"}");
+        assert_eq!(
+            users[1].headers[0],
+            ("Content-Type".into(), "application/vnd.api+json".into())
+        );
+        assert_eq!(users[2].name.as_deref(), Some("remove"));
+    }
+
+    #[test]
+    fn test_writes_edits_back() {
+        let (mut http, mut project, mut env) = open_users();
+        assert_eq!(http.documents[0].render(), USERS);
+
+        let users = project.requests.get_mut("users").unwrap();
+        users[1].body = FlBody::ApplicationJson("{\"name\": \"Grace\"}".into());
+        users[2].name = Some("delete user".into());
+        users.remove(0);
+        project.add_request(
+            "users",
+            PendingRequest {
+                url: "{{HOST}}/health".into(),
+                ..Default::default()
+            },
+        );
+        env.set_variable("TOKEN", "xyz");
+
+        http.update(&project, Some(&env));
+        let content = http.documents[0].render();
+
+        assert!(content.starts_with("@host = https://api.test\n@token = xyz\n\n### Create user\n"));
+        assert!(!content.contains("Lists the users"));
+        assert!(content.contains("// creates Ada\nPOST {{host}}/users\nContent-Type: application/vnd.api+json\n\n{\"name\": \"Grace\"}\n\n> {%"));
+        assert!(content.contains("# @name delete user\nDELETE {{host}}/users/1\n\n### GET {{host}}/health\nGET {{host}}/health\n"));
+    }
+}
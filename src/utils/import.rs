@@ -0,0 +1,275 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::db::env::Env;
+use super::db::project::Project;
+use super::db::response_history::{ResponseHistory, ResponseRecord};
+use super::db::DB;
+
+pub mod curl;
+pub mod har;
+pub mod hurl;
+pub mod openapi;
+pub mod postman;
+
+/// What an import created, and everything it could not convert.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ImportReport {
+    pub requests: usize,
+    pub folders: usize,
+    pub envs: usize,
+    pub unsupported: Vec<String>,
+}
+
+impl ImportReport {
+    pub fn unsupported(&mut self, message: impl Into<String>) {
+        self.unsupported.push(message.into());
+    }
+}
+
+impl Display for ImportReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Imported {} requests in {} folders and {} envs",
+            self.requests, self.folders, self.envs
+        )?;
+
+        match self.unsupported.len() {
+            0 => Ok(()),
+            count => write!(f, ", {} items not supported", count),
+        }
+    }
+}
+
+/// Projects and envs read from a file, ready to be added to the DB.
+#[derive(Debug, Default, Clone)]
+pub struct Imported {
+    pub projects: Vec<Project>,
+    pub envs: Vec<Env>,
+    pub report: ImportReport,
+    /// Responses recorded with the imported requests, kept as examples on request.
+    pub responses: Vec<(Uuid, ResponseRecord)>,
+    /// Re-importing updates the projects and envs with the same name instead of adding copies.
+    pub merge: bool,
+}
+
+impl Imported {
+    /// Adds the imported projects and envs to the DB, the last project becomes the active one.
+    pub fn apply(self, db: &mut DB) -> ImportReport {
+        let mut report = self.report;
+        let mut merged_envs = vec![];
+
+        for env in self.envs {
+            match db.env_by_name_mut(&env.name).filter(|_| self.merge) {
+                Some(existing) => {
+                    merged_envs.push((env.id, existing.id));
+                    existing.base_url = env.base_url;
+                    for (key, value) in env.items.into_iter().filter(|(key, _)| !key.is_empty()) {
+                        if existing.variable(&key).is_none() {
+                            existing.set_variable(key, value);
+                        }
+                    }
+                }
+                None => db.add_env(env),
+            }
+        }
+
+        for mut project in self.projects {
+            if let Some((_, id)) = merged_envs
+                .iter()
+                .find(|(imported, _)| Some(*imported) == project.default_env)
+            {
+                project.default_env = Some(*id);
+            }
+
+            match db.project_by_name_mut(&project.name).filter(|_| self.merge) {
+                Some(existing) => {
+                    let id = existing.id;
+                    openapi::merge(existing, project, &mut report);
+                    db.set_active(&id);
+                }
+                None => {
+                    project.is_active = true;
+                    db.add(project);
+                }
+            }
+        }
+
+        report
+    }
+}
+
+/// Formats a project is exported to, for other tools.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Postman,
+    OpenApi,
+    Hurl,
+}
+
+impl ExportFormat {
+    pub fn file_name(&self, project: &str) -> String {
+        match self {
+            ExportFormat::Postman => format!("{}.postman_collection.json", project),
+            ExportFormat::OpenApi => format!("{}.openapi.json", project),
+            ExportFormat::Hurl => format!("{}.hurl", project),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Postman | ExportFormat::OpenApi => "json",
+            ExportFormat::Hurl => "hurl",
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ExportFormat::Postman => "Postman collection",
+            ExportFormat::OpenApi => "OpenAPI 3.1",
+            ExportFormat::Hurl => "Hurl",
+        };
+
+        write!(f, "{}", label)
+    }
+}
+
+/// Exports the project with its default env, saved responses describe the OpenAPI responses.
+pub fn export_project(
+    project: &Project,
+    env: Option<&Env>,
+    history: &ResponseHistory,
+    format: ExportFormat,
+) -> Result<String, String> {
+    match format {
+        ExportFormat::Postman => postman::export_collection(project, env),
+        ExportFormat::OpenApi => openapi::export_spec(project, env, history),
+        ExportFormat::Hurl => hurl::export_hurl(project),
+    }
+}
+
+const UNSUPPORTED: &str =
+    "Unsupported file, expected a Postman collection or environment, an OpenAPI specification, a HAR or a Hurl file";
+
+/// Reads a file and imports it with the importer matching its content.
+pub fn import_file(path: &Path) -> Result<Imported, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Unable to read {}, cause {}", path.display(), err))?;
+
+    if hurl::is_hurl(&contents) {
+        let name = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or("Hurl import".into());
+        return hurl::import_hurl(&contents, &name);
+    }
+
+    import_str(&contents)
+}
+
+pub fn import_str(contents: &str) -> Result<Imported, String> {
+    if hurl::is_hurl(contents) {
+        return hurl::import_hurl(contents, "Hurl import");
+    }
+
+    // YAML is a superset of JSON, but JSON files are parsed as such for better errors
+    let json: Value = serde_json::from_str(contents)
+        .or_else(|_| serde_yaml::from_str(contents))
+        .map_err(|_| UNSUPPORTED.to_string())?;
+
+    if openapi::is_spec(&json) {
+        return openapi::import_spec(&json);
+    }
+
+    if har::is_har(&json) {
+        return har::import_har(contents);
+    }
+
+    if json.get("info").is_some() && json.get("item").is_some() {
+        let import = postman::import_collection(contents)?;
+        let mut project = import.project;
+        project.default_env = import.env.as_ref().map(|env| env.id);
+
+        return Ok(Imported {
+            projects: vec![project],
+            envs: import.env.into_iter().collect(),
+            report: import.report,
+            ..Default::default()
+        });
+    }
+
+    if json.get("values").is_some() && json.get("name").is_some() {
+        let (env, report) = postman::import_environment(contents)?;
+
+        return Ok(Imported {
+            projects: vec![],
+            envs: vec![env],
+            report,
+            ..Default::default()
+        });
+    }
+
+    Err(UNSUPPORTED.into())
+}
+
+/// Env variables only match upper case names, `baseUrl` becomes `BASE_URL`.
+pub fn variable_name(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous_lower = false;
+
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lower {
+                result.push('_');
+            }
+            previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+            result.push(c.to_ascii_uppercase());
+        } else {
+            if !result.ends_with('_') {
+                result.push('_');
+            }
+            previous_lower = false;
+        }
+    }
+
+    result
+}
+
+/// Rewrites every `{{name}}` placeholder with the env name of the variable.
+/// Dynamic variables like `{{$guid}}` are left untouched.
+pub fn convert_placeholders(input: &str) -> String {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.\-]+)\s*\}\}").unwrap());
+
+    re.replace_all(input, |caps: &regex::Captures| {
+        format!("{{{{{}}}}}", variable_name(&caps[1]))
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variable_names() {
+        assert_eq!(variable_name("baseUrl"), "BASE_URL");
+        assert_eq!(variable_name("api-key"), "API_KEY");
+        assert_eq!(variable_name("USER_ID"), "USER_ID");
+        assert_eq!(variable_name("v2Token"), "V2_TOKEN");
+
+        assert_eq!(
+            convert_placeholders("{{baseUrl}}/users/{{ userId }}?t={{$timestamp}}"),
+            "{{BASE_URL}}/users/{{USER_ID}}?t={{$timestamp}}"
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::VecDeque;
+
+use base64::Engine;
+use reqwest::header;
+
+use crate::utils::request::{PendingRequest, ResolvedRequest};
+
+use super::ImportReport;
+
+/// Options without a value that change nothing in the request.
+const IGNORED_FLAGS: [&str; 12] = [
+    "-s",
+    "--silent",
+    "-S",
+    "--show-error",
+    "-v",
+    "--verbose",
+    "-i",
+    "--include",
+    "-L",
+    "--location",
+    "--compressed",
+    "-#",
+];
+
+/// Options with a value that change nothing in the request.
+const IGNORED_OPTIONS: [&str; 8] = [
+    "-o",
+    "--output",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "-w",
+    "--write-out",
+    "--retry",
+];
+
+/// Short options without a value the import handles, they can be clustered like `-sk`.
+const SHORT_FLAGS: [&str; 3] = ["-G", "-I", "-k"];
+
+/// Short options with a value the import handles, the value can follow them like `-XPOST`.
+const SHORT_OPTIONS: [&str; 8] = ["-X", "-H", "-A", "-e", "-b", "-d", "-F", "-u"];
+
+/// A request parsed from a cURL command, with the options it could not keep.
+#[derive(Debug, Clone)]
+pub struct CurlImport {
+    pub request: PendingRequest,
+    pub report: ImportReport,
+}
+
+/// Whether the input looks like a cURL command rather than a URL.
+pub fn is_command(input: &str) -> bool {
+    input.trim_start().starts_with("curl ")
+}
+
+/// Parses a cURL command as copied from the browser devtools or the docs of an API.
+pub fn import_command(command: &str) -> Result<CurlImport, String> {
+    let mut args: VecDeque<String> = split_args(command)?.into();
+
+    if args.pop_front().as_deref() != Some("curl") {
+        return Err("Not a cURL command".into());
+    }
+
+    let mut report = ImportReport::default();
+    let mut method: Option<String> = None;
+    let mut url: Option<String> = None;
+    let mut headers: Vec<(String, String)> = vec![];
+    let mut data: Vec<String> = vec![];
+    let mut data_in_query = false;
+
+    while let Some(arg) = args.pop_front() {
+        let (name, mut inline) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => {
+                (name.to_string(), Some(value.to_string()))
+            }
+            _ if arg.starts_with('-') && !arg.starts_with("--") => split_short(&arg, &mut args),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| {
+            inline
+                .take()
+                .or_else(|| args.pop_front())
+                .ok_or(format!("Missing value for {}", name))
+        };
+
+        match name.as_str() {
+            "-X" | "--request" => method = Some(value(&name)?.to_uppercase()),
+            "--url" => url = Some(value(&name)?),
+            "-H" | "--header" => {
+                let header = value(&name)?;
+                match header.split_once(':') {
+                    Some((key, value)) => {
+                        headers.push((key.trim().to_string(), value.trim().to_string()))
+                    }
+                    None => report.unsupported(format!("Header {}", header)),
+                }
+            }
+            "-A" | "--user-agent" => {
+                headers.push((header::USER_AGENT.to_string(), value(&name)?));
+            }
+            "-e" | "--referer" => headers.push((header::REFERER.to_string(), value(&name)?)),
+            "-b" | "--cookie" => {
+                let cookies = value(&name)?;
+                match cookies.contains('=') {
+                    true => headers.push((header::COOKIE.to_string(), cookies)),
+                    false => report.unsupported(format!("Cookie file {}", cookies)),
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii"
+            | "--data-urlencode" => {
+                let body = value(&name)?;
+                match body.strip_prefix('@').filter(|_| name != "--data-raw") {
+                    Some(file) => report.unsupported(format!("Body read from file {}", file)),
+                    None if name == "--data-urlencode" => data.push(urlencode(&body)),
+                    None => data.push(body),
+                }
+            }
+            "--json" => {
+                data.push(value(&name)?);
+                headers.push((header::CONTENT_TYPE.to_string(), "application/json".into()));
+                headers.push((header::ACCEPT.to_string(), "application/json".into()));
+            }
+            "-F" | "--form" | "--form-string" => {
+                let field = value(&name)?;
+                method.get_or_insert("POST".into());
+                report.unsupported(format!("Multipart form field {}", field));
+            }
+            "-u" | "--user" => {
+                let credentials = value(&name)?;
+                let encoded = base64::engine::general_purpose::STANDARD.encode(&credentials);
+                headers.push((
+                    header::AUTHORIZATION.to_string(),
+                    format!("Basic {}", encoded),
+                ));
+            }
+            "--oauth2-bearer" => {
+                headers.push((
+                    header::AUTHORIZATION.to_string(),
+                    format!("Bearer {}", value(&name)?),
+                ));
+            }
+            "-G" | "--get" => data_in_query = true,
+            "-I" | "--head" => method = Some("HEAD".into()),
+            "-k" | "--insecure" => {
+                report.unsupported("Insecure TLS (-k), certificates are always verified")
+            }
+            flag if IGNORED_FLAGS.contains(&flag) => {}
+            option if IGNORED_OPTIONS.contains(&option) => {
+                value(option)?;
+            }
+            option if option.starts_with('-') => {
+                report.unsupported(format!("Option {}", option));
+            }
+            _ => url = Some(arg),
+        }
+    }
+
+    let mut url = url.ok_or("The cURL command has no URL".to_string())?;
+    if !url.contains("://") {
+        url = format!("http://{}", url);
+    }
+
+    let mut body = data.join("&");
+    if data_in_query && !body.is_empty() {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        url = format!("{}{}{}", url, separator, body);
+        body = String::new();
+    }
+
+    // sending data makes curl switch to POST
+    let method = method.unwrap_or(match body.is_empty() {
+        true => "GET".into(),
+        false => "POST".into(),
+    });
+
+    let resolved = ResolvedRequest {
+        method: method.as_str().into(),
+        url,
+        headers,
+        body,
+    };
+    let mut request = PendingRequest::from(&resolved);
+    let path = url::Url::parse(&request.url)
+        .map(|url| url.path().to_string())
+        .unwrap_or(request.url.clone());
+    request.name = Some(format!("{} {}", method, path));
+    report.requests = 1;
+
+    Ok(CurlImport { request, report })
+}
+
+/// Splits `-XPOST` into the option and its value, and `-sk` into `-s` with `-k` queued
+/// after it. A cluster with an option the import does not know stays whole and is
+/// reported as it is.
+fn split_short(arg: &str, args: &mut VecDeque<String>) -> (String, Option<String>) {
+    let Some((at, _)) = arg.char_indices().nth(2) else {
+        return (arg.to_string(), None);
+    };
+    let (name, rest) = arg.split_at(at);
+
+    if SHORT_OPTIONS.contains(&name) || IGNORED_OPTIONS.contains(&name) {
+        (name.to_string(), Some(rest.to_string()))
+    } else if SHORT_FLAGS.contains(&name) || IGNORED_FLAGS.contains(&name) {
+        args.push_front(format!("-{}", rest));
+        (name.to_string(), None)
+    } else {
+        (arg.to_string(), None)
+    }
+}
+
+/// `--data-urlencode` encodes the value after the first `=`, or the whole content.
+fn urlencode(data: &str) -> String {
+    let encode = |value: &str| -> String {
+        url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+    };
+
+    match data.split_once('=') {
+        Some((name, value)) => format!("{}={}", name, encode(value)),
+        None => encode(data),
+    }
+}
+
+/// Splits the command like a POSIX shell would, with line continuations and `$'...'` strings.
+fn split_args(command: &str) -> Result<Vec<String>, String> {
+    let mut args = vec![];
+    let mut current: Option<String> = None;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(next) => current.get_or_insert_default().push(next),
+                None => {}
+            },
+            '\'' => {
+                let arg = current.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => return Err("Unterminated ' quote".into()),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                let arg = current.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => arg.push('\n'),
+                            Some('t') => arg.push('\t'),
+                            Some('r') => arg.push('\r'),
+                            Some(c) => arg.push(c),
+                            None => return Err("Unterminated $' quote".into()),
+                        },
+                        Some(c) => arg.push(c),
+                        None => return Err("Unterminated $' quote".into()),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => arg.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                arg.push('\\');
+                                arg.push(c);
+                            }
+                            None => return Err("Unterminated \" quote".into()),
+                        },
+                        Some(c) => arg.push(c),
+                        None => return Err("Unterminated \" quote".into()),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if let Some(arg) = current.take() {
+                    args.push(arg);
+                }
+            }
+            c => current.get_or_insert_default().push(c),
+        }
+    }
+
+    if let Some(arg) = current {
+        args.push(arg);
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::request::FalconAuthorization;
+
+    use super::*;
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(
+            split_args("curl 'a b' \"c \\\"d\\\"\" e\\ f \\\n  $'g\\nh'").unwrap(),
+            vec!["curl", "a b", "c \"d\"", "e f", "g\nh"]
+        );
+        assert!(split_args("curl 'open").is_err());
+    }
+
+    #[test]
+    fn test_imports_browser_command() {
+        let import = import_command(
+            r#"curl 'https://api.test/users?page=2' \
+  -H 'accept: application/json' \
+  -H 'authorization: Bearer abc.def' \
+  -b 'session=s1; theme=dark' \
+  --data-raw '{"name":"Ada"}' \
+  --compressed -k"#,
+        )
+        .unwrap();
+        let request = import.request;
+
+        assert_eq!(request.method.0, reqwest::Method::POST);
+        assert_eq!(request.url, "https://api.test/users");
+        assert_eq!(request.name.as_deref(), Some("POST /users"));
+        assert_eq!(request.queries[0], ("page".into(), "2".into()));
+        assert_eq!(
+            request.headers[0],
+            ("accept".into(), "application/json".into())
+        );
+        assert_eq!(request.cookies[1], ("theme".into(), "dark".into()));
+        assert_eq!(request.body.to_string(), r#"{"name":"Ada"}"#);
+        assert!(matches!(
+            request.authorization,
+            FalconAuthorization::Bearer { ref token, .. } if token == "abc.def"
+        ));
+        assert_eq!(import.report.unsupported.len(), 1);
+    }
+
+    #[test]
+    fn test_keeps_the_content_type() {
+        let import = import_command(
+            "curl api.test -d a=b -H 'Content-Type: application/x-www-form-urlencoded'",
+        )
+        .unwrap();
+
+        assert_eq!(
+            import.request.headers[0],
+            (
+                "Content-Type".into(),
+                "application/x-www-form-urlencoded".into()
+            )
+        );
+        assert_eq!(import.request.body.to_string(), "a=b");
+    }
+
+    #[test]
+    fn test_imports_basic_auth_and_get_data() {
+        let request = import_command(
+            "curl -XDELETE -u ada:secret api.test/items -G -d id=1 --data-urlencode 'q=a b'",
+        )
+        .unwrap()
+        .request;
+
+        assert_eq!(request.method.0, reqwest::Method::DELETE);
+        assert_eq!(request.url, "http://api.test/items");
+        assert_eq!(request.queries[0], ("id".into(), "1".into()));
+        assert_eq!(request.queries[1], ("q".into(), "a b".into()));
+        assert!(matches!(
+            request.authorization,
+            FalconAuthorization::Basic { ref username, ref password }
+                if username == "ada" && password == "secret"
+        ));
+
+        assert!(import_command("curl -H 'a: b'").is_err());
+        assert!(import_command("wget https://api.test").is_err());
+    }
+
+    #[test]
+    fn test_clustered_and_non_ascii_short_options() {
+        let import = import_command("curl -sSLk -é -zq -HAccept:text/plain api.test").unwrap();
+
+        assert_eq!(
+            import.request.headers[0],
+            ("Accept".into(), "text/plain".into())
+        );
+        assert_eq!(
+            import.report.unsupported,
+            vec![
+                "Insecure TLS (-k), certificates are always verified".to_string(),
+                "Option -é".to_string(),
+                "Option -zq".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,579 @@
+use std::collections::HashMap;
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use reqwest::header;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::utils::db::project::Project;
+use crate::utils::db::request_log::RequestLogEntry;
+use crate::utils::db::response_history::ResponseRecord;
+use crate::utils::request::{PendingRequest, ResolvedRequest};
+
+use super::{ImportReport, Imported};
+
+/// Headers set by the browser or the HTTP client, never copied into requests.
+const SKIPPED_HEADERS: [&str; 4] = ["host", "content-length", "connection", "accept-encoding"];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Log {
+    version: String,
+    creator: Creator,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pages: Vec<Page>,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Creator {
+    name: String,
+    version: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Page {
+    #[serde(default)]
+    title: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    started_date_time: DateTime<Utc>,
+    #[serde(default)]
+    time: f64,
+    request: Request,
+    response: Response,
+    #[serde(default)]
+    cache: Cache,
+    #[serde(default)]
+    timings: Timings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    method: String,
+    url: String,
+    #[serde(default = "http_version")]
+    http_version: String,
+    #[serde(default)]
+    cookies: Vec<NameValue>,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    #[serde(default)]
+    query_string: Vec<NameValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    post_data: Option<PostData>,
+    #[serde(default = "unknown_size")]
+    headers_size: i64,
+    #[serde(default = "unknown_size")]
+    body_size: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Response {
+    status: u16,
+    #[serde(default)]
+    status_text: String,
+    #[serde(default = "http_version")]
+    http_version: String,
+    #[serde(default)]
+    cookies: Vec<NameValue>,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    content: Content,
+    #[serde(default, rename = "redirectURL")]
+    redirect_url: String,
+    #[serde(default = "unknown_size")]
+    headers_size: i64,
+    #[serde(default = "unknown_size")]
+    body_size: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostData {
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    params: Vec<NameValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Content {
+    #[serde(default)]
+    size: i64,
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encoding: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NameValue {
+    name: String,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cache {}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Timings {
+    #[serde(default)]
+    send: f64,
+    #[serde(default)]
+    wait: f64,
+    #[serde(default)]
+    receive: f64,
+}
+
+fn http_version() -> String {
+    "HTTP/1.1".into()
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
+
+/// Whether the document is a HAR archive.
+pub fn is_har(json: &serde_json::Value) -> bool {
+    json.pointer("/log/entries").is_some()
+}
+
+/// Converts a HAR archive into a project with a folder per host.
+/// The recorded responses are returned apart, to be kept as examples on request.
+pub fn import_har(json: &str) -> Result<Imported, String> {
+    let har: Har = serde_json::from_str(json).map_err(|err| format!("Not a HAR file, {}", err))?;
+
+    let mut report = ImportReport::default();
+    let mut requests: HashMap<String, Vec<PendingRequest>> = HashMap::new();
+    let mut responses = vec![];
+
+    for entry in har.log.entries {
+        let (folder, request) = to_request(&entry.request, &mut report);
+
+        if let Some(record) = to_record(&entry) {
+            responses.push((request.id, record));
+        }
+        requests.entry(folder).or_default().push(request);
+    }
+
+    report.folders = requests.len();
+    report.requests = requests.values().map(Vec::len).sum();
+
+    let name = har
+        .log
+        .pages
+        .first()
+        .map(|page| page.title.trim())
+        .filter(|title| !title.is_empty())
+        .unwrap_or("HAR import")
+        .to_string();
+
+    Ok(Imported {
+        projects: vec![Project {
+            name,
+            id: Uuid::now_v7(),
+            requests,
+            ..Default::default()
+        }],
+        responses,
+        report,
+        ..Default::default()
+    })
+}
+
+fn to_request(har: &Request, report: &mut ImportReport) -> (String, PendingRequest) {
+    let mut headers: Vec<(String, String)> = har
+        .headers
+        .iter()
+        // HTTP/2 pseudo headers like `:authority`
+        .filter(|item| !item.name.starts_with(':'))
+        .filter(|item| !SKIPPED_HEADERS.contains(&item.name.to_lowercase().as_str()))
+        .map(|item| (item.name.clone(), item.value.clone()))
+        .collect();
+
+    let has_cookie_header = headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(header::COOKIE.as_str()));
+    if !has_cookie_header && !har.cookies.is_empty() {
+        let cookies: Vec<String> = har
+            .cookies
+            .iter()
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        headers.push((header::COOKIE.to_string(), cookies.join("; ")));
+    }
+
+    // the type of the body is kept, sending only sets JSON when there is none
+    let has_content_type = headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(header::CONTENT_TYPE.as_str()));
+    if let Some(data) = har.post_data.as_ref().filter(|data| !data.mime_type.is_empty()) {
+        if !has_content_type && !data.text.is_empty() {
+            headers.push((header::CONTENT_TYPE.to_string(), data.mime_type.clone()));
+        }
+    }
+
+    let body = match &har.post_data {
+        Some(data) if data.text.is_empty() && !data.params.is_empty() => {
+            report.unsupported(format!("Form body of {} {}", har.method, har.url));
+            String::new()
+        }
+        Some(data) => data.text.clone(),
+        None => String::new(),
+    };
+
+    let resolved = ResolvedRequest {
+        method: har.method.as_str().into(),
+        url: har.url.clone(),
+        headers,
+        body,
+    };
+    let mut request = PendingRequest::from(&resolved);
+
+    let url = url::Url::parse(&har.url).ok();
+    let folder = url
+        .as_ref()
+        .and_then(|url| url.host_str().map(String::from))
+        .unwrap_or("root".into());
+    let path = url.as_ref().map(|url| url.path()).unwrap_or(&har.url);
+    request.name = Some(format!("{} {}", har.method, path));
+
+    (folder, request)
+}
+
+/// Requests blocked or cancelled by the browser are recorded with a status 0.
+fn to_record(entry: &Entry) -> Option<ResponseRecord> {
+    let response = &entry.response;
+    if response.status == 0 {
+        return None;
+    }
+
+    let text = response.content.text.clone().unwrap_or_default();
+    let body = match response.content.encoding.as_deref() {
+        Some("base64") => base64::engine::general_purpose::STANDARD
+            .decode(text.as_bytes())
+            .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+            .unwrap_or_default(),
+        _ => text,
+    };
+
+    Some(ResponseRecord {
+        id: Uuid::now_v7(),
+        timestamp: entry.started_date_time,
+        status_code: response.status,
+        headers: response
+            .headers
+            .iter()
+            .filter(|item| !item.name.starts_with(':'))
+            .map(|item| (item.name.clone(), item.value.clone()))
+            .collect(),
+        size_kb: (body.len() as f64 / 1024.0).ceil(),
+        body,
+        duration_ms: entry.time.max(0.0) as u64,
+        env_name: None,
+    })
+}
+
+/// Writes the sent requests with the responses they received as a HAR 1.2 archive.
+pub fn export_log(entries: &[&RequestLogEntry]) -> Result<String, String> {
+    let har = Har {
+        log: Log {
+            version: "1.2".into(),
+            creator: Creator {
+                name: "Falcon".into(),
+                version: env!("CARGO_PKG_VERSION").into(),
+            },
+            pages: vec![],
+            entries: entries.iter().map(|entry| to_entry(entry)).collect(),
+        },
+    };
+
+    serde_json::to_string_pretty(&har).map_err(|err| format!("HAR export failed, cause {}", err))
+}
+
+fn to_entry(entry: &RequestLogEntry) -> Entry {
+    let request = &entry.request;
+    let url = url::Url::parse(&request.url).ok();
+
+    let cookies = request
+        .header(header::COOKIE.as_str())
+        .map(|value| {
+            value
+                .split(';')
+                .filter_map(|cookie| cookie.trim().split_once('='))
+                .map(|(name, value)| NameValue {
+                    name: name.into(),
+                    value: value.into(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let post_data = match request.body.is_empty() {
+        true => None,
+        false => Some(PostData {
+            mime_type: request
+                .header(header::CONTENT_TYPE.as_str())
+                .unwrap_or("application/json")
+                .into(),
+            text: request.body.clone(),
+            params: vec![],
+        }),
+    };
+
+    let response = match &entry.response {
+        Some(record) => Response {
+            status: record.status_code,
+            status_text: reqwest::StatusCode::from_u16(record.status_code)
+                .ok()
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or_default()
+                .into(),
+            http_version: http_version(),
+            cookies: vec![],
+            headers: name_values(&record.headers),
+            content: Content {
+                size: record.body.len() as i64,
+                mime_type: record
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(header::CONTENT_TYPE.as_str()))
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default(),
+                text: Some(record.body.clone()),
+                encoding: None,
+            },
+            redirect_url: String::new(),
+            headers_size: -1,
+            body_size: record.body.len() as i64,
+        },
+        // failed sends have no response, HAR uses status 0 for them
+        None => Response {
+            status: 0,
+            status_text: String::new(),
+            http_version: http_version(),
+            cookies: vec![],
+            headers: vec![],
+            content: Content {
+                size: 0,
+                mime_type: String::new(),
+                text: None,
+                encoding: None,
+            },
+            redirect_url: String::new(),
+            headers_size: -1,
+            body_size: -1,
+        },
+    };
+
+    let time = entry.duration_ms.unwrap_or_default() as f64;
+
+    Entry {
+        started_date_time: entry.timestamp,
+        time,
+        request: Request {
+            method: request.method.0.to_string(),
+            url: request.url.clone(),
+            http_version: http_version(),
+            cookies,
+            headers: name_values(&request.headers),
+            query_string: url
+                .map(|url| {
+                    url.query_pairs()
+                        .map(|(name, value)| NameValue {
+                            name: name.into(),
+                            value: value.into(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            post_data,
+            headers_size: -1,
+            body_size: request.body.len() as i64,
+        },
+        response,
+        cache: Cache::default(),
+        timings: Timings {
+            send: 0.0,
+            wait: time,
+            receive: 0.0,
+        },
+        comment: entry.error.clone(),
+    }
+}
+
+fn name_values(items: &[(String, String)]) -> Vec<NameValue> {
+    items
+        .iter()
+        .map(|(name, value)| NameValue {
+            name: name.clone(),
+            value: value.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::request::FalconAuthorization;
+
+    use super::*;
+
+    const HAR: &str = r#"{
+      "log": {
+        "version": "1.2",
+        "creator": {"name": "Firefox", "version": "126.0"},
+        "pages": [{"title": "Shop"}],
+        "entries": [
+          {
+            "startedDateTime": "2024-06-01T10:00:00.000+02:00",
+            "time": 42.5,
+            "request": {
+              "method": "POST",
+              "url": "https://api.shop.test/orders?draft=true",
+              "httpVersion": "HTTP/2",
+              "headers": [
+                {"name": ":authority", "value": "api.shop.test"},
+                {"name": "Authorization", "value": "Bearer abc"},
+                {"name": "Content-Type", "value": "application/json"},
+                {"name": "X-Client", "value": "web"}
+              ],
+              "cookies": [{"name": "session", "value": "s1"}],
+              "queryString": [{"name": "draft", "value": "true"}],
+              "postData": {"mimeType": "application/json", "text": "{\"qty\":1}"},
+              "headersSize": -1,
+              "bodySize": 9
+            },
+            "response": {
+              "status": 201,
+              "statusText": "Created",
+              "headers": [{"name": "content-type", "value": "application/json"}],
+              "content": {"size": 8, "mimeType": "application/json", "text": "eyJpZCI6N30=", "encoding": "base64"},
+              "redirectURL": ""
+            },
+            "cache": {},
+            "timings": {"send": 1, "wait": 40, "receive": 1.5}
+          },
+          {
+            "startedDateTime": "2024-06-01T10:00:01.000Z",
+            "time": 0,
+            "request": {"method": "GET", "url": "https://cdn.shop.test/app.js", "headers": []},
+            "response": {"status": 0, "content": {}}
+          }
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn test_imports_har() {
+        let imported = import_har(HAR).unwrap();
+        let project = &imported.projects[0];
+
+        assert_eq!(project.name, "Shop");
+        assert_eq!(imported.report.requests, 2);
+        assert_eq!(imported.report.folders, 2);
+
+        let order = &project.requests["api.shop.test"][0];
+        assert_eq!(order.name.as_deref(), Some("POST /orders"));
+        assert_eq!(order.url, "https://api.shop.test/orders");
+        assert_eq!(order.queries[0], ("draft".into(), "true".into()));
+        assert_eq!(
+            order.headers,
+            vec![("X-Client".into(), "web".into()), ("".into(), "".into())]
+        );
+        assert_eq!(order.cookies[0], ("session".into(), "s1".into()));
+        assert!(
+            matches!(order.authorization, FalconAuthorization::Bearer { ref token, .. } if token == "abc")
+        );
+        assert_eq!(order.body.to_string(), "{\"qty\":1}");
+
+        // the blocked request has no response to keep
+        assert_eq!(imported.responses.len(), 1);
+        let (id, record) = &imported.responses[0];
+        assert_eq!(*id, order.id);
+        assert_eq!(record.status_code, 201);
+        assert_eq!(record.body, "{\"id\":7}");
+        assert_eq!(record.duration_ms, 42);
+    }
+
+    #[test]
+    fn test_keeps_the_type_of_the_body() {
+        let har = HAR
+            .replace(r#"{"name": "Content-Type", "value": "application/json"},"#, "")
+            .replace(
+                r#""mimeType": "application/json", "text": "{\"qty\":1}""#,
+                r#""mimeType": "text/xml", "text": "<order/>""#,
+            );
+        let imported = import_har(&har).unwrap();
+        let order = &imported.projects[0].requests["api.shop.test"][0];
+
+        assert_eq!(
+            order.headers[1],
+            ("content-type".into(), "text/xml".into())
+        );
+        assert_eq!(order.body.to_string(), "<order/>");
+    }
+
+    #[test]
+    fn test_exports_request_log() {
+        let response = crate::utils::request::FalconResponse::from(&ResponseRecord {
+            id: Uuid::now_v7(),
+            timestamp: Utc::now(),
+            status_code: 404,
+            headers: vec![("content-type".into(), "text/plain".into())],
+            body: "missing".into(),
+            duration_ms: 12,
+            size_kb: 1.0,
+            env_name: None,
+        });
+        let request = ResolvedRequest {
+            method: "PUT".into(),
+            url: "https://api.test/items/1?force=1".into(),
+            headers: vec![("Cookie".into(), "a=1; b=2".into())],
+            body: "{}".into(),
+        };
+        let sent = RequestLogEntry::new(request.clone(), None, &Ok(response));
+        let failed = RequestLogEntry::new(request, None, &Err("Connection refused".into()));
+
+        let har = export_log(&[&sent, &failed]).unwrap();
+        let parsed: Har = serde_json::from_str(&har).unwrap();
+        let entry = &parsed.log.entries[0];
+
+        assert_eq!(parsed.log.version, "1.2");
+        assert_eq!(entry.request.method, "PUT");
+        assert_eq!(entry.request.query_string[0].name, "force");
+        assert_eq!(entry.request.cookies.len(), 2);
+        assert_eq!(entry.request.post_data.as_ref().unwrap().text, "{}");
+        assert_eq!(entry.response.status, 404);
+        assert_eq!(entry.response.status_text, "Not Found");
+        assert_eq!(entry.response.content.text.as_deref(), Some("missing"));
+        assert_eq!(entry.time, 12.0);
+
+        let failed = &parsed.log.entries[1];
+        assert_eq!(failed.response.status, 0);
+        assert_eq!(failed.comment.as_deref(), Some("Connection refused"));
+
+        // what Falcon exports it can import back
+        let imported = import_har(&har).unwrap();
+        assert_eq!(imported.report.requests, 2);
+        assert_eq!(imported.responses.len(), 1);
+    }
+}
@@ -0,0 +1,738 @@
+use std::sync::OnceLock;
+
+use base64::Engine;
+use regex::Regex;
+use reqwest::header;
+use serde_json::Value;
+
+use crate::utils::db::env::Env;
+use crate::utils::db::project::Project;
+use crate::utils::request::assertion::{Assertion, AssertionKind};
+use crate::utils::request::extraction::{Extraction, ExtractionSource};
+use crate::utils::request::{FalconAuthorization, PendingRequest, RequestUrl, ResolvedRequest};
+
+use super::{convert_placeholders, variable_name, ImportReport, Imported};
+
+const METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+/// Hurl variable the base url of the env is exported as.
+const BASE_URL_VARIABLE: &str = "base_url";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Headers,
+    Query,
+    Cookies,
+    BasicAuth,
+    Skipped,
+    Body,
+    Multiline,
+    ResponseHeaders,
+    Captures,
+    Asserts,
+}
+
+/// What an assert or capture reads from the response.
+#[derive(Debug, PartialEq)]
+enum Query {
+    Status,
+    Duration,
+    Body,
+    Header(String),
+    JsonPath(String),
+    Cookie(String),
+    Regex(String),
+}
+
+/// An entry with the comments right above its request line.
+struct Entry {
+    comments: Vec<String>,
+    lines: Vec<String>,
+}
+
+/// Whether the file starts like a Hurl file, with a request line after the comments.
+pub fn is_hurl(contents: &str) -> bool {
+    contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .is_some_and(|line| request_line(line).is_some())
+}
+
+/// A Hurl file is a project running its entries in order, captures become extractions
+/// and asserts become assertions. Captured and referenced variables are items of its env.
+pub fn import_hurl(contents: &str, name: &str) -> Result<Imported, String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+
+    let mut report = ImportReport::default();
+    let requests: Vec<PendingRequest> = split_entries(contents)
+        .iter()
+        .map(|entry| parse_entry(entry, &mut report))
+        .collect();
+
+    if requests.is_empty() {
+        return Err("The Hurl file has no request".into());
+    }
+
+    let mut env = Env {
+        name: format!("{} variables", name),
+        items: vec![],
+        ..Default::default()
+    };
+    let re = RE.get_or_init(|| Regex::new(r"\{\{([A-Z0-9_]+)\}\}").unwrap());
+    for request in requests.iter() {
+        let mut texts = vec![request.url.clone(), request.body.to_string()];
+        for (_, value) in request
+            .headers
+            .iter()
+            .chain(request.queries.iter())
+            .chain(request.cookies.iter())
+        {
+            texts.push(value.clone());
+        }
+
+        let mut names: Vec<String> = texts
+            .iter()
+            .flat_map(|text| re.captures_iter(text).map(|caps| caps[1].to_string()))
+            .collect();
+        names.extend(request.extractions.iter().map(|e| e.variable.clone()));
+        for name in names {
+            if env.variable(&name).is_none() {
+                env.add_item(name, "");
+            }
+        }
+    }
+    env.items.push(("".into(), "".into()));
+
+    report.requests = requests.len();
+    report.folders = 1;
+    report.envs = 1;
+
+    let mut project = Project {
+        name: name.to_string(),
+        default_env: Some(env.id),
+        ..Default::default()
+    };
+    project.requests.insert("root".into(), requests);
+
+    Ok(Imported {
+        projects: vec![project],
+        envs: vec![env],
+        report,
+        ..Default::default()
+    })
+}
+
+fn split_entries(contents: &str) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = vec![];
+    let mut comments = vec![];
+    let mut in_multiline = false;
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+
+        if in_multiline {
+            in_multiline = !trimmed.starts_with("```");
+        } else if request_line(trimmed).is_some() {
+            entries.push(Entry {
+                comments: std::mem::take(&mut comments),
+                lines: vec![trimmed.to_string()],
+            });
+            continue;
+        } else if let Some(comment) = trimmed.strip_prefix('#') {
+            comments.push(comment.trim().to_string());
+            continue;
+        } else {
+            comments.clear();
+            in_multiline = trimmed.starts_with("```") && !is_oneline(trimmed);
+        }
+
+        if let Some(entry) = entries.last_mut() {
+            entry.lines.push(line.to_string());
+        }
+    }
+
+    entries
+}
+
+fn parse_entry(entry: &Entry, report: &mut ImportReport) -> PendingRequest {
+    let (method, url) = request_line(&entry.lines[0]).unwrap_or_default();
+    let url = convert_placeholders(&url);
+    let mut headers: Vec<(String, String)> = vec![];
+    let mut queries = vec![];
+    let mut cookies = vec![];
+    let mut body = vec![];
+    let mut assertions = vec![];
+    let mut extractions = vec![];
+    let mut section = Section::Headers;
+
+    for line in entry.lines[1..].iter() {
+        let trimmed = line.trim();
+
+        if section == Section::Multiline {
+            match trimmed.starts_with("```") {
+                true => section = Section::Body,
+                false => body.push(convert_placeholders(line)),
+            }
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(status) = response_line(trimmed) {
+            if let Some(status) = status {
+                assertions.push(Assertion::new(AssertionKind::StatusEquals, "", status));
+            }
+            section = Section::ResponseHeaders;
+            continue;
+        }
+        if let Some(name) = section_name(trimmed) {
+            let response = matches!(
+                section,
+                Section::ResponseHeaders | Section::Captures | Section::Asserts
+            );
+            section = match (name, response) {
+                ("QueryStringParams" | "Query", false) => Section::Query,
+                ("Cookies", false) => Section::Cookies,
+                ("BasicAuth", false) => Section::BasicAuth,
+                ("Captures", true) => Section::Captures,
+                ("Asserts", true) => Section::Asserts,
+                (name, _) => {
+                    report.unsupported(format!("[{}] of {} {}", name, method, url));
+                    Section::Skipped
+                }
+            };
+            continue;
+        }
+
+        let pair = key_value(trimmed).map(|(key, value)| (key, convert_placeholders(&value)));
+        match (section, pair) {
+            (Section::Body | Section::Multiline, _) => body.push(convert_placeholders(line)),
+            (Section::Skipped, _) => {}
+            (Section::Headers, Some(pair)) => headers.push(pair),
+            (Section::Query, Some(pair)) => queries.push(pair),
+            (Section::Cookies, Some(pair)) => cookies.push(pair),
+            (Section::BasicAuth, Some((username, password))) => {
+                let credentials = format!("{}:{}", username, password);
+                let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+                headers.push((
+                    header::AUTHORIZATION.to_string(),
+                    format!("Basic {}", encoded),
+                ));
+            }
+            (Section::Headers | Section::Query | Section::Cookies | Section::BasicAuth, None) => {
+                section = Section::Body;
+                if is_oneline(trimmed) {
+                    body.push(convert_placeholders(trimmed.trim_matches('`')));
+                } else if trimmed.starts_with("```") {
+                    section = Section::Multiline;
+                } else if ["base64,", "hex,", "file,"]
+                    .iter()
+                    .any(|prefix| trimmed.starts_with(prefix))
+                {
+                    report.unsupported(format!("Body {} of {} {}", trimmed, method, url));
+                } else {
+                    body.push(convert_placeholders(line));
+                }
+            }
+            (Section::ResponseHeaders, Some((name, value))) => {
+                let expected = format!("^{}$", regex::escape(&value));
+                assertions.push(Assertion::new(AssertionKind::HeaderMatches, name, expected));
+            }
+            (Section::Captures, _) => match parse_capture(&convert_placeholders(trimmed)) {
+                Some(extraction) => extractions.push(extraction),
+                None => report.unsupported(format!("Capture {}", trimmed)),
+            },
+            (Section::Asserts, _) => match parse_assert(&convert_placeholders(trimmed)) {
+                Some(assertion) => assertions.push(assertion),
+                None => report.unsupported(format!("Assert {}", trimmed)),
+            },
+            (_, None) => report.unsupported(format!("Line {} of {} {}", trimmed, method, url)),
+        }
+    }
+
+    if !cookies.is_empty() {
+        let cookies: Vec<String> = cookies
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        headers.push((header::COOKIE.to_string(), cookies.join("; ")));
+    }
+
+    let resolved = ResolvedRequest {
+        method: method.as_str().into(),
+        url,
+        headers,
+        body: body.join("\n").trim().to_string(),
+    };
+    let mut request = PendingRequest::from(&resolved);
+    if !queries.is_empty() {
+        request.queries.pop();
+        request.queries.extend(queries);
+        request.queries.push(("".into(), "".into()));
+    }
+
+    let path = url::Url::parse(&request.url)
+        .map(|url| url.path().to_string())
+        .unwrap_or(request.url.clone());
+    request.name = Some(match entry.comments.last() {
+        Some(comment) if !comment.is_empty() => comment.clone(),
+        _ => format!("{} {}", method, path),
+    });
+    request.assertions = assertions;
+    request.extractions = extractions;
+
+    request
+}
+
+/// `GET https://api.test/users`.
+fn request_line(line: &str) -> Option<(String, String)> {
+    let (method, url) = line.split_once(char::is_whitespace)?;
+    let url = url.trim();
+
+    (METHODS.contains(&method) && !url.is_empty() && !url.contains(char::is_whitespace))
+        .then(|| (method.to_string(), url.to_string()))
+}
+
+/// `HTTP 200`, `HTTP/1.1 200` or `HTTP *`, with the status to assert.
+fn response_line(line: &str) -> Option<Option<String>> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"^HTTP(/[0-9.]+)?\s+(\d{3}|\*)$").unwrap());
+
+    re.captures(line)
+        .map(|caps| Some(caps[2].to_string()).filter(|status| status != "*"))
+}
+
+fn section_name(line: &str) -> Option<&str> {
+    line.strip_prefix('[')?.strip_suffix(']')
+}
+
+/// A `` `text` `` string on a single line.
+fn is_oneline(line: &str) -> bool {
+    line.len() > 2 && line.starts_with('`') && line.ends_with('`') && !line.starts_with("```")
+}
+
+fn key_value(line: &str) -> Option<(String, String)> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"^([A-Za-z0-9_\-.]+)\s*:\s*(.*)$").unwrap());
+
+    re.captures(line)
+        .map(|caps| (caps[1].to_string(), caps[2].trim().to_string()))
+}
+
+/// A `"quoted"` string, or a `/regex/`, with the rest of the input.
+fn parse_string(input: &str) -> Option<(String, &str)> {
+    let input = input.trim_start();
+    let delimiter = input.chars().next().filter(|c| *c == '"' || *c == '/')?;
+    let mut result = String::new();
+    let mut chars = input.char_indices().skip(1);
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            c if c == delimiter => return Some((result, &input[index + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => result.push('\n'),
+                't' => result.push('\t'),
+                // regex escapes are kept for the regex
+                c if delimiter == '/' && c != '/' => {
+                    result.push('\\');
+                    result.push(c);
+                }
+                c => result.push(c),
+            },
+            c => result.push(c),
+        }
+    }
+
+    None
+}
+
+fn parse_query(input: &str) -> Option<(Query, &str)> {
+    let input = input.trim_start();
+    let (name, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+
+    match name {
+        "status" => Some((Query::Status, rest)),
+        "duration" => Some((Query::Duration, rest)),
+        "body" => Some((Query::Body, rest)),
+        "header" | "jsonpath" | "cookie" | "regex" => {
+            let (argument, rest) = parse_string(rest)?;
+            let query = match name {
+                "header" => Query::Header(argument),
+                "jsonpath" => Query::JsonPath(argument),
+                "cookie" => Query::Cookie(argument),
+                _ => Query::Regex(argument),
+            };
+            Some((query, rest))
+        }
+        _ => None,
+    }
+}
+
+/// `token: jsonpath "$.token"`, filters are not supported.
+fn parse_capture(line: &str) -> Option<Extraction> {
+    let (variable, query) = line.split_once(':')?;
+    let (query, rest) = parse_query(query)?;
+    if !rest.trim().is_empty() {
+        return None;
+    }
+
+    let (source, expression) = match query {
+        Query::JsonPath(path) => (ExtractionSource::JsonPath, path),
+        Query::Header(name) => (ExtractionSource::Header, name),
+        Query::Cookie(name) => (ExtractionSource::Cookie, name),
+        Query::Regex(regex) => (ExtractionSource::Regex, regex),
+        _ => return None,
+    };
+
+    Some(Extraction::new(source, expression, variable_name(variable)))
+}
+
+/// `jsonpath "$.name" == "Ada"`, for the predicates Falcon assertions can check.
+fn parse_assert(line: &str) -> Option<Assertion> {
+    let (query, rest) = parse_query(line)?;
+    let rest = rest.trim();
+    let (predicate, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let value = value.trim();
+    let string = || parse_string(value).map(|(text, _)| text);
+
+    let (kind, target, expected) = match (query, predicate) {
+        (Query::Status, "==") => (AssertionKind::StatusEquals, String::new(), value.into()),
+        (Query::Duration, "<") => (
+            AssertionKind::ResponseTimeBelow,
+            String::new(),
+            value.into(),
+        ),
+        (Query::Duration, "<=") => {
+            let limit = value.parse::<u64>().ok()? + 1;
+            (
+                AssertionKind::ResponseTimeBelow,
+                String::new(),
+                limit.to_string(),
+            )
+        }
+        (Query::Body, "contains") => (AssertionKind::BodyContains, String::new(), string()?),
+        (Query::Header(name), "exists") => (AssertionKind::HeaderPresent, name, String::new()),
+        (Query::Header(name), predicate) => {
+            let expected = match predicate {
+                "==" => format!("^{}$", regex::escape(&string()?)),
+                "contains" => regex::escape(&string()?),
+                "startsWith" => format!("^{}", regex::escape(&string()?)),
+                "endsWith" => format!("{}$", regex::escape(&string()?)),
+                "matches" => string()?,
+                _ => return None,
+            };
+            (AssertionKind::HeaderMatches, name, expected)
+        }
+        (Query::JsonPath(path), "exists") => (AssertionKind::JsonPathExists, path, String::new()),
+        (Query::JsonPath(path), "==") => {
+            // Hurl strings are JSON strings, numbers, booleans and null are the same
+            let expected = match string() {
+                Some(text) => Value::String(text).to_string(),
+                None => value.to_string(),
+            };
+            (AssertionKind::JsonPathEquals, path, expected)
+        }
+        (Query::JsonPath(path), predicate) => {
+            let kind = match predicate {
+                "isString" => "string",
+                "isNumber" | "isInteger" | "isFloat" => "number",
+                "isBoolean" => "boolean",
+                "isList" => "array",
+                "isObject" => "object",
+                _ => return None,
+            };
+            (AssertionKind::JsonPathType, path, kind.to_string())
+        }
+        _ => return None,
+    };
+
+    Some(Assertion::new(kind, target, expected))
+}
+
+/// Exports the requests of the project in run order, their assertions and extractions
+/// become asserts and captures. Env variables keep their name, in lower case.
+pub fn export_hurl(project: &Project) -> Result<String, String> {
+    let entries: Vec<String> = project
+        .ordered_requests(None)
+        .into_iter()
+        .map(|(_, request)| export_request(request))
+        .collect();
+
+    if entries.is_empty() {
+        return Err("The project has no request to export".into());
+    }
+
+    Ok(entries.join("\n"))
+}
+
+fn export_request(request: &PendingRequest) -> String {
+    let filled = |items: &[(String, String)]| {
+        items
+            .iter()
+            .filter(|(name, _)| !name.trim().is_empty())
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect::<Vec<_>>()
+    };
+    let mut lines = vec![];
+
+    if let Some(name) = request.name.as_ref().filter(|name| !name.trim().is_empty()) {
+        lines.push(format!("# {}", name));
+    }
+    lines.push(format!("{} {}", request.method.0, request.url));
+    lines.extend(filled(&request.headers));
+
+    let body = request.body.to_string();
+    if !body.trim().is_empty() && !request.has_header(header::CONTENT_TYPE.as_str()) {
+        lines.push("Content-Type: application/json".into());
+    }
+    let mut sections = vec![];
+    match &request.authorization {
+        FalconAuthorization::Bearer { prefix, token } if !token.is_empty() => {
+            lines.push(format!("Authorization: {} {}", prefix, token));
+        }
+        FalconAuthorization::Basic { username, password } if !username.is_empty() => {
+            sections.push("[BasicAuth]".to_string());
+            sections.push(format!("{}: {}", username, password));
+        }
+        _ => {}
+    }
+    for (name, items) in [
+        ("QueryStringParams", &request.queries),
+        ("Cookies", &request.cookies),
+    ] {
+        let items = filled(items);
+        if !items.is_empty() {
+            sections.push(format!("[{}]", name));
+            sections.extend(items);
+        }
+    }
+    lines.extend(sections);
+
+    if !body.trim().is_empty() {
+        match serde_json::from_str::<Value>(&body) {
+            Ok(_) => lines.extend(body.trim().lines().map(String::from)),
+            Err(_) => {
+                lines.push("```".into());
+                lines.extend(body.lines().map(String::from));
+                lines.push("```".into());
+            }
+        }
+    }
+
+    let mut assertions = request.assertions.iter().peekable();
+    let status = assertions
+        .next_if(|assertion| assertion.kind == AssertionKind::StatusEquals)
+        .map(|assertion| assertion.expected.trim().to_string())
+        .filter(|status| !status.is_empty());
+    lines.push(String::new());
+    lines.push(format!("HTTP {}", status.as_deref().unwrap_or("*")));
+
+    let captures: Vec<String> = request
+        .extractions
+        .iter()
+        .filter(|extraction| !extraction.variable.trim().is_empty())
+        .map(|extraction| {
+            let query = match extraction.source {
+                ExtractionSource::JsonPath => "jsonpath",
+                ExtractionSource::Header => "header",
+                ExtractionSource::Cookie => "cookie",
+                ExtractionSource::Regex => "regex",
+            };
+            format!(
+                "{}: {} {}",
+                extraction.variable.trim().to_lowercase(),
+                query,
+                quote(extraction.expression.trim())
+            )
+        })
+        .collect();
+    if !captures.is_empty() {
+        lines.push("[Captures]".into());
+        lines.extend(captures);
+    }
+
+    let asserts: Vec<String> = assertions.flat_map(export_assertion).collect();
+    if !asserts.is_empty() {
+        lines.push("[Asserts]".into());
+        lines.extend(asserts);
+    }
+
+    let mut entry = lines
+        .into_iter()
+        .map(|line| hurl_variables(&line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    entry.push('\n');
+    entry
+}
+
+fn export_assertion(assertion: &Assertion) -> Vec<String> {
+    let target = quote(assertion.target.trim());
+    let expected = assertion.expected.trim();
+
+    match assertion.kind {
+        AssertionKind::StatusEquals => vec![format!("status == {}", expected)],
+        AssertionKind::StatusInRange => match expected.split_once('-') {
+            Some((from, to)) => vec![
+                format!("status >= {}", from.trim()),
+                format!("status <= {}", to.trim()),
+            ],
+            None => vec![format!("status == {}", expected)],
+        },
+        AssertionKind::HeaderPresent => vec![format!("header {} exists", target)],
+        AssertionKind::HeaderMatches => {
+            vec![format!("header {} matches {}", target, quote(expected))]
+        }
+        AssertionKind::JsonPathEquals => {
+            // a value that is not JSON is compared as text, as Falcon does
+            let value = match serde_json::from_str::<Value>(expected) {
+                Ok(_) => expected.to_string(),
+                Err(_) => quote(expected),
+            };
+            vec![format!("jsonpath {} == {}", target, value)]
+        }
+        AssertionKind::JsonPathExists => vec![format!("jsonpath {} exists", target)],
+        AssertionKind::JsonPathType => {
+            let predicate = match expected.to_lowercase().as_str() {
+                "string" => "isString",
+                "number" => "isNumber",
+                "boolean" => "isBoolean",
+                "array" => "isList",
+                "object" => "isObject",
+                _ => return vec![format!("jsonpath {} == null", target)],
+            };
+            vec![format!("jsonpath {} {}", target, predicate)]
+        }
+        AssertionKind::BodyContains => vec![format!("body contains {}", quote(expected))],
+        AssertionKind::ResponseTimeBelow => vec![format!("duration < {}", expected)],
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Env variables in the Hurl naming, `{{API_KEY}}` becomes `{{api_key}}`.
+fn hurl_variables(text: &str) -> String {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"\{\{([A-Z0-9_]+)\}\}").unwrap());
+
+    let text = text.replace(
+        RequestUrl::BASE_URL_PLACEHOLDER,
+        &format!("{{{{{}}}}}", BASE_URL_VARIABLE),
+    );
+    re.replace_all(&text, |caps: &regex::Captures| {
+        format!("{{{{{}}}}}", caps[1].to_lowercase())
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENARIO: &str = r#"# Login
+POST {{host}}/login
+[BasicAuth]
+ada: secret
+{
+  "remember": true
+}
+HTTP 200
+Content-Type: application/json
+[Captures]
+token: jsonpath "$.token"
+[Asserts]
+jsonpath "$.token" isString
+duration < 500
+
+# List the users
+GET {{host}}/users
+Authorization: Bearer {{token}}
+[QueryStringParams]
+page: 2
+[Options]
+retry: 3
+HTTP *
+[Asserts]
+header "Content-Type" contains "json"
+jsonpath "$.users[0].name" == "Ada"
+jsonpath "$.users" count == 2
+body contains "Ada"
+"#;
+
+    #[test]
+    fn test_imports_scenario() {
+        assert!(is_hurl(SCENARIO));
+        let imported = import_hurl(SCENARIO, "users").unwrap();
+        let requests = &imported.projects[0].requests["root"];
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].name.as_deref(), Some("Login"));
+        assert_eq!(requests[0].url, "{{HOST}}/login");
+        assert_eq!(requests[0].body.to_string(), "{\n  \"remember\": true\n}");
+        assert!(matches!(
+            requests[0].authorization,
+            FalconAuthorization::Basic { ref username, .. } if username == "ada"
+        ));
+        assert_eq!(
+            requests[0].extractions,
+            vec![Extraction::new(
+                ExtractionSource::JsonPath,
+                "$.token",
+                "TOKEN"
+            )]
+        );
+        assert_eq!(
+            requests[0].assertions,
+            vec![
+                Assertion::new(AssertionKind::StatusEquals, "", "200"),
+                Assertion::new(
+                    AssertionKind::HeaderMatches,
+                    "Content-Type",
+                    "^application/json$"
+                ),
+                Assertion::new(AssertionKind::JsonPathType, "$.token", "string"),
+                Assertion::new(AssertionKind::ResponseTimeBelow, "", "500"),
+            ]
+        );
+
+        assert_eq!(requests[1].queries[0], ("page".into(), "2".into()));
+        assert_eq!(requests[1].assertions.len(), 3);
+        assert_eq!(
+            requests[1].assertions[1],
+            Assertion::new(AssertionKind::JsonPathEquals, "$.users[0].name", "\"Ada\"")
+        );
+
+        let env = &imported.envs[0];
+        assert_eq!(env.variable("HOST"), Some(""));
+        assert_eq!(env.variable("TOKEN"), Some(""));
+        assert_eq!(imported.report.unsupported.len(), 2);
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let imported = import_hurl(SCENARIO, "users").unwrap();
+        let exported = export_hurl(&imported.projects[0]).unwrap();
+
+        assert!(exported.starts_with("# Login\nPOST {{host}}/login\nContent-Type: application/json\n[BasicAuth]\nada: secret\n{"));
+        assert!(exported.contains("HTTP 200\n[Captures]\ntoken: jsonpath \"$.token\"\n[Asserts]\nheader \"Content-Type\" matches \"^application/json$\""));
+
+        let again = import_hurl(&exported, "users").unwrap();
+        let (before, after) = (
+            &imported.projects[0].requests["root"],
+            &again.projects[0].requests["root"],
+        );
+        for (before, after) in before.iter().zip(after.iter()) {
+            assert_eq!(before.url, after.url);
+            assert_eq!(before.queries, after.queries);
+            assert_eq!(before.body.to_string(), after.body.to_string());
+            assert_eq!(before.assertions, after.assertions);
+            assert_eq!(before.extractions, after.extractions);
+        }
+        assert!(again.report.unsupported.is_empty());
+    }
+}
@@ -0,0 +1,956 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::utils::db::env::Env;
+use crate::utils::db::project::Project;
+use crate::utils::db::response_history::{ResponseHistory, ResponseRecord};
+use crate::utils::request::http_method::HttpMethod;
+use crate::utils::request::{FalconAuthorization, FlBody, PendingRequest, RequestUrl};
+
+use super::{variable_name, ImportReport, Imported};
+
+/// Nested schemas deeper than this are left empty in generated examples.
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Whether the document is an OpenAPI 3 or Swagger 2 specification.
+pub fn is_spec(spec: &Value) -> bool {
+    spec.get("openapi").is_some() || spec.get("swagger").is_some()
+}
+
+/// Converts a specification, the servers become envs with their base url.
+pub fn import_spec(spec: &Value) -> Result<Imported, String> {
+    let title = spec
+        .pointer("/info/title")
+        .and_then(Value::as_str)
+        .unwrap_or("OpenAPI")
+        .to_string();
+    let paths = spec
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or("The specification has no paths".to_string())?;
+
+    let mut report = ImportReport::default();
+    let mut requests: HashMap<String, Vec<PendingRequest>> = HashMap::new();
+    let authorization = security(spec, &mut report);
+
+    for (path, item) in paths {
+        let item = resolve(spec, item);
+        let shared = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+
+            let folder = operation
+                .pointer("/tags/0")
+                .and_then(Value::as_str)
+                .unwrap_or("root")
+                .to_string();
+            let request = to_request(
+                spec,
+                path,
+                method,
+                operation,
+                &shared,
+                &authorization,
+                &mut report,
+            );
+
+            requests.entry(folder).or_default().push(request);
+        }
+    }
+
+    let envs: Vec<Env> = servers(spec)
+        .into_iter()
+        .map(|(name, url)| Env {
+            id: Uuid::now_v7(),
+            name: format!("{} - {}", title, name),
+            base_url: Some(url),
+            ..Default::default()
+        })
+        .collect();
+
+    report.folders = requests.len();
+    report.requests = requests.values().map(Vec::len).sum();
+    report.envs = envs.len();
+
+    Ok(Imported {
+        projects: vec![Project {
+            name: title,
+            id: Uuid::now_v7(),
+            requests,
+            default_env: envs.first().map(|env| env.id),
+            ..Default::default()
+        }],
+        envs,
+        report,
+        merge: true,
+        ..Default::default()
+    })
+}
+
+/// Follows `$ref` pointers inside the document.
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+
+    // a few hops at most, guards against self referencing pointers
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        match value
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|pointer| pointer.strip_prefix('#'))
+            .and_then(|pointer| spec.pointer(pointer))
+        {
+            Some(target) => value = target,
+            None => break,
+        }
+    }
+
+    value
+}
+
+fn to_request(
+    spec: &Value,
+    path: &str,
+    method: &str,
+    operation: &Value,
+    shared: &[Value],
+    authorization: &Option<(FalconAuthorization, Vec<(String, String)>)>,
+    report: &mut ImportReport,
+) -> PendingRequest {
+    let name = operation
+        .get("summary")
+        .or(operation.get("operationId"))
+        .and_then(Value::as_str)
+        .map(String::from)
+        .unwrap_or(format!("{} {}", method.to_uppercase(), path));
+
+    let mut request = PendingRequest {
+        name: Some(name.clone()),
+        url: request_url(path),
+        method: HttpMethod::from(method),
+        headers: vec![],
+        cookies: vec![],
+        queries: vec![],
+        ..Default::default()
+    };
+
+    if let Some((auth, headers)) = authorization {
+        request.authorization = auth.clone();
+        request.headers.extend(headers.iter().cloned());
+    }
+
+    // operation parameters override the ones shared by the path
+    let mut parameters: Vec<&Value> = vec![];
+    let operation_parameters = operation.get("parameters").and_then(Value::as_array);
+    for parameter in shared
+        .iter()
+        .chain(operation_parameters.into_iter().flatten())
+    {
+        let parameter = resolve(spec, parameter);
+        let key = (parameter.get("name"), parameter.get("in"));
+
+        parameters.retain(|existing| (existing.get("name"), existing.get("in")) != key);
+        parameters.push(parameter);
+    }
+
+    for parameter in parameters {
+        let name = parameter
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let location = parameter
+            .get("in")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let required = parameter
+            .get("required")
+            .and_then(Value::as_bool)
+            .unwrap_or_default();
+        let example = parameter_example(spec, parameter);
+
+        match location {
+            "query" if required || example.is_some() => {
+                request.queries.push((name, example.unwrap_or_default()))
+            }
+            "header" if !name.eq_ignore_ascii_case("content-type") => {
+                request.headers.push((name, example.unwrap_or_default()))
+            }
+            "cookie" => request.cookies.push((name, example.unwrap_or_default())),
+            // Swagger 2 request body
+            "body" => {
+                if let Some(schema) = parameter.get("schema") {
+                    request.body =
+                        FlBody::ApplicationJson(pretty(&schema_example(spec, schema, 0)));
+                }
+            }
+            "formData" => report.unsupported(format!(
+                "Form parameter {} of {}",
+                name,
+                request_label(&request)
+            )),
+            _ => {}
+        }
+    }
+
+    if let Some(body) = operation.get("requestBody") {
+        let body = resolve(spec, body);
+        let content = body.get("content").and_then(Value::as_object);
+        let json = content.and_then(|content| {
+            content
+                .iter()
+                .find(|(media, _)| media.contains("json"))
+                .map(|(_, value)| value)
+        });
+
+        match (json, content) {
+            (Some(media), _) => {
+                let example = media
+                    .get("example")
+                    .cloned()
+                    .or(media
+                        .get("examples")
+                        .and_then(Value::as_object)
+                        .and_then(|examples| examples.values().next())
+                        .map(|example| resolve(spec, example))
+                        .and_then(|example| example.get("value").cloned()))
+                    .or(media
+                        .get("schema")
+                        .map(|schema| schema_example(spec, schema, 0)));
+
+                if let Some(example) = example {
+                    request.body = FlBody::ApplicationJson(pretty(&example));
+                }
+            }
+            (None, Some(content)) if !content.is_empty() => {
+                let media: Vec<&str> = content.keys().map(String::as_str).collect();
+                report.unsupported(format!(
+                    "Body {} of {}",
+                    media.join(", "),
+                    request_label(&request)
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    // keep an empty row for editing, same as new requests
+    for items in [
+        &mut request.headers,
+        &mut request.cookies,
+        &mut request.queries,
+    ] {
+        items.push(("".to_string(), "".to_string()));
+    }
+
+    request
+}
+
+/// `/users/{id}` becomes `{{%PROJECT_BASE_URL%}}/users/{{ID}}`.
+fn request_url(path: &str) -> String {
+    let mut url = String::from(RequestUrl::BASE_URL_PLACEHOLDER);
+    let mut rest = path;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+
+        url.push_str(&rest[..start]);
+        url.push_str(&format!(
+            "{{{{{}}}}}",
+            variable_name(&rest[start + 1..start + end])
+        ));
+        rest = &rest[start + end + 1..];
+    }
+    url.push_str(rest);
+
+    url
+}
+
+fn request_label(request: &PendingRequest) -> String {
+    request.name.clone().unwrap_or_default()
+}
+
+fn parameter_example(spec: &Value, parameter: &Value) -> Option<String> {
+    let schema = parameter.get("schema").map(|schema| resolve(spec, schema));
+    let value = parameter
+        .get("example")
+        .or(parameter.get("default"))
+        .or(parameter.pointer("/enum/0"))
+        .or(schema.and_then(|schema| {
+            schema
+                .get("example")
+                .or(schema.get("default"))
+                .or(schema.pointer("/enum/0"))
+        }))?;
+
+    Some(match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    })
+}
+
+/// An example value built from the schema, preferring the examples it declares.
+fn schema_example(spec: &Value, schema: &Value, depth: usize) -> Value {
+    let schema = resolve(spec, schema);
+
+    if depth > MAX_SCHEMA_DEPTH {
+        return Value::Null;
+    }
+    if let Some(example) = schema.get("example").or(schema.get("default")) {
+        return example.clone();
+    }
+    if let Some(value) = schema.pointer("/enum/0") {
+        return value.clone();
+    }
+
+    if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+        let mut merged = Map::new();
+        for part in all {
+            if let Value::Object(fields) = schema_example(spec, part, depth + 1) {
+                merged.extend(fields);
+            }
+        }
+        return Value::Object(merged);
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema.get(key).and_then(|options| options.get(0)) {
+            return schema_example(spec, first, depth + 1);
+        }
+    }
+
+    let kind = schema.get("type").and_then(Value::as_str).unwrap_or(
+        match schema.get("properties").is_some() {
+            true => "object",
+            false => "",
+        },
+    );
+
+    match kind {
+        "object" => Value::Object(
+            schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .map(|properties| {
+                    properties
+                        .iter()
+                        .map(|(name, property)| {
+                            (name.clone(), schema_example(spec, property, depth + 1))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        ),
+        "array" => Value::Array(match schema.get("items") {
+            Some(items) => vec![schema_example(spec, items, depth + 1)],
+            None => vec![],
+        }),
+        "integer" => Value::from(0),
+        "number" => Value::from(0.0),
+        "boolean" => Value::Bool(true),
+        "string" => Value::String(
+            match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => "2024-01-01T00:00:00Z",
+                Some("date") => "2024-01-01",
+                Some("email") => "user@example.com",
+                Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                _ => "string",
+            }
+            .to_string(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// Name and url of every server, server variables take their default value.
+fn servers(spec: &Value) -> Vec<(String, String)> {
+    // Swagger 2 has a single host
+    if let Some(host) = spec.get("host").and_then(Value::as_str) {
+        let scheme = spec
+            .pointer("/schemes/0")
+            .and_then(Value::as_str)
+            .unwrap_or("https");
+        let base_path = spec.get("basePath").and_then(Value::as_str).unwrap_or("");
+        let url = format!("{}://{}{}", scheme, host, base_path);
+
+        return vec![(url.clone(), url.trim_end_matches('/').to_string())];
+    }
+
+    spec.get("servers")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|server| {
+            let mut url = server.get("url").and_then(Value::as_str)?.to_string();
+
+            if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+                for (name, variable) in variables {
+                    let default = variable
+                        .get("default")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    url = url.replace(&format!("{{{}}}", name), default);
+                }
+            }
+
+            let name = server
+                .get("description")
+                .and_then(Value::as_str)
+                .map(String::from)
+                .unwrap_or(url.clone());
+
+            Some((name, url.trim_end_matches('/').to_string()))
+        })
+        .collect()
+}
+
+/// The authorization of the first global security requirement, with the api key headers.
+fn security(
+    spec: &Value,
+    report: &mut ImportReport,
+) -> Option<(FalconAuthorization, Vec<(String, String)>)> {
+    let requirement = spec.pointer("/security/0").and_then(Value::as_object)?;
+    let name = requirement.keys().next()?;
+    let scheme = spec
+        .pointer(&format!("/components/securitySchemes/{}", name))
+        .or(spec.pointer(&format!("/securityDefinitions/{}", name)))?;
+
+    let kind = scheme
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let http_scheme = scheme
+        .get("scheme")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match (kind, http_scheme.as_str()) {
+        ("http", "bearer") => Some((
+            FalconAuthorization::Bearer {
+                prefix: "Bearer".into(),
+                token: "{{TOKEN}}".into(),
+            },
+            vec![],
+        )),
+        ("http", "basic") | ("basic", _) => Some((
+            FalconAuthorization::Basic {
+                username: "{{USERNAME}}".into(),
+                password: "{{PASSWORD}}".into(),
+            },
+            vec![],
+        )),
+        ("apiKey", _) if scheme.get("in").and_then(Value::as_str) == Some("header") => {
+            let header = scheme
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+
+            Some((
+                FalconAuthorization::default(),
+                vec![(
+                    header.to_string(),
+                    format!("{{{{{}}}}}", variable_name(header)),
+                )],
+            ))
+        }
+        _ => {
+            report.unsupported(format!("Security scheme {}", name));
+            None
+        }
+    }
+}
+
+/// Updates a project from a newer import of the same specification.
+///
+/// Requests are matched by method and url. Matched requests keep their assertions,
+/// extractions, scripts and values, new parameters are added to them. Requests no
+/// longer in the specification are kept and listed in the report.
+pub fn merge(existing: &mut Project, imported: Project, report: &mut ImportReport) {
+    let mut added = 0;
+    let mut updated = 0;
+    let mut seen: Vec<Uuid> = vec![];
+
+    for (folder, requests) in imported.requests {
+        for request in requests {
+            let found = existing
+                .requests
+                .values_mut()
+                .flat_map(|requests| requests.iter_mut())
+                .find(|current| current.method == request.method && current.url == request.url);
+
+            match found {
+                Some(current) => {
+                    current.name = request.name;
+                    merge_items(&mut current.queries, request.queries);
+                    merge_items(&mut current.headers, request.headers);
+                    merge_items(&mut current.cookies, request.cookies);
+                    if current.body.to_string().trim().is_empty() {
+                        current.body = request.body;
+                    }
+                    seen.push(current.id);
+                    updated += 1;
+                }
+                None => {
+                    seen.push(request.id);
+                    existing
+                        .requests
+                        .entry(folder.clone())
+                        .or_default()
+                        .push(request);
+                    added += 1;
+                }
+            }
+        }
+    }
+
+    for (_, request) in existing.ordered_requests(None) {
+        if !seen.contains(&request.id) {
+            report.unsupported(format!(
+                "{} {} is no longer in the specification, kept as is",
+                request.method.0, request.url
+            ));
+        }
+    }
+
+    report.requests = added + updated;
+    report.folders = existing.requests.len();
+}
+
+/// Adds the parameters the request doesn't have yet, before the empty editing row.
+fn merge_items(current: &mut Vec<(String, String)>, imported: Vec<(String, String)>) {
+    for (key, value) in imported.into_iter().filter(|(key, _)| !key.is_empty()) {
+        if current.iter().any(|(name, _)| name == &key) {
+            continue;
+        }
+
+        let position = current
+            .iter()
+            .position(|(name, value)| name.is_empty() && value.is_empty())
+            .unwrap_or(current.len());
+        current.insert(position, (key, value));
+    }
+}
+
+/// Exports a project as an OpenAPI 3.1 skeleton. Paths, methods and parameters come
+/// from the requests, response schemas are inferred from the responses saved in the history.
+pub fn export_spec(
+    project: &Project,
+    env: Option<&Env>,
+    history: &ResponseHistory,
+) -> Result<String, String> {
+    let mut paths = Map::new();
+    let mut security_schemes = Map::new();
+
+    for (folder, request) in project.ordered_requests(None) {
+        let (path, path_params) = spec_path(&request.url);
+        let method = request.method.0.to_string().to_lowercase();
+
+        let item = paths
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        // two requests on the same operation, the first one describes it
+        if item.get(&method).is_some() {
+            continue;
+        }
+
+        let mut operation = Map::new();
+        if let Some(name) = request.name.as_ref() {
+            operation.insert("summary".into(), json!(name));
+        }
+        if folder != "root" {
+            operation.insert("tags".into(), json!([folder]));
+        }
+
+        let mut parameters: Vec<Value> = path_params
+            .iter()
+            .map(|name| {
+                json!({"name": name, "in": "path", "required": true, "schema": {"type": "string"}})
+            })
+            .collect();
+        for (location, items) in [
+            ("query", &request.queries),
+            ("header", &request.headers),
+            ("cookie", &request.cookies),
+        ] {
+            for (name, value) in items.iter().filter(|(name, _)| !name.trim().is_empty()) {
+                let example = Value::String(value.clone());
+                parameters.push(json!({
+                    "name": name,
+                    "in": location,
+                    "schema": schema_of(&example),
+                    "example": value,
+                }));
+            }
+        }
+        if !parameters.is_empty() {
+            operation.insert("parameters".into(), Value::Array(parameters));
+        }
+
+        let body = request.body.to_string();
+        if !body.trim().is_empty() {
+            operation.insert("requestBody".into(), json!({"content": media(&body)}));
+        }
+
+        let security = match &request.authorization {
+            FalconAuthorization::Bearer { token, .. } if !token.trim().is_empty() => {
+                Some(("bearerAuth", json!({"type": "http", "scheme": "bearer"})))
+            }
+            FalconAuthorization::Basic { username, .. } if !username.trim().is_empty() => {
+                Some(("basicAuth", json!({"type": "http", "scheme": "basic"})))
+            }
+            _ => None,
+        };
+        if let Some((name, scheme)) = security {
+            security_schemes.insert(name.into(), scheme);
+            operation.insert("security".into(), json!([{ name: [] }]));
+        }
+
+        operation.insert("responses".into(), responses(history.entries(&request.id)));
+        item[&method] = Value::Object(operation);
+    }
+
+    let mut spec = json!({
+        "openapi": "3.1.0",
+        "info": {"title": project.name, "version": "1.0.0"},
+        "paths": paths,
+    });
+    if let Some(base_url) = env.and_then(|env| env.base_url.as_ref()) {
+        spec["servers"] = json!([{ "url": base_url, "description": env.map(|env| &env.name) }]);
+    }
+    if !security_schemes.is_empty() {
+        spec["components"] = json!({ "securitySchemes": security_schemes });
+    }
+
+    serde_json::to_string_pretty(&spec)
+        .map_err(|err| format!("OpenAPI export failed, cause {}", err))
+}
+
+/// The path of the request url with `{{VARS}}` as path parameters.
+fn spec_path(url: &str) -> (String, Vec<String>) {
+    static RE: OnceLock<Regex> = OnceLock::new();
+
+    let path = match url.strip_prefix(RequestUrl::BASE_URL_PLACEHOLDER) {
+        Some(path) => path.to_string(),
+        None => url::Url::parse(url)
+            .map(|url| url.path().to_string())
+            .unwrap_or(url.to_string()),
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    let re = RE.get_or_init(|| Regex::new(r"\{\{([A-Z0-9_]+)\}\}").unwrap());
+    let params = re
+        .captures_iter(path)
+        .map(|cap| cap[1].to_string())
+        .collect();
+    let path = re.replace_all(path, "{$1}").to_string();
+
+    match path.starts_with('/') {
+        true => (path, params),
+        false => (format!("/{}", path), params),
+    }
+}
+
+/// JSON bodies get a schema and an example, anything else is plain text.
+fn media(body: &str) -> Value {
+    match serde_json::from_str::<Value>(body) {
+        Ok(value) => json!({"application/json": {"schema": schema_of(&value), "example": value}}),
+        Err(_) => json!({"text/plain": {"schema": {"type": "string"}, "example": body}}),
+    }
+}
+
+/// One response per status, described by the newest saved response.
+fn responses(records: &[ResponseRecord]) -> Value {
+    let mut responses = Map::new();
+
+    for record in records {
+        let status = record.status_code.to_string();
+        if responses.contains_key(&status) {
+            continue;
+        }
+
+        let description = reqwest::StatusCode::from_u16(record.status_code)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or("Response");
+        let mut response = json!({ "description": description });
+        if !record.body.trim().is_empty() {
+            response["content"] = media(&record.body);
+        }
+        responses.insert(status, response);
+    }
+
+    if responses.is_empty() {
+        responses.insert(
+            "default".into(),
+            json!({"description": "No saved response"}),
+        );
+    }
+
+    Value::Object(responses)
+}
+
+/// A schema describing the value, arrays are described by their first item.
+fn schema_of(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({"type": "null"}),
+        Value::Bool(_) => json!({"type": "boolean"}),
+        Value::Number(number) if number.is_i64() || number.is_u64() => json!({"type": "integer"}),
+        Value::Number(_) => json!({"type": "number"}),
+        Value::String(_) => json!({"type": "string"}),
+        Value::Array(items) => match items.first() {
+            Some(item) => json!({"type": "array", "items": schema_of(item)}),
+            None => json!({"type": "array"}),
+        },
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, value)| (name.clone(), schema_of(value)))
+                .collect();
+            json!({"type": "object", "properties": properties})
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPENAPI: &str = r#"
+openapi: 3.0.3
+info:
+  title: Pets
+servers:
+  - url: https://{region}.pets.test/v1
+    description: Production
+    variables:
+      region:
+        default: eu
+components:
+  securitySchemes:
+    token:
+      type: http
+      scheme: bearer
+  schemas:
+    Pet:
+      type: object
+      properties:
+        id: {type: integer}
+        name: {type: string, example: Rex}
+        tags:
+          type: array
+          items: {type: string}
+security:
+  - token: []
+paths:
+  /pets/{petId}:
+    parameters:
+      - {name: petId, in: path, required: true, schema: {type: integer}}
+    get:
+      tags: [pets]
+      summary: Get a pet
+      parameters:
+        - {name: verbose, in: query, schema: {type: boolean, default: false}}
+        - {name: limit, in: query, schema: {type: integer}}
+        - {name: X-Trace, in: header, example: abc}
+    put:
+      tags: [pets]
+      operationId: updatePet
+      requestBody:
+        content:
+          application/json:
+            schema: {$ref: '#/components/schemas/Pet'}
+  /health:
+    get:
+      summary: Health
+"#;
+
+    fn import(spec: &str) -> Imported {
+        import_spec(&serde_yaml::from_str(spec).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_imports_openapi() {
+        let imported = import(OPENAPI);
+        let project = &imported.projects[0];
+
+        assert_eq!(project.name, "Pets");
+        assert_eq!(imported.report.requests, 3);
+        assert_eq!(imported.envs[0].name, "Pets - Production");
+        assert_eq!(
+            imported.envs[0].base_url.as_deref(),
+            Some("https://eu.pets.test/v1")
+        );
+
+        let get = &project.requests["pets"][0];
+        assert_eq!(get.name.as_deref(), Some("Get a pet"));
+        assert_eq!(get.url, "{{%PROJECT_BASE_URL%}}/pets/{{PET_ID}}");
+        assert_eq!(
+            get.queries,
+            vec![("verbose".into(), "false".into()), ("".into(), "".into())]
+        );
+        assert_eq!(get.headers[0], ("X-Trace".into(), "abc".into()));
+        assert!(
+            matches!(get.authorization, FalconAuthorization::Bearer { ref token, .. } if token == "{{TOKEN}}")
+        );
+
+        let put = &project.requests["pets"][1];
+        assert_eq!(put.name.as_deref(), Some("updatePet"));
+        let body: Value = serde_json::from_str(&put.body.to_string()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"id": 0, "name": "Rex", "tags": ["string"]})
+        );
+
+        assert_eq!(project.requests["root"][0].name.as_deref(), Some("Health"));
+    }
+
+    #[test]
+    fn test_imports_swagger() {
+        let imported = import(
+            r#"
+swagger: "2.0"
+info: {title: Legacy}
+host: api.legacy.test
+basePath: /v2
+schemes: [http]
+paths:
+  /orders:
+    post:
+      parameters:
+        - in: body
+          name: order
+          schema:
+            type: object
+            properties:
+              quantity: {type: integer, example: 3}
+"#,
+        );
+
+        assert_eq!(
+            imported.envs[0].base_url.as_deref(),
+            Some("http://api.legacy.test/v2")
+        );
+        let post = &imported.projects[0].requests["root"][0];
+        assert_eq!(post.name.as_deref(), Some("POST /orders"));
+        assert_eq!(post.body.to_string(), "{\n  \"quantity\": 3\n}");
+    }
+
+    #[test]
+    fn test_merges_reimport() {
+        let mut existing = import(OPENAPI).projects.remove(0);
+        let get_id = existing.requests["pets"][0].id;
+        existing.requests.get_mut("pets").unwrap()[0].queries[0].1 = "true".into();
+
+        let updated = OPENAPI
+            .replace("summary: Health", "summary: Health check")
+            .replace(
+                "        - {name: X-Trace, in: header, example: abc}",
+                "        - {name: X-Trace, in: header, example: abc}\n        - {name: page, in: query, required: true}",
+            )
+            .replace("  /health:", "  /status:");
+        let imported = import(&updated).projects.remove(0);
+
+        let mut report = ImportReport::default();
+        merge(&mut existing, imported, &mut report);
+
+        let get = &existing.requests["pets"][0];
+        assert_eq!(get.id, get_id);
+        assert_eq!(
+            get.queries,
+            vec![
+                ("verbose".into(), "true".into()),
+                ("page".into(), "".into()),
+                ("".into(), "".into())
+            ]
+        );
+        assert_eq!(existing.requests["root"].len(), 2);
+        assert_eq!(
+            report.unsupported,
+            vec!["GET {{%PROJECT_BASE_URL%}}/health is no longer in the specification, kept as is"]
+        );
+    }
+
+    #[test]
+    fn test_exports_spec() {
+        let project = import(OPENAPI).projects.remove(0);
+        let get = &project.requests["pets"][0];
+        let env = Env {
+            name: "Production".into(),
+            base_url: Some("https://eu.pets.test/v1".into()),
+            ..Default::default()
+        };
+
+        let mut history = ResponseHistory::default();
+        history.record(
+            get.id,
+            ResponseRecord {
+                id: Uuid::now_v7(),
+                timestamp: chrono::Utc::now(),
+                status_code: 200,
+                headers: vec![],
+                body: r#"{"id": 7, "name": "Rex", "tags": ["good"], "weight": 4.5}"#.into(),
+                duration_ms: 10,
+                size_kb: 1.0,
+                env_name: None,
+            },
+        );
+
+        let exported = export_spec(&project, Some(&env), &history).unwrap();
+        let spec: Value = serde_json::from_str(&exported).unwrap();
+
+        assert_eq!(spec["openapi"], "3.1.0");
+        assert_eq!(spec["servers"][0]["url"], "https://eu.pets.test/v1");
+
+        let operation = &spec["paths"]["/pets/{PET_ID}"]["get"];
+        assert_eq!(operation["summary"], "Get a pet");
+        assert_eq!(operation["tags"], json!(["pets"]));
+        assert_eq!(operation["parameters"][0]["in"], "path");
+        assert_eq!(operation["security"], json!([{"bearerAuth": []}]));
+        assert_eq!(
+            operation["responses"]["200"]["content"]["application/json"]["schema"],
+            json!({"type": "object", "properties": {
+                "id": {"type": "integer"},
+                "name": {"type": "string"},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "weight": {"type": "number"},
+            }})
+        );
+        assert_eq!(
+            spec["paths"]["/health"]["get"]["responses"]["default"]["description"],
+            "No saved response"
+        );
+
+        // the skeleton imports back into the same operations
+        let imported = import_spec(&spec).unwrap();
+        let project = &imported.projects[0];
+        assert_eq!(imported.report.requests, 3);
+        assert_eq!(
+            project.requests["pets"][0].url,
+            "{{%PROJECT_BASE_URL%}}/pets/{{PET_ID}}"
+        );
+        assert_eq!(
+            project.requests["pets"][1].body.to_string(),
+            pretty(&json!({"id": 0, "name": "Rex", "tags": ["string"]}))
+        );
+    }
+}
@@ -0,0 +1,846 @@
+use std::collections::HashMap;
+
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::utils::db::env::Env;
+use crate::utils::db::project::Project;
+use crate::utils::request::http_method::HttpMethod;
+use crate::utils::request::{FalconAuthorization, FlBody, PendingRequest, RequestUrl};
+
+use super::{convert_placeholders, variable_name, ImportReport};
+
+/// Nested Postman folders are flattened, their names joined with this.
+pub const FOLDER_SEPARATOR: &str = " > ";
+
+#[derive(Debug, Deserialize)]
+struct Collection {
+    info: Info,
+    #[serde(default)]
+    item: Vec<Item>,
+    auth: Option<Auth>,
+    #[serde(default)]
+    event: Vec<Value>,
+    #[serde(default)]
+    variable: Vec<KeyValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Info {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Item {
+    #[serde(default)]
+    name: String,
+    item: Option<Vec<Item>>,
+    request: Option<RequestDef>,
+    auth: Option<Auth>,
+    #[serde(default)]
+    event: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RequestDef {
+    Url(String),
+    Full(Box<Request>),
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    method: Option<String>,
+    url: Option<Url>,
+    #[serde(default)]
+    header: Vec<KeyValue>,
+    body: Option<Body>,
+    auth: Option<Auth>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Url {
+    Raw(String),
+    Parts(UrlParts),
+}
+
+#[derive(Debug, Deserialize)]
+struct UrlParts {
+    #[serde(default)]
+    raw: String,
+    #[serde(default)]
+    query: Vec<KeyValue>,
+    #[serde(default)]
+    variable: Vec<KeyValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyValue {
+    #[serde(default)]
+    key: String,
+    #[serde(default)]
+    value: Value,
+    #[serde(default)]
+    disabled: bool,
+    /// Environment files use `enabled` instead of `disabled`.
+    enabled: Option<bool>,
+}
+
+impl KeyValue {
+    fn is_enabled(&self) -> bool {
+        !self.disabled && self.enabled.unwrap_or(true)
+    }
+
+    fn value(&self) -> String {
+        match &self.value {
+            Value::Null => String::new(),
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Body {
+    #[serde(default)]
+    mode: String,
+    #[serde(default)]
+    raw: String,
+    graphql: Option<Value>,
+    /// Settings of the body, the language of a raw body sets its content type.
+    options: Option<Value>,
+}
+
+impl Body {
+    /// The content type Postman sends a raw body with when the request sets none.
+    fn raw_content_type(&self) -> Option<&'static str> {
+        let language = self
+            .options
+            .as_ref()
+            .and_then(|options| options.pointer("/raw/language"))
+            .and_then(Value::as_str);
+
+        match language {
+            Some("text") => Some("text/plain"),
+            Some("xml") => Some("application/xml"),
+            Some("html") => Some("text/html"),
+            Some("javascript") => Some("application/javascript"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Auth {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(flatten)]
+    params: HashMap<String, Value>,
+}
+
+impl Auth {
+    /// A parameter of the auth, v2.1 stores them as a key/value list and v2.0 as an object.
+    fn param(&self, name: &str) -> String {
+        let value = match self.params.get(&self.kind) {
+            Some(Value::Array(items)) => items
+                .iter()
+                .find(|item| item.get("key").and_then(Value::as_str) == Some(name))
+                .and_then(|item| item.get("value")),
+            Some(Value::Object(fields)) => fields.get(name),
+            _ => None,
+        };
+
+        match value {
+            Some(Value::String(value)) => convert_placeholders(value),
+            Some(Value::Null) | None => String::new(),
+            Some(value) => value.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Environment {
+    name: String,
+    #[serde(default)]
+    values: Vec<KeyValue>,
+}
+
+/// A collection converted into a project, collection variables become an env.
+#[derive(Debug, Clone)]
+pub struct PostmanImport {
+    pub project: Project,
+    pub env: Option<Env>,
+    pub report: ImportReport,
+}
+
+/// Converts a Postman v2.1 collection export.
+pub fn import_collection(json: &str) -> Result<PostmanImport, String> {
+    let collection: Collection =
+        serde_json::from_str(json).map_err(|err| format!("Not a Postman collection, {}", err))?;
+
+    let mut report = ImportReport::default();
+    let mut requests: HashMap<String, Vec<PendingRequest>> = HashMap::new();
+
+    if !collection.event.is_empty() {
+        report.unsupported(format!(
+            "Scripts of the collection {}",
+            collection.info.name
+        ));
+    }
+
+    collect_items(
+        &collection.item,
+        None,
+        collection.auth.as_ref(),
+        &mut requests,
+        &mut report,
+    );
+
+    report.folders = requests.len();
+    report.requests = requests.values().map(Vec::len).sum();
+
+    let env = match collection.variable.is_empty() {
+        true => None,
+        false => {
+            report.envs = 1;
+            Some(to_env(
+                format!("{} variables", collection.info.name),
+                &collection.variable,
+                &mut report,
+            ))
+        }
+    };
+
+    Ok(PostmanImport {
+        project: Project {
+            name: collection.info.name,
+            id: Uuid::now_v7(),
+            requests,
+            ..Default::default()
+        },
+        env,
+        report,
+    })
+}
+
+/// Converts a Postman environment export.
+pub fn import_environment(json: &str) -> Result<(Env, ImportReport), String> {
+    let environment: Environment =
+        serde_json::from_str(json).map_err(|err| format!("Not a Postman environment, {}", err))?;
+
+    let mut report = ImportReport {
+        envs: 1,
+        ..Default::default()
+    };
+    let env = to_env(environment.name, &environment.values, &mut report);
+
+    Ok((env, report))
+}
+
+fn to_env(name: String, values: &[KeyValue], report: &mut ImportReport) -> Env {
+    let mut items: Vec<(String, String)> = vec![];
+
+    for value in values.iter().filter(|value| !value.key.trim().is_empty()) {
+        if !value.is_enabled() {
+            report.unsupported(format!("Disabled variable {} of {}", value.key, name));
+            continue;
+        }
+        items.push((
+            variable_name(&value.key),
+            convert_placeholders(&value.value()),
+        ));
+    }
+    items.push(("".into(), "".into()));
+
+    Env {
+        id: Uuid::now_v7(),
+        name,
+        items,
+        is_active: false,
+        base_url: None,
+    }
+}
+
+fn collect_items(
+    items: &[Item],
+    folder: Option<&str>,
+    parent_auth: Option<&Auth>,
+    requests: &mut HashMap<String, Vec<PendingRequest>>,
+    report: &mut ImportReport,
+) {
+    for item in items {
+        let auth = item.auth.as_ref().or(parent_auth);
+
+        if !item.event.is_empty() {
+            report.unsupported(format!("Scripts of {}", item.name));
+        }
+
+        if let Some(children) = &item.item {
+            let name = match folder {
+                Some(folder) => format!("{}{}{}", folder, FOLDER_SEPARATOR, item.name),
+                None => item.name.clone(),
+            };
+            collect_items(children, Some(&name), auth, requests, report);
+            continue;
+        }
+
+        if let Some(request) = &item.request {
+            requests
+                .entry(folder.unwrap_or("root").to_string())
+                .or_default()
+                .push(to_request(&item.name, request, auth, report));
+        }
+    }
+}
+
+fn to_request(
+    name: &str,
+    request: &RequestDef,
+    parent_auth: Option<&Auth>,
+    report: &mut ImportReport,
+) -> PendingRequest {
+    let request = match request {
+        RequestDef::Url(url) => {
+            return PendingRequest {
+                name: Some(name.to_string()),
+                url: convert_placeholders(url),
+                ..Default::default()
+            }
+        }
+        RequestDef::Full(request) => request,
+    };
+
+    let method = request.method.clone().unwrap_or("GET".into());
+    let mut pending = PendingRequest {
+        name: Some(name.to_string()),
+        method: HttpMethod(
+            Method::from_bytes(method.to_uppercase().as_bytes()).unwrap_or_default(),
+        ),
+        headers: vec![],
+        cookies: vec![],
+        queries: vec![],
+        ..Default::default()
+    };
+
+    let (url, queries) = match &request.url {
+        Some(Url::Raw(raw)) => split_query(raw),
+        Some(Url::Parts(parts)) => {
+            let (mut url, _) = split_query(&parts.raw);
+
+            // path variables `:id` take their value, or become a placeholder
+            for variable in parts.variable.iter() {
+                let value = match variable.value() {
+                    value if value.is_empty() => format!("{{{{{}}}}}", variable.key),
+                    value => value,
+                };
+                url = url.replace(&format!(":{}", variable.key), &value);
+            }
+
+            let queries = parts
+                .query
+                .iter()
+                .filter(|query| query.is_enabled())
+                .map(|query| (query.key.clone(), query.value()))
+                .collect();
+            (url, queries)
+        }
+        None => (String::new(), vec![]),
+    };
+
+    pending.url = convert_placeholders(&url);
+    pending.queries = queries
+        .into_iter()
+        .map(|(key, value)| (convert_placeholders(&key), convert_placeholders(&value)))
+        .collect();
+    pending.headers = request
+        .header
+        .iter()
+        .filter(|header| header.is_enabled() && !header.key.trim().is_empty())
+        .map(|header| {
+            (
+                convert_placeholders(&header.key),
+                convert_placeholders(&header.value()),
+            )
+        })
+        .collect();
+
+    if let Some(body) = &request.body {
+        match body.mode.as_str() {
+            "raw" => {
+                pending.body = FlBody::ApplicationJson(convert_placeholders(&body.raw));
+                // sending sets JSON when the request has no content type
+                if let Some(content_type) = body.raw_content_type() {
+                    if !pending.has_header("Content-Type") {
+                        pending.add_header("Content-Type", content_type);
+                    }
+                }
+            }
+            "graphql" => {
+                let graphql = body.graphql.clone().unwrap_or_default();
+                let variables = match graphql.get("variables").and_then(Value::as_str) {
+                    Some(variables) => serde_json::from_str(variables).unwrap_or(Value::Null),
+                    None => Value::Null,
+                };
+                let query = serde_json::json!({
+                    "query": graphql.get("query").cloned().unwrap_or_default(),
+                    "variables": variables,
+                });
+
+                pending.body = FlBody::ApplicationJson(convert_placeholders(
+                    &serde_json::to_string_pretty(&query).unwrap_or_default(),
+                ));
+            }
+            "" => {}
+            mode => report.unsupported(format!("Body mode {} of {}", mode, name)),
+        }
+    }
+
+    if let Some(auth) = request.auth.as_ref().or(parent_auth) {
+        match auth.kind.as_str() {
+            "bearer" => {
+                pending.authorization = FalconAuthorization::Bearer {
+                    prefix: "Bearer".into(),
+                    token: auth.param("token"),
+                }
+            }
+            "basic" => {
+                pending.authorization = FalconAuthorization::Basic {
+                    username: auth.param("username"),
+                    password: auth.param("password"),
+                }
+            }
+            "noauth" => {}
+            kind => report.unsupported(format!("Authorization type {} of {}", kind, name)),
+        }
+    }
+
+    // keep an empty row for editing, same as new requests
+    for items in [
+        &mut pending.headers,
+        &mut pending.cookies,
+        &mut pending.queries,
+    ] {
+        items.push(("".to_string(), "".to_string()));
+    }
+
+    pending
+}
+
+/// The url without its query string, and the query parameters.
+fn split_query(raw: &str) -> (String, Vec<(String, String)>) {
+    match raw.split_once('?') {
+        Some((url, query)) => (
+            url.to_string(),
+            query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| match pair.split_once('=') {
+                    Some((key, value)) => (key.to_string(), value.to_string()),
+                    None => (pair.to_string(), String::new()),
+                })
+                .collect(),
+        ),
+        None => (raw.to_string(), vec![]),
+    }
+}
+
+/// Schema of the exported collections.
+const COLLECTION_SCHEMA: &str =
+    "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// Postman variable holding the base url of the env.
+const BASE_URL_VARIABLE: &str = "baseUrl";
+
+/// Exports a project as a Postman v2.1 collection, folders joined with
+/// [`FOLDER_SEPARATOR`] become nested folders again. The variables of the env
+/// become collection variables.
+pub fn export_collection(project: &Project, env: Option<&Env>) -> Result<String, String> {
+    let mut items: Vec<Value> = vec![];
+
+    for folder in project.folders() {
+        let requests: Vec<Value> = project.requests[folder]
+            .iter()
+            .map(export_request)
+            .collect();
+
+        match folder.as_str() {
+            "root" => items.extend(requests),
+            folder => {
+                let path: Vec<&str> = folder.split(FOLDER_SEPARATOR).collect();
+                insert_folder(&mut items, &path, requests);
+            }
+        }
+    }
+
+    let mut variables: Vec<Value> = vec![];
+    if let Some(env) = env {
+        if let Some(base_url) = env.base_url.as_ref() {
+            variables.push(json!({"key": BASE_URL_VARIABLE, "value": base_url}));
+        }
+        for (key, value) in env.items.iter().filter(|(key, _)| !key.trim().is_empty()) {
+            variables.push(json!({"key": key, "value": value}));
+        }
+    }
+
+    let collection = json!({
+        "info": {
+            "_postman_id": project.id,
+            "name": project.name,
+            "schema": COLLECTION_SCHEMA,
+        },
+        "item": items,
+        "variable": variables,
+    });
+
+    serde_json::to_string_pretty(&collection)
+        .map_err(|err| format!("Postman export failed, cause {}", err))
+}
+
+/// Adds the requests in the nested folder, creating the folders on the way.
+fn insert_folder(items: &mut Vec<Value>, path: &[&str], requests: Vec<Value>) {
+    let Some((name, rest)) = path.split_first() else {
+        items.extend(requests);
+        return;
+    };
+
+    let position = items.iter().position(|item| {
+        item.get("item").is_some() && item.get("name").and_then(Value::as_str) == Some(name)
+    });
+    let index = match position {
+        Some(index) => index,
+        None => {
+            items.push(json!({"name": name, "item": []}));
+            items.len() - 1
+        }
+    };
+
+    if let Some(Value::Array(children)) = items[index].get_mut("item") {
+        insert_folder(children, rest, requests);
+    }
+}
+
+fn export_request(request: &PendingRequest) -> Value {
+    let url = request.url.replacen(
+        RequestUrl::BASE_URL_PLACEHOLDER,
+        &format!("{{{{{}}}}}", BASE_URL_VARIABLE),
+        1,
+    );
+    let queries: Vec<&(String, String)> = request
+        .queries
+        .iter()
+        .filter(|(key, _)| !key.trim().is_empty())
+        .collect();
+    let raw = match queries.is_empty() {
+        true => url,
+        false => {
+            let pairs: Vec<String> = queries
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            format!("{}?{}", url, pairs.join("&"))
+        }
+    };
+
+    let mut headers: Vec<Value> = request
+        .headers
+        .iter()
+        .filter(|(key, _)| !key.trim().is_empty())
+        .map(|(key, value)| json!({"key": key, "value": value}))
+        .collect();
+
+    // Postman has no cookies per request, they are sent as a header
+    let cookies: Vec<String> = request
+        .cookies
+        .iter()
+        .filter(|(key, _)| !key.trim().is_empty())
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    if !cookies.is_empty() {
+        headers.push(json!({"key": "Cookie", "value": cookies.join("; ")}));
+    }
+
+    let body = request.body.to_string();
+    if !body.trim().is_empty() && !request.has_header("Content-Type") {
+        headers.push(json!({"key": "Content-Type", "value": "application/json"}));
+    }
+
+    let mut exported = json!({
+        "method": request.method.0.to_string(),
+        "header": headers,
+        "url": {
+            "raw": raw,
+            "query": queries
+                .iter()
+                .map(|(key, value)| json!({"key": key, "value": value}))
+                .collect::<Vec<Value>>(),
+        },
+    });
+
+    if !body.trim().is_empty() {
+        exported["body"] = json!({
+            "mode": "raw",
+            "raw": body,
+            "options": {"raw": {"language": "json"}},
+        });
+    }
+
+    match &request.authorization {
+        FalconAuthorization::Bearer { prefix, token } if !token.trim().is_empty() => {
+            exported["auth"] = match prefix.trim().eq_ignore_ascii_case("bearer") {
+                true => json!({
+                    "type": "bearer",
+                    "bearer": [{"key": "token", "value": token, "type": "string"}],
+                }),
+                // other schemes are only known as a header
+                false => json!({
+                    "type": "apikey",
+                    "apikey": [
+                        {"key": "key", "value": "Authorization", "type": "string"},
+                        {"key": "value", "value": format!("{} {}", prefix, token), "type": "string"},
+                        {"key": "in", "value": "header", "type": "string"},
+                    ],
+                }),
+            };
+        }
+        FalconAuthorization::Basic { username, password } if !username.trim().is_empty() => {
+            exported["auth"] = json!({
+                "type": "basic",
+                "basic": [
+                    {"key": "username", "value": username, "type": "string"},
+                    {"key": "password", "value": password, "type": "string"},
+                ],
+            });
+        }
+        _ => {}
+    }
+
+    json!({
+        "name": request.name.clone().unwrap_or(request.url.clone()),
+        "request": exported,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLLECTION: &str = r#"{
+        "info": {
+            "name": "Shop",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{authToken}}", "type": "string"}]},
+        "variable": [{"key": "baseUrl", "value": "https://shop.test"}],
+        "item": [
+            {
+                "name": "Users",
+                "item": [
+                    {
+                        "name": "Get user",
+                        "event": [{"listen": "test", "script": {"exec": ["pm.test()"]}}],
+                        "request": {
+                            "method": "GET",
+                            "header": [
+                                {"key": "Accept", "value": "application/json"},
+                                {"key": "X-Debug", "value": "1", "disabled": true}
+                            ],
+                            "url": {
+                                "raw": "{{baseUrl}}/users/:id?expand=true",
+                                "query": [{"key": "expand", "value": "true"}],
+                                "variable": [{"key": "id", "value": "7"}]
+                            }
+                        }
+                    },
+                    {
+                        "name": "Admin",
+                        "item": [{
+                            "name": "Login",
+                            "request": {
+                                "method": "POST",
+                                "auth": {"type": "basic", "basic": [
+                                    {"key": "username", "value": "ada"},
+                                    {"key": "password", "value": "{{password}}"}
+                                ]},
+                                "body": {"mode": "raw", "raw": "{\"remember\": true}"},
+                                "url": "{{baseUrl}}/login"
+                            }
+                        }]
+                    }
+                ]
+            },
+            {
+                "name": "Upload",
+                "request": {
+                    "method": "PUT",
+                    "auth": {"type": "oauth2"},
+                    "body": {"mode": "formdata", "formdata": []},
+                    "url": "{{baseUrl}}/upload"
+                }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_imports_collection() {
+        let import = import_collection(COLLECTION).unwrap();
+        let requests = &import.project.requests;
+
+        assert_eq!(import.project.name, "Shop");
+        assert_eq!(import.report.requests, 3);
+        assert_eq!(import.report.folders, 3);
+
+        let get_user = &requests["Users"][0];
+        assert_eq!(get_user.url, "{{BASE_URL}}/users/7");
+        assert_eq!(get_user.queries[0], ("expand".into(), "true".into()));
+        assert_eq!(get_user.headers.len(), 2);
+        match &get_user.authorization {
+            FalconAuthorization::Bearer { token, .. } => assert_eq!(token, "{{AUTH_TOKEN}}"),
+            auth => panic!("expected bearer, got {:?}", auth),
+        }
+
+        let login = &requests["Users > Admin"][0];
+        assert_eq!(login.method.0, Method::POST);
+        assert_eq!(login.body.to_string(), "{\"remember\": true}");
+        match &login.authorization {
+            FalconAuthorization::Basic { username, password } => {
+                assert_eq!(
+                    (username.as_str(), password.as_str()),
+                    ("ada", "{{PASSWORD}}")
+                )
+            }
+            auth => panic!("expected basic, got {:?}", auth),
+        }
+
+        assert_eq!(
+            import.report.unsupported,
+            vec![
+                "Scripts of Get user",
+                "Body mode formdata of Upload",
+                "Authorization type oauth2 of Upload",
+            ]
+        );
+
+        let env = import.env.unwrap();
+        assert_eq!(env.name, "Shop variables");
+        assert_eq!(env.variable("BASE_URL"), Some("https://shop.test"));
+    }
+
+    #[test]
+    fn test_keeps_the_type_of_raw_bodies() {
+        let import = import_collection(
+            r#"{
+            "info": {"name": "Import"},
+            "item": [
+                {
+                    "name": "Orders",
+                    "request": {
+                        "method": "POST",
+                        "header": [{"key": "Content-Type", "value": "application/xml"}],
+                        "body": {"mode": "raw", "raw": "<orders/>"},
+                        "url": "https://shop.test/orders"
+                    }
+                },
+                {
+                    "name": "Notes",
+                    "request": {
+                        "method": "POST",
+                        "body": {
+                            "mode": "raw",
+                            "raw": "a note",
+                            "options": {"raw": {"language": "text"}}
+                        },
+                        "url": "https://shop.test/notes"
+                    }
+                }
+            ]
+        }"#,
+        )
+        .unwrap();
+        let requests = &import.project.requests["root"];
+
+        assert_eq!(
+            requests[0].headers[0],
+            ("Content-Type".into(), "application/xml".into())
+        );
+        assert_eq!(
+            requests[1].headers[0],
+            ("Content-Type".into(), "text/plain".into())
+        );
+        assert!(import.report.unsupported.is_empty());
+    }
+
+    #[test]
+    fn test_exports_collection() {
+        let mut project = import_collection(COLLECTION).unwrap().project;
+        project.add_request(
+            "root",
+            PendingRequest {
+                name: Some("Health".into()),
+                url: "{{%PROJECT_BASE_URL%}}/health".into(),
+                cookies: vec![("session".into(), "s1".into())],
+                ..Default::default()
+            },
+        );
+        let env = Env {
+            base_url: Some("https://shop.test".into()),
+            items: vec![("TOKEN".into(), "abc".into()), ("".into(), "".into())],
+            ..Default::default()
+        };
+
+        let exported = export_collection(&project, Some(&env)).unwrap();
+        let json: Value = serde_json::from_str(&exported).unwrap();
+        assert_eq!(json["info"]["schema"], COLLECTION_SCHEMA);
+        assert_eq!(
+            json["item"][1]["request"]["url"]["raw"],
+            "{{baseUrl}}/health"
+        );
+        assert_eq!(
+            json["item"][1]["request"]["header"][0]["value"],
+            "session=s1"
+        );
+        assert_eq!(json["item"][2]["item"][1]["name"], "Admin");
+        assert_eq!(json["variable"][1]["key"], "TOKEN");
+
+        // the export imports back into the same requests
+        let import = import_collection(&exported).unwrap();
+        assert_eq!(import.report.requests, 4);
+        assert_eq!(import.report.folders, 3);
+
+        let login = &import.project.requests["Users > Admin"][0];
+        let original = &project.requests["Users > Admin"][0];
+        assert_eq!(login.url, original.url);
+        assert_eq!(login.body.to_string(), original.body.to_string());
+        assert!(matches!(
+            login.authorization,
+            FalconAuthorization::Basic { ref password, .. } if password == "{{PASSWORD}}"
+        ));
+        assert_eq!(
+            import.project.requests["Users"][0].queries,
+            project.requests["Users"][0].queries
+        );
+    }
+
+    #[test]
+    fn test_imports_environment() {
+        let (env, report) = import_environment(
+            r#"{"name": "Staging", "values": [
+                {"key": "baseUrl", "value": "https://staging.test", "enabled": true},
+                {"key": "old", "value": "x", "enabled": false}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(env.name, "Staging");
+        assert_eq!(
+            env.items,
+            vec![
+                ("BASE_URL".into(), "https://staging.test".into()),
+                ("".into(), "".into())
+            ]
+        );
+        assert_eq!(report.unsupported, vec!["Disabled variable old of Staging"]);
+    }
+}
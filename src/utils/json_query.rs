@@ -0,0 +1,127 @@
+use std::fmt::Display;
+
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonQuery {
+    JsonPath(String),
+    JmesPath(String),
+}
+
+impl From<&str> for JsonQuery {
+    fn from(value: &str) -> Self {
+        let value = value.trim();
+
+        // JSONPath expressions always start from the root node
+        if value.starts_with('$') {
+            JsonQuery::JsonPath(value.to_string())
+        } else {
+            JsonQuery::JmesPath(value.to_string())
+        }
+    }
+}
+
+impl Display for JsonQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonQuery::JsonPath(expr) => write!(f, "JSONPath {}", expr),
+            JsonQuery::JmesPath(expr) => write!(f, "JMESPath {}", expr),
+        }
+    }
+}
+
+impl JsonQuery {
+    /// Evaluates the expression, `None` means nothing matched.
+    /// JSONPath returns the node itself for a single match and an array otherwise.
+    pub fn evaluate(&self, value: &Value) -> Result<Option<Value>, String> {
+        match self {
+            JsonQuery::JsonPath(expr) => {
+                let path = JsonPath::parse(expr).map_err(|err| err.to_string())?;
+                let mut nodes = path.query(value).all();
+
+                Ok(match nodes.len() {
+                    0 => None,
+                    1 => nodes.pop().cloned(),
+                    _ => Some(Value::Array(nodes.into_iter().cloned().collect())),
+                })
+            }
+            JsonQuery::JmesPath(expr) => {
+                let expression = jmespath::compile(expr).map_err(|err| err.to_string())?;
+                let result = expression
+                    .search(value.clone())
+                    .map_err(|err| err.to_string())?;
+
+                if result.is_null() {
+                    return Ok(None);
+                }
+
+                serde_json::to_value(&*result)
+                    .map(Some)
+                    .map_err(|err| err.to_string())
+            }
+        }
+    }
+
+    pub fn evaluate_str(&self, json: &str) -> Result<Option<Value>, String> {
+        let value: Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        self.evaluate(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn payload() -> Value {
+        json!({"hits": {"total": 2, "items": [{"id": 1, "tag": "a"}, {"id": 2, "tag": "b"}]}})
+    }
+
+    #[test]
+    fn test_detects_query_language() {
+        assert_eq!(
+            JsonQuery::from(" $.hits"),
+            JsonQuery::JsonPath("$.hits".into())
+        );
+        assert_eq!(
+            JsonQuery::from("hits.total"),
+            JsonQuery::JmesPath("hits.total".into())
+        );
+    }
+
+    #[test]
+    fn test_json_path_single_and_multiple_matches() {
+        let value = payload();
+
+        let total = JsonQuery::from("$.hits.total").evaluate(&value).unwrap();
+        assert_eq!(total, Some(json!(2)));
+
+        let ids = JsonQuery::from("$.hits.items[*].id")
+            .evaluate(&value)
+            .unwrap();
+        assert_eq!(ids, Some(json!([1, 2])));
+
+        let missing = JsonQuery::from("$.nope").evaluate(&value).unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn test_jmes_path() {
+        let value = payload();
+
+        let tags = JsonQuery::from("hits.items[?id > `1`].tag")
+            .evaluate(&value)
+            .unwrap();
+        assert_eq!(tags, Some(json!(["b"])));
+
+        let missing = JsonQuery::from("hits.nope").evaluate(&value).unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn test_invalid_expression() {
+        assert!(JsonQuery::from("$.[").evaluate(&payload()).is_err());
+        assert!(JsonQuery::from("hits.[").evaluate(&payload()).is_err());
+    }
+}
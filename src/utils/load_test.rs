@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+
+use super::request::ResolvedRequest;
+
+/// Buckets of the latency histogram.
+pub const HISTOGRAM_BUCKETS: usize = 10;
+
+/// A worker waiting for its turn at the target rate checks this often for a stop.
+const STOP_CHECK: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadLimit {
+    Count(u64),
+    Duration(Duration),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum LoadLimitKind {
+    #[default]
+    Count,
+    Duration,
+}
+
+impl LoadLimitKind {
+    pub const ALL: [LoadLimitKind; 2] = [LoadLimitKind::Count, LoadLimitKind::Duration];
+}
+
+impl Display for LoadLimitKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            LoadLimitKind::Count => "Total requests",
+            LoadLimitKind::Duration => "Duration (s)",
+        };
+
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadTestOptions {
+    pub concurrency: usize,
+    pub limit: LoadLimit,
+    /// Requests per second over all workers, `None` sends as fast as possible.
+    pub target_rps: Option<f64>,
+}
+
+impl Default for LoadTestOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 10,
+            limit: LoadLimit::Count(100),
+            target_rps: None,
+        }
+    }
+}
+
+/// One request of the test, `outcome` is the status code or the kind of error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadSample {
+    pub latency: Duration,
+    pub outcome: Result<u16, String>,
+}
+
+impl LoadSample {
+    pub fn failed(&self) -> bool {
+        !matches!(self.outcome, Ok(status) if status < 400)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramBucket {
+    pub from_ms: f64,
+    pub to_ms: f64,
+    pub count: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LoadReport {
+    pub total: u64,
+    pub failed: u64,
+    pub elapsed: Duration,
+    /// Completed requests per second.
+    pub throughput: f64,
+    pub statuses: BTreeMap<u16, u64>,
+    pub errors: BTreeMap<String, u64>,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    pub histogram: Vec<HistogramBucket>,
+    pub stopped: bool,
+}
+
+impl LoadReport {
+    pub fn new(samples: &[LoadSample], elapsed: Duration) -> Self {
+        let mut latencies: Vec<Duration> = samples.iter().map(|sample| sample.latency).collect();
+        latencies.sort();
+
+        let mut statuses = BTreeMap::new();
+        let mut errors = BTreeMap::new();
+        for sample in samples {
+            match &sample.outcome {
+                Ok(status) => *statuses.entry(*status).or_insert(0) += 1,
+                Err(kind) => *errors.entry(kind.clone()).or_insert(0) += 1,
+            }
+        }
+
+        let seconds = elapsed.as_secs_f64();
+
+        Self {
+            total: samples.len() as u64,
+            failed: samples.iter().filter(|sample| sample.failed()).count() as u64,
+            elapsed,
+            throughput: if seconds > 0.0 {
+                samples.len() as f64 / seconds
+            } else {
+                0.0
+            },
+            statuses,
+            errors,
+            p50: percentile(&latencies, 50.0),
+            p90: percentile(&latencies, 90.0),
+            p99: percentile(&latencies, 99.0),
+            max: latencies.last().copied().unwrap_or_default(),
+            histogram: histogram(&latencies),
+            stopped: false,
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted latencies.
+fn percentile(sorted: &[Duration], percent: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Evenly sized buckets between the fastest and the slowest request.
+fn histogram(sorted: &[Duration]) -> Vec<HistogramBucket> {
+    let (Some(min), Some(max)) = (sorted.first(), sorted.last()) else {
+        return vec![];
+    };
+
+    let min = min.as_secs_f64() * 1000.0;
+    let max = max.as_secs_f64() * 1000.0;
+    let width = ((max - min) / HISTOGRAM_BUCKETS as f64).max(f64::EPSILON);
+
+    let mut buckets: Vec<HistogramBucket> = (0..HISTOGRAM_BUCKETS)
+        .map(|index| HistogramBucket {
+            from_ms: min + width * index as f64,
+            to_ms: min + width * (index + 1) as f64,
+            count: 0,
+        })
+        .collect();
+
+    for latency in sorted {
+        let ms = latency.as_secs_f64() * 1000.0;
+        let index = (((ms - min) / width) as usize).min(HISTOGRAM_BUCKETS - 1);
+        buckets[index].count += 1;
+    }
+
+    buckets
+}
+
+fn error_kind(err: &reqwest::Error) -> String {
+    let kind = if err.is_timeout() {
+        "Timeout"
+    } else if err.is_connect() {
+        "Connection"
+    } else if err.is_body() || err.is_decode() {
+        "Body"
+    } else if err.is_redirect() {
+        "Redirect"
+    } else {
+        "Request"
+    };
+
+    kind.to_string()
+}
+
+/// Sends the request until the limit is reached or `stop` is set.
+/// Every worker shares one client so connections are reused.
+pub async fn run(
+    request: ResolvedRequest,
+    options: LoadTestOptions,
+    stop: Arc<AtomicBool>,
+) -> Result<LoadReport, String> {
+    let client = Client::builder()
+        .pool_max_idle_per_host(options.concurrency)
+        .build()
+        .map_err(|err| err.to_string())?;
+    // fail fast on an invalid request instead of once per worker
+    if let Err(err) = request.build(&client) {
+        return Err(format!("Invalid request, {}", err));
+    }
+
+    let request = Arc::new(request);
+    let issued = Arc::new(AtomicU64::new(0));
+    let start = Instant::now();
+
+    let workers: Vec<_> = (0..options.concurrency.max(1))
+        .map(|_| {
+            let (client, request) = (client.clone(), request.clone());
+            let (issued, stop) = (issued.clone(), stop.clone());
+
+            tokio::spawn(async move {
+                let mut samples = vec![];
+                let within_limit = |index: u64| match options.limit {
+                    LoadLimit::Count(count) => index < count,
+                    LoadLimit::Duration(duration) => start.elapsed() < duration,
+                };
+
+                loop {
+                    let index = issued.fetch_add(1, Ordering::SeqCst);
+                    if !within_limit(index) || stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    // the limit is checked again, the turn may come after the deadline
+                    if let Some(rps) = options.target_rps.filter(|rps| *rps > 0.0) {
+                        let due = start + Duration::from_secs_f64(index as f64 / rps);
+                        if !wait_until(due, &stop).await || !within_limit(index) {
+                            break;
+                        }
+                    }
+
+                    samples.push(send(&client, &request).await);
+                }
+
+                samples
+            })
+        })
+        .collect();
+
+    let mut samples = vec![];
+    for worker in workers {
+        samples.extend(worker.await.map_err(|err| err.to_string())?);
+    }
+
+    let mut report = LoadReport::new(&samples, start.elapsed());
+    report.stopped = stop.load(Ordering::SeqCst);
+
+    Ok(report)
+}
+
+/// Sleeps until `due`, `false` when stopped before.
+async fn wait_until(due: Instant, stop: &AtomicBool) -> bool {
+    while !stop.load(Ordering::SeqCst) {
+        let now = Instant::now();
+        if now >= due {
+            return true;
+        }
+        tokio::time::sleep((due - now).min(STOP_CHECK)).await;
+    }
+
+    false
+}
+
+async fn send(client: &Client, request: &ResolvedRequest) -> LoadSample {
+    let start = Instant::now();
+    let outcome = match request.build(client) {
+        Ok(builder) => match builder.send().await {
+            // the body is read so the connection goes back to the pool
+            Ok(res) => {
+                let status = res.status().as_u16();
+                res.bytes()
+                    .await
+                    .map(|_| status)
+                    .map_err(|err| error_kind(&err))
+            }
+            Err(err) => Err(error_kind(&err)),
+        },
+        Err(_) => Err("Request".to_string()),
+    };
+
+    LoadSample {
+        latency: start.elapsed(),
+        outcome,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(ms: u64, outcome: Result<u16, &str>) -> LoadSample {
+        LoadSample {
+            latency: Duration::from_millis(ms),
+            outcome: outcome.map_err(String::from),
+        }
+    }
+
+    #[test]
+    fn test_report_percentiles_and_breakdown() {
+        let mut samples: Vec<LoadSample> = (1..=100).map(|ms| sample(ms, Ok(200))).collect();
+        samples[0] = sample(1, Ok(503));
+        samples[1] = sample(2, Err("Timeout"));
+
+        let report = LoadReport::new(&samples, Duration::from_secs(4));
+
+        assert_eq!(report.total, 100);
+        assert_eq!(report.failed, 2);
+        assert_eq!(report.throughput, 25.0);
+        assert_eq!(report.p50, Duration::from_millis(50));
+        assert_eq!(report.p90, Duration::from_millis(90));
+        assert_eq!(report.p99, Duration::from_millis(99));
+        assert_eq!(report.max, Duration::from_millis(100));
+        assert_eq!(report.statuses.get(&200), Some(&98));
+        assert_eq!(report.statuses.get(&503), Some(&1));
+        assert_eq!(report.errors.get("Timeout"), Some(&1));
+
+        assert_eq!(report.histogram.len(), HISTOGRAM_BUCKETS);
+        assert_eq!(
+            report
+                .histogram
+                .iter()
+                .map(|bucket| bucket.count)
+                .sum::<u64>(),
+            100
+        );
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let report = LoadReport::new(&[], Duration::ZERO);
+
+        assert_eq!(report.throughput, 0.0);
+        assert_eq!(report.p99, Duration::ZERO);
+        assert!(report.histogram.is_empty());
+    }
+
+    #[test]
+    fn test_stops_at_the_deadline_and_on_stop() {
+        let request = ResolvedRequest {
+            method: "GET".into(),
+            // refused right away, only the number of sends matters
+            url: "http://127.0.0.1:9".into(),
+            ..Default::default()
+        };
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        // turns at 0s, 0.5s and 1s, the last one is past the deadline
+        let options = LoadTestOptions {
+            concurrency: 2,
+            limit: LoadLimit::Duration(Duration::from_millis(700)),
+            target_rps: Some(2.0),
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+        let report = runtime
+            .block_on(run(request.clone(), options, stop))
+            .unwrap();
+        assert_eq!(report.total, 2);
+
+        // the second turn is 10s away
+        let options = LoadTestOptions {
+            target_rps: Some(0.1),
+            limit: LoadLimit::Count(5),
+            ..options
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+        let started = Instant::now();
+        let report = runtime.block_on(async {
+            let stopping = stop.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                stopping.store(true, Ordering::SeqCst);
+            });
+            run(request, options, stop).await.unwrap()
+        });
+        assert!(report.stopped && report.total == 1);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
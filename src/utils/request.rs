@@ -1,20 +1,27 @@
+use base64::Engine;
 use http_method::HttpMethod;
-use reqwest::cookie::Jar;
-use reqwest::header::{self, HeaderMap, HeaderName, HeaderValue};
-use reqwest::{Body, Client, StatusCode};
+use reqwest::header::{self, HeaderMap};
+use reqwest::{Body, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
-use std::sync::Arc;
-use std::time::{Instant, SystemTime};
+use std::time::SystemTime;
 use uuid::Uuid;
 
+use assertion::{Assertion, AssertionKind};
+use extraction::{Extraction, ExtractionSource};
+
 use super::db::env::Env;
 use super::falcon_duration::FalconDuration;
 
+pub mod assertion;
+pub mod extraction;
 pub mod http_method;
+pub mod json_body;
 pub mod request_url;
+pub mod resolved_request;
 
 pub use request_url::RequestUrl;
+pub use resolved_request::ResolvedRequest;
 
 #[derive(Debug, Clone)]
 pub enum PendingRequestItem {
@@ -44,6 +51,7 @@ pub struct FalconResponse {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum FalconAuthorization {
     Bearer { prefix: String, token: String },
+    Basic { username: String, password: String },
 }
 
 impl Default for FalconAuthorization {
@@ -93,6 +101,21 @@ pub struct PendingRequest {
     pub queries: Vec<(String, String)>,
     pub authorization: FalconAuthorization,
     pub body: FlBody,
+    /// Fields from here on were added after the first release, `migration` writes them
+    /// out in older files.
+    pub response_filter: Option<String>,
+    pub assertions: Vec<Assertion>,
+    pub extractions: Vec<Extraction>,
+    pub pre_request_script: String,
+    pub post_response_script: String,
+}
+
+/// Adds a header the request sets on its own, a header of the same name the user set
+/// explicitly wins.
+fn push_default(headers: &mut Vec<(String, String)>, name: header::HeaderName, value: String) {
+    if !headers.iter().any(|(key, _)| key.trim().eq_ignore_ascii_case(name.as_str())) {
+        headers.push((name.to_string(), value));
+    }
 }
 
 impl Default for PendingRequest {
@@ -107,18 +130,20 @@ impl Default for PendingRequest {
             queries: vec![("".to_string(), "".to_string())],
             authorization: FalconAuthorization::default(),
             body: FlBody::default(),
+            response_filter: None,
+            assertions: vec![],
+            extractions: vec![],
+            pre_request_script: String::new(),
+            post_response_script: String::new(),
         }
     }
 }
 
 impl PendingRequest {
-    pub async fn send(&self, env: &Env, base_url: &str) -> anyhow::Result<FalconResponse> {
-        // Create a cookie jar
-        let cookie_jar = Arc::new(Jar::default());
-
+    /// Resolves the env variables, base url, queries, cookies and authorization
+    /// into the request that is actually sent.
+    pub fn resolve(&self, env: &Env, base_url: &str) -> anyhow::Result<ResolvedRequest> {
         let url = RequestUrl::from(self.url.clone()).build(base_url);
-
-        // Add a cookie manually (if needed)
         let mut url = url::Url::parse(&env.replace_variables(url))?;
 
         for (name, value) in self.queries.iter() {
@@ -128,107 +153,83 @@ impl PendingRequest {
             }
         }
 
-        println!(
-            "{:<10}[FALCON]: ({}) {}",
-            "INFO",
-            self.method.0.clone(),
-            url.clone()
-        );
-
-        for (name, value) in self.cookies.iter() {
-            cookie_jar.add_cookie_str(
-                &format!(
-                    "{}={}",
-                    env.replace_variables(name),
-                    env.replace_variables(value)
-                ),
-                &url,
-            );
-        }
-
-        let mut headers = HeaderMap::new();
+        let mut headers = Vec::new();
 
         for (key, value) in self.headers.iter() {
             if !key.trim().is_empty() {
-                headers.insert(
-                    HeaderName::from_bytes(env.replace_variables(key).as_bytes())?,
-                    HeaderValue::from_str(&env.replace_variables(value))?,
-                );
+                headers.push((env.replace_variables(key), env.replace_variables(value)));
             }
         }
 
+        // the headers below are only added when the user did not set them, one value each
         match self.body {
             FlBody::ApplicationJson(_) => {
-                headers.insert(
-                    header::CONTENT_TYPE,
-                    HeaderValue::from_str("application/json")?,
-                );
+                push_default(&mut headers, header::CONTENT_TYPE, "application/json".to_string());
             }
         }
 
         match self.authorization.clone() {
             FalconAuthorization::Bearer { prefix, token } => {
                 if !token.trim().is_empty() {
-                    headers.insert(
+                    push_default(
+                        &mut headers,
                         header::AUTHORIZATION,
-                        HeaderValue::from_str(&format!(
+                        format!(
                             "{} {}",
                             env.replace_variables(prefix),
                             env.replace_variables(token)
-                        ))?,
+                        ),
+                    );
+                }
+            }
+            FalconAuthorization::Basic { username, password } => {
+                if !username.trim().is_empty() {
+                    let credentials = format!(
+                        "{}:{}",
+                        env.replace_variables(username),
+                        env.replace_variables(password)
+                    );
+                    push_default(
+                        &mut headers,
+                        header::AUTHORIZATION,
+                        format!(
+                            "Basic {}",
+                            base64::engine::general_purpose::STANDARD.encode(credentials)
+                        ),
                     );
                 }
             }
         }
 
-        // Create a reqwest client with the cookie jar
-        let client = Client::builder()
-            .default_headers(headers)
-            .cookie_provider(cookie_jar)
-            .build()?;
-
-        // Start timing the request
-        let start = Instant::now();
-
-        // Send a request
-        let res = client
-            .request(self.method.clone().into(), url)
-            .body(self.body.clone())
-            .send()
-            .await?;
-
-        // Calculate the duration
-        let duration = start.elapsed();
-
-        // Get the status, body, headers, and cookies
-        let status_code = res.status();
-        let headers = res.headers().clone();
-
-        let cookies: Vec<FalconCookie> = res
-            .cookies()
-            .map(|c| FalconCookie {
-                name: c.name().to_string(),
-                http_only: c.http_only(),
-                value: Some(c.value().to_string()),
-                expires: c.expires(),
+        let cookies: Vec<String> = self
+            .cookies
+            .iter()
+            .filter(|(name, _)| !name.trim().is_empty())
+            .map(|(name, value)| {
+                format!(
+                    "{}={}",
+                    env.replace_variables(name),
+                    env.replace_variables(value)
+                )
             })
             .collect();
 
-        let body = res.text().await.unwrap_or_default();
-
-        // Calculate response size in kilobytes
-        let size_kb = (body.len() as f64 / 1024.0).ceil();
+        if !cookies.is_empty() {
+            push_default(&mut headers, header::COOKIE, cookies.join("; "));
+        }
 
-        Ok(FalconResponse {
-            body,
-            duration: duration.into(),
+        Ok(ResolvedRequest {
+            method: self.method.clone(),
+            url: url.to_string(),
             headers,
-            size_kb,
-            status_code,
-            cookies,
+            body: env.replace_variables(self.body.to_string()),
         })
     }
 
+    pub async fn send(&self, env: &Env, base_url: &str) -> anyhow::Result<FalconResponse> {
+        self.resolve(env, base_url)?.send().await
+    }
+
     pub fn set_url(&mut self, url: impl Into<String>) {
         self.url = url.into();
     }
@@ -237,6 +238,13 @@ impl PendingRequest {
         self.headers.push((name.into(), value.into()));
     }
 
+    /// Whether the user set the header, whatever the case of its name.
+    pub fn has_header(&self, name: &str) -> bool {
+        self.headers
+            .iter()
+            .any(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+    }
+
     pub fn add_cookie(&mut self, name: impl Into<String>, value: impl Into<String>) {
         self.cookies.push((name.into(), value.into()));
     }
@@ -350,4 +358,69 @@ impl PendingRequest {
     pub fn set_body(&mut self, body: FlBody) {
         self.body = body;
     }
+
+    pub fn set_response_filter(&mut self, filter: impl Into<String>) {
+        let filter = filter.into();
+        self.response_filter = if filter.trim().is_empty() {
+            None
+        } else {
+            Some(filter)
+        };
+    }
+
+    pub fn add_assertion(&mut self) {
+        self.assertions.push(Assertion::default());
+    }
+
+    pub fn remove_assertion(&mut self, index: usize) {
+        if index < self.assertions.len() {
+            self.assertions.remove(index);
+        }
+    }
+
+    pub fn update_assertion_kind(&mut self, index: usize, kind: AssertionKind) {
+        if let Some(assertion) = self.assertions.get_mut(index) {
+            assertion.kind = kind;
+        }
+    }
+
+    pub fn update_assertion_target(&mut self, index: usize, target: impl Into<String>) {
+        if let Some(assertion) = self.assertions.get_mut(index) {
+            assertion.target = target.into();
+        }
+    }
+
+    pub fn update_assertion_expected(&mut self, index: usize, expected: impl Into<String>) {
+        if let Some(assertion) = self.assertions.get_mut(index) {
+            assertion.expected = expected.into();
+        }
+    }
+
+    pub fn add_extraction(&mut self) {
+        self.extractions.push(Extraction::default());
+    }
+
+    pub fn remove_extraction(&mut self, index: usize) {
+        if index < self.extractions.len() {
+            self.extractions.remove(index);
+        }
+    }
+
+    pub fn update_extraction_source(&mut self, index: usize, source: ExtractionSource) {
+        if let Some(extraction) = self.extractions.get_mut(index) {
+            extraction.source = source;
+        }
+    }
+
+    pub fn update_extraction_expression(&mut self, index: usize, expression: impl Into<String>) {
+        if let Some(extraction) = self.extractions.get_mut(index) {
+            extraction.expression = expression.into();
+        }
+    }
+
+    pub fn update_extraction_variable(&mut self, index: usize, variable: impl Into<String>) {
+        if let Some(extraction) = self.extractions.get_mut(index) {
+            extraction.variable = variable.into();
+        }
+    }
 }
@@ -0,0 +1,327 @@
+use std::fmt::Display;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::utils::json_query::JsonQuery;
+
+use super::FalconResponse;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum AssertionKind {
+    #[default]
+    StatusEquals,
+    StatusInRange,
+    HeaderPresent,
+    HeaderMatches,
+    JsonPathEquals,
+    JsonPathExists,
+    JsonPathType,
+    BodyContains,
+    ResponseTimeBelow,
+}
+
+impl AssertionKind {
+    pub const ALL: [AssertionKind; 9] = [
+        AssertionKind::StatusEquals,
+        AssertionKind::StatusInRange,
+        AssertionKind::HeaderPresent,
+        AssertionKind::HeaderMatches,
+        AssertionKind::JsonPathEquals,
+        AssertionKind::JsonPathExists,
+        AssertionKind::JsonPathType,
+        AssertionKind::BodyContains,
+        AssertionKind::ResponseTimeBelow,
+    ];
+
+    /// Placeholder of the target input, `None` when the kind has no target.
+    pub fn target_hint(&self) -> Option<&'static str> {
+        match self {
+            AssertionKind::HeaderPresent | AssertionKind::HeaderMatches => Some("Header name"),
+            AssertionKind::JsonPathEquals
+            | AssertionKind::JsonPathExists
+            | AssertionKind::JsonPathType => Some("$.data.id"),
+            _ => None,
+        }
+    }
+
+    /// Placeholder of the expected value input, `None` when the kind has no value.
+    pub fn expected_hint(&self) -> Option<&'static str> {
+        match self {
+            AssertionKind::StatusEquals => Some("200"),
+            AssertionKind::StatusInRange => Some("200-299"),
+            AssertionKind::HeaderMatches => Some("^application/json"),
+            AssertionKind::JsonPathEquals => Some("JSON value, e.g. 42 or \"ok\""),
+            AssertionKind::JsonPathType => Some("string, number, boolean, object, array or null"),
+            AssertionKind::BodyContains => Some("Text"),
+            AssertionKind::ResponseTimeBelow => Some("Milliseconds"),
+            AssertionKind::HeaderPresent | AssertionKind::JsonPathExists => None,
+        }
+    }
+}
+
+impl Display for AssertionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            AssertionKind::StatusEquals => "Status equals",
+            AssertionKind::StatusInRange => "Status in range",
+            AssertionKind::HeaderPresent => "Header present",
+            AssertionKind::HeaderMatches => "Header matches",
+            AssertionKind::JsonPathEquals => "JSON path equals",
+            AssertionKind::JsonPathExists => "JSON path exists",
+            AssertionKind::JsonPathType => "JSON path type",
+            AssertionKind::BodyContains => "Body contains",
+            AssertionKind::ResponseTimeBelow => "Response time below",
+        };
+
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Assertion {
+    pub kind: AssertionKind,
+    #[serde(default)]
+    pub target: String,
+    #[serde(default)]
+    pub expected: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AssertionResult {
+    pub name: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+impl AssertionResult {
+    pub fn new(name: impl Into<String>, result: Result<(), String>) -> Self {
+        let (passed, message) = match result {
+            Ok(_) => (true, String::new()),
+            Err(message) => (false, message),
+        };
+
+        Self {
+            name: name.into(),
+            passed,
+            message,
+        }
+    }
+}
+
+impl Display for Assertion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<&str> = [self.target.trim(), self.expected.trim()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        match self.kind {
+            AssertionKind::ResponseTimeBelow => write!(f, "{} {} ms", self.kind, parts.join(" ")),
+            _ => write!(f, "{} {}", self.kind, parts.join(" ")),
+        }
+    }
+}
+
+impl Assertion {
+    pub fn new(
+        kind: AssertionKind,
+        target: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            target: target.into(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn evaluate(&self, response: &FalconResponse) -> AssertionResult {
+        AssertionResult::new(self.to_string(), self.check(response))
+    }
+
+    fn check(&self, response: &FalconResponse) -> Result<(), String> {
+        let status = response.status_code.as_u16();
+        let expected = self.expected.trim();
+        let target = self.target.trim();
+
+        match self.kind {
+            AssertionKind::StatusEquals => {
+                let code = parse_number::<u16>(expected)?;
+                expect(status == code, || format!("got status {}", status))
+            }
+            AssertionKind::StatusInRange => {
+                let (from, to) = expected
+                    .split_once('-')
+                    .ok_or(format!("invalid range \"{}\", use 200-299", expected))?;
+                let (from, to) = (parse_number::<u16>(from)?, parse_number::<u16>(to)?);
+
+                expect((from..=to).contains(&status), || {
+                    format!("got status {}", status)
+                })
+            }
+            AssertionKind::HeaderPresent => expect(response.headers.contains_key(target), || {
+                format!("header \"{}\" is missing", target)
+            }),
+            AssertionKind::HeaderMatches => {
+                let regex = Regex::new(expected).map_err(|err| err.to_string())?;
+                let value = response
+                    .headers
+                    .get(target)
+                    .ok_or(format!("header \"{}\" is missing", target))?;
+                let value = String::from_utf8_lossy(value.as_bytes());
+
+                expect(regex.is_match(&value), || format!("got \"{}\"", value))
+            }
+            AssertionKind::JsonPathEquals => {
+                let actual = query_json(&response.body, target)?
+                    .ok_or(format!("nothing found at {}", target))?;
+                // anything which isn't valid JSON is compared as a plain string
+                let expected_value = serde_json::from_str::<Value>(expected)
+                    .unwrap_or(Value::String(expected.to_string()));
+
+                expect(actual == expected_value, || format!("got {}", actual))
+            }
+            AssertionKind::JsonPathExists => {
+                let actual = query_json(&response.body, target)?;
+                expect(actual.is_some(), || format!("nothing found at {}", target))
+            }
+            AssertionKind::JsonPathType => {
+                let actual = query_json(&response.body, target)?
+                    .ok_or(format!("nothing found at {}", target))?;
+                let actual_type = json_type(&actual);
+
+                expect(actual_type.eq_ignore_ascii_case(expected), || {
+                    format!("got {}", actual_type)
+                })
+            }
+            AssertionKind::BodyContains => expect(response.body.contains(expected), || {
+                "body doesn't contain the text".to_string()
+            }),
+            AssertionKind::ResponseTimeBelow => {
+                let limit = parse_number::<u128>(expected)?;
+                let actual = response.duration.as_duration().as_millis();
+
+                expect(actual < limit, || format!("took {} ms", actual))
+            }
+        }
+    }
+}
+
+/// Evaluates every assertion against the response, in order.
+pub fn evaluate_all(assertions: &[Assertion], response: &FalconResponse) -> Vec<AssertionResult> {
+    assertions
+        .iter()
+        .map(|assertion| assertion.evaluate(response))
+        .collect()
+}
+
+fn expect(passed: bool, message: impl FnOnce() -> String) -> Result<(), String> {
+    if passed {
+        Ok(())
+    } else {
+        Err(message())
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("\"{}\" is not a valid number", value.trim()))
+}
+
+fn query_json(body: &str, path: &str) -> Result<Option<Value>, String> {
+    JsonQuery::from(path).evaluate_str(body)
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::StatusCode;
+
+    use super::*;
+
+    fn response() -> FalconResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/json; charset=utf-8"),
+        );
+
+        FalconResponse {
+            status_code: StatusCode::CREATED,
+            body: r#"{"data": {"id": 7, "name": "Ada", "tags": []}}"#.into(),
+            headers,
+            cookies: vec![],
+            duration: Duration::from_millis(120).into(),
+            size_kb: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_passing_assertions() {
+        let assertions = vec![
+            Assertion::new(AssertionKind::StatusEquals, "", "201"),
+            Assertion::new(AssertionKind::StatusInRange, "", "200-299"),
+            Assertion::new(AssertionKind::HeaderPresent, "Content-Type", ""),
+            Assertion::new(
+                AssertionKind::HeaderMatches,
+                "content-type",
+                "^application/json",
+            ),
+            Assertion::new(AssertionKind::JsonPathEquals, "$.data.id", "7"),
+            Assertion::new(AssertionKind::JsonPathEquals, "data.name", "Ada"),
+            Assertion::new(AssertionKind::JsonPathExists, "$.data.tags", ""),
+            Assertion::new(AssertionKind::JsonPathType, "$.data.tags", "array"),
+            Assertion::new(AssertionKind::BodyContains, "", "Ada"),
+            Assertion::new(AssertionKind::ResponseTimeBelow, "", "500"),
+        ];
+
+        for result in evaluate_all(&assertions, &response()) {
+            assert!(result.passed, "{} failed: {}", result.name, result.message);
+        }
+    }
+
+    #[test]
+    fn test_failing_assertions_explain_why() {
+        let results = evaluate_all(
+            &[
+                Assertion::new(AssertionKind::StatusEquals, "", "200"),
+                Assertion::new(AssertionKind::JsonPathType, "$.data.id", "string"),
+                Assertion::new(AssertionKind::ResponseTimeBelow, "", "100"),
+                Assertion::new(AssertionKind::HeaderPresent, "x-request-id", ""),
+                Assertion::new(AssertionKind::StatusInRange, "", "200"),
+            ],
+            &response(),
+        );
+
+        let messages: Vec<&str> = results.iter().map(|r| r.message.as_str()).collect();
+        assert!(results.iter().all(|r| !r.passed));
+        assert_eq!(
+            messages,
+            vec![
+                "got status 201",
+                "got number",
+                "took 120 ms",
+                "header \"x-request-id\" is missing",
+                "invalid range \"200\", use 200-299",
+            ]
+        );
+        assert_eq!(results[0].name, "Status equals 200");
+    }
+}
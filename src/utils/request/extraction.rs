@@ -0,0 +1,218 @@
+use std::fmt::Display;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::utils::db::env::Env;
+use crate::utils::json_query::JsonQuery;
+
+use super::FalconResponse;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum ExtractionSource {
+    #[default]
+    JsonPath,
+    Header,
+    Cookie,
+    Regex,
+}
+
+impl ExtractionSource {
+    pub const ALL: [ExtractionSource; 4] = [
+        ExtractionSource::JsonPath,
+        ExtractionSource::Header,
+        ExtractionSource::Cookie,
+        ExtractionSource::Regex,
+    ];
+
+    /// Placeholder of the expression input.
+    pub fn hint(&self) -> &'static str {
+        match self {
+            ExtractionSource::JsonPath => "$.data.token",
+            ExtractionSource::Header => "Header name",
+            ExtractionSource::Cookie => "Cookie name",
+            ExtractionSource::Regex => "token=(\\w+)",
+        }
+    }
+}
+
+impl Display for ExtractionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ExtractionSource::JsonPath => "JSON path",
+            ExtractionSource::Header => "Header",
+            ExtractionSource::Cookie => "Cookie",
+            ExtractionSource::Regex => "Regex on body",
+        };
+
+        write!(f, "{}", label)
+    }
+}
+
+/// Stores a value of the response into an env variable.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Extraction {
+    pub source: ExtractionSource,
+    #[serde(default)]
+    pub expression: String,
+    #[serde(default)]
+    pub variable: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractionResult {
+    pub variable: String,
+    pub value: Result<String, String>,
+}
+
+impl Display for ExtractionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            Ok(value) => write!(f, "{} = {}", self.variable, value),
+            Err(err) => write!(f, "{} not set, {}", self.variable, err),
+        }
+    }
+}
+
+impl Extraction {
+    pub fn new(
+        source: ExtractionSource,
+        expression: impl Into<String>,
+        variable: impl Into<String>,
+    ) -> Self {
+        Self {
+            source,
+            expression: expression.into(),
+            variable: variable.into(),
+        }
+    }
+
+    /// The extracted value, JSON strings are unquoted and other JSON values kept as JSON.
+    pub fn extract(&self, response: &FalconResponse) -> Result<String, String> {
+        let expression = self.expression.trim();
+
+        match self.source {
+            ExtractionSource::JsonPath => {
+                let value = JsonQuery::from(expression)
+                    .evaluate_str(&response.body)?
+                    .ok_or(format!("nothing found at {}", expression))?;
+
+                Ok(match value {
+                    Value::String(value) => value,
+                    value => value.to_string(),
+                })
+            }
+            ExtractionSource::Header => response
+                .headers
+                .get(expression)
+                .map(|value| String::from_utf8_lossy(value.as_bytes()).to_string())
+                .ok_or(format!("header \"{}\" is missing", expression)),
+            ExtractionSource::Cookie => response
+                .cookies
+                .iter()
+                .find(|cookie| cookie.name == expression)
+                .map(|cookie| cookie.value.clone().unwrap_or_default())
+                .ok_or(format!("cookie \"{}\" is missing", expression)),
+            ExtractionSource::Regex => {
+                let regex = Regex::new(expression).map_err(|err| err.to_string())?;
+                let captures = regex
+                    .captures(&response.body)
+                    .ok_or("no match in the body".to_string())?;
+
+                // the first group when there is one, the whole match otherwise
+                let value = captures.get(1).or(captures.get(0));
+                Ok(value.map(|m| m.as_str().to_string()).unwrap_or_default())
+            }
+        }
+    }
+}
+
+/// Applies the extractions to the env, rules without a variable name are skipped.
+pub fn apply_all(
+    extractions: &[Extraction],
+    response: &FalconResponse,
+    env: &mut Env,
+) -> Vec<ExtractionResult> {
+    extractions
+        .iter()
+        .filter(|extraction| !extraction.variable.trim().is_empty())
+        .map(|extraction| {
+            let variable = extraction.variable.trim().to_string();
+            let value = extraction.extract(response);
+
+            if let Ok(value) = &value {
+                env.set_variable(variable.clone(), value.clone());
+            }
+
+            ExtractionResult { variable, value }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::utils::request::FalconCookie;
+
+    #[test]
+    fn test_extracts_into_env() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+
+        let response = FalconResponse {
+            status_code: StatusCode::OK,
+            body: r#"{"token": "abc", "user": {"id": 7}, "link": "/next?page=3"}"#.into(),
+            headers,
+            cookies: vec![FalconCookie {
+                name: "session".into(),
+                value: Some("s1".into()),
+                http_only: true,
+                expires: None,
+            }],
+            duration: Duration::from_millis(10).into(),
+            size_kb: 1.0,
+        };
+
+        let mut env = Env {
+            items: vec![("TOKEN".into(), "old".into()), ("".into(), "".into())],
+            ..Default::default()
+        };
+
+        let results = apply_all(
+            &[
+                Extraction::new(ExtractionSource::JsonPath, "$.token", "TOKEN"),
+                Extraction::new(ExtractionSource::JsonPath, "user.id", "USER_ID"),
+                Extraction::new(ExtractionSource::Header, "X-Request-Id", "REQUEST_ID"),
+                Extraction::new(ExtractionSource::Cookie, "session", "SESSION"),
+                Extraction::new(ExtractionSource::Regex, r"page=(\d+)", "PAGE"),
+                Extraction::new(ExtractionSource::JsonPath, "$.missing", "MISSING"),
+                Extraction::new(ExtractionSource::JsonPath, "$.token", " "),
+            ],
+            &response,
+            &mut env,
+        );
+
+        assert_eq!(results.len(), 6);
+        assert_eq!(
+            results[5].to_string(),
+            "MISSING not set, nothing found at $.missing"
+        );
+        assert_eq!(
+            env.items,
+            vec![
+                ("TOKEN".into(), "abc".into()),
+                ("USER_ID".into(), "7".into()),
+                ("REQUEST_ID".into(), "req-1".into()),
+                ("SESSION".into(), "s1".into()),
+                ("PAGE".into(), "3".into()),
+                ("".into(), "".into()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt::Display;
+
+use serde_json::Value;
+
+use crate::utils::db::env::Env;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonBodyError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl From<serde_json::Error> for JsonBodyError {
+    fn from(err: serde_json::Error) -> Self {
+        let message = err.to_string();
+        let message = match message.rsplit_once(" at line ") {
+            Some((msg, _)) => msg.to_string(),
+            None => message,
+        };
+
+        Self {
+            line: err.line(),
+            column: err.column(),
+            message,
+        }
+    }
+}
+
+impl Display for JsonBodyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Validates the body, `{{VAR}}` placeholders outside of strings are accepted as values.
+pub fn validate(body: &str) -> Result<(), JsonBodyError> {
+    if body.trim().is_empty() {
+        return Ok(());
+    }
+
+    let (masked, _) = mask_placeholders(body, |_, placeholder| {
+        format!("\"{}\"", "_".repeat(placeholder.len() - 2))
+    });
+
+    serde_json::from_str::<Value>(&masked)?;
+    Ok(())
+}
+
+pub fn prettify(body: &str) -> Result<String, JsonBodyError> {
+    reformat(body, serde_json::to_string_pretty)
+}
+
+pub fn minify(body: &str) -> Result<String, JsonBodyError> {
+    reformat(body, serde_json::to_string)
+}
+
+/// Returns a warning when substituting the env variables turns the body into invalid JSON.
+pub fn placeholder_warning(body: &str, env: &Env) -> Option<String> {
+    if validate(body).is_err() || !body.contains("{{") {
+        return None;
+    }
+
+    let replaced = env.replace_variables(body);
+
+    match serde_json::from_str::<Value>(&replaced) {
+        Ok(_) => None,
+        Err(err) => Some(format!(
+            "Body becomes invalid JSON after replacing variables ({}), {}",
+            env.name,
+            JsonBodyError::from(err)
+        )),
+    }
+}
+
+fn reformat(
+    body: &str,
+    format: impl Fn(&Value) -> serde_json::Result<String>,
+) -> Result<String, JsonBodyError> {
+    if body.trim().is_empty() {
+        return Ok(body.to_string());
+    }
+
+    let (masked, placeholders) = mask_placeholders(body, |index, _| {
+        format!("\"__FALCON_PLACEHOLDER_{}__\"", index)
+    });
+
+    let value: Value = serde_json::from_str(&masked)?;
+    let mut formatted = format(&value)?;
+
+    for (index, placeholder) in placeholders.iter().enumerate() {
+        formatted = formatted.replace(
+            &format!("\"__FALCON_PLACEHOLDER_{}__\"", index),
+            placeholder,
+        );
+    }
+
+    Ok(formatted)
+}
+
+/// Replaces every `{{...}}` placeholder found outside of JSON strings.
+fn mask_placeholders(
+    body: &str,
+    replacement: impl Fn(usize, &str) -> String,
+) -> (String, Vec<String>) {
+    let mut masked = String::with_capacity(body.len());
+    let mut placeholders = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut rest = body;
+
+    while let Some(c) = rest.chars().next() {
+        if !in_string && rest.starts_with("{{") {
+            if let Some(end) = rest.find("}}") {
+                let placeholder = &rest[..end + 2];
+                masked.push_str(&replacement(placeholders.len(), placeholder));
+                placeholders.push(placeholder.to_string());
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        }
+
+        masked.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    (masked, placeholders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(items: Vec<(&str, &str)>) -> Env {
+        Env {
+            items: items
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_reports_position() {
+        assert!(validate("").is_ok());
+        assert!(validate(r#"{"id": {{USER_ID}}, "name": "{{NAME}}"}"#).is_ok());
+
+        let err = validate("{\n  \"id\": 1,\n  \"name\" \"x\"\n}").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 10);
+        assert_eq!(err.message, "expected `:`");
+    }
+
+    #[test]
+    fn test_prettify_and_minify_keep_placeholders() {
+        let body = r#"{"id":{{USER_ID}},"tags":["{{TAG}}"]}"#;
+
+        assert_eq!(
+            prettify(body).unwrap(),
+            "{\n  \"id\": {{USER_ID}},\n  \"tags\": [\n    \"{{TAG}}\"\n  ]\n}"
+        );
+        assert_eq!(minify(&prettify(body).unwrap()).unwrap(), body);
+        assert!(prettify("{").is_err());
+    }
+
+    #[test]
+    fn test_placeholder_warning() {
+        let body = r#"{"id": {{USER_ID}}}"#;
+
+        assert!(placeholder_warning(body, &env(vec![("USER_ID", "42")])).is_none());
+        assert!(placeholder_warning(body, &env(vec![("USER_ID", "abc")])).is_some());
+        assert!(placeholder_warning(body, &env(vec![])).is_some());
+        assert!(placeholder_warning(r#"{"id": "{{USER_ID}}"}"#, &env(vec![])).is_none());
+    }
+}
@@ -0,0 +1,293 @@
+use std::time::Instant;
+
+use base64::Engine;
+use reqwest::header::{self, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+
+use super::http_method::HttpMethod;
+use super::{FalconAuthorization, FalconCookie, FalconResponse, FlBody, PendingRequest};
+
+/// A request with every variable, query, cookie and authorization applied,
+/// exactly as it goes over the wire.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct ResolvedRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl ResolvedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The user and password of a `Basic` authorization header.
+    pub fn basic_auth(&self) -> Option<(String, String)> {
+        self.header(header::AUTHORIZATION.as_str())
+            .and_then(|value| value.split_once(' '))
+            .and_then(|(scheme, token)| basic_credentials(scheme, token))
+    }
+
+    pub async fn send(&self) -> anyhow::Result<FalconResponse> {
+        self.send_with(&Client::new()).await
+    }
+
+    /// Builds the request for the client without sending it.
+    pub fn build(&self, client: &Client) -> anyhow::Result<RequestBuilder> {
+        let url = url::Url::parse(&self.url)?;
+        let mut headers = HeaderMap::new();
+
+        for (key, value) in self.headers.iter() {
+            headers.append(
+                HeaderName::from_bytes(key.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+
+        Ok(client
+            .request(self.method.0.clone(), url)
+            .headers(headers)
+            .body(self.body.clone()))
+    }
+
+    /// Sends the request with the given client, reusing its connection pool.
+    pub async fn send_with(&self, client: &Client) -> anyhow::Result<FalconResponse> {
+        let request = self.build(client)?;
+
+        // on stderr so the output of the command line stays parseable
+        eprintln!(
+            "{:<10}[FALCON]: ({}) {}",
+            "INFO",
+            self.method.0.clone(),
+            self.url
+        );
+
+        // Start timing the request
+        let start = Instant::now();
+
+        // Send a request
+        let res = request.send().await?;
+
+        // Calculate the duration
+        let duration = start.elapsed();
+
+        // Get the status, body, headers, and cookies
+        let status_code = res.status();
+        let headers = res.headers().clone();
+
+        let cookies: Vec<FalconCookie> = res
+            .cookies()
+            .map(|c| FalconCookie {
+                name: c.name().to_string(),
+                http_only: c.http_only(),
+                value: Some(c.value().to_string()),
+                expires: c.expires(),
+            })
+            .collect();
+
+        let body = res.text().await.unwrap_or_default();
+
+        // Calculate response size in kilobytes
+        let size_kb = (body.len() as f64 / 1024.0).ceil();
+
+        Ok(FalconResponse {
+            body,
+            duration: duration.into(),
+            headers,
+            size_kb,
+            status_code,
+            cookies,
+        })
+    }
+}
+
+impl From<&ResolvedRequest> for PendingRequest {
+    fn from(resolved: &ResolvedRequest) -> Self {
+        let mut request = PendingRequest {
+            method: resolved.method.clone(),
+            url: resolved.url.clone(),
+            headers: vec![],
+            cookies: vec![],
+            queries: vec![],
+            body: FlBody::ApplicationJson(resolved.body.clone()),
+            ..Default::default()
+        };
+
+        if let Ok(mut url) = url::Url::parse(&resolved.url) {
+            request.queries = url
+                .query_pairs()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+
+            url.set_query(None);
+            request.url = url.to_string();
+        }
+
+        for (name, value) in resolved.headers.iter() {
+            let lower = name.to_lowercase();
+
+            // sending sets JSON on its own, any other type of the body is kept
+            if lower == header::CONTENT_TYPE.as_str() && value.trim() == "application/json" {
+                continue;
+            } else if lower == header::COOKIE.as_str() {
+                request
+                    .cookies
+                    .extend(value.split(';').filter_map(|cookie| {
+                        cookie
+                            .trim()
+                            .split_once('=')
+                            .map(|(name, value)| (name.to_string(), value.to_string()))
+                    }));
+            } else if let Some((prefix, token)) = value
+                .split_once(' ')
+                .filter(|_| lower == header::AUTHORIZATION.as_str())
+            {
+                request.authorization = match basic_credentials(prefix, token) {
+                    Some((username, password)) => FalconAuthorization::Basic { username, password },
+                    None => FalconAuthorization::Bearer {
+                        prefix: prefix.to_string(),
+                        token: token.to_string(),
+                    },
+                };
+            } else {
+                request.headers.push((name.clone(), value.clone()));
+            }
+        }
+
+        // keep an empty row for editing, same as new requests
+        for items in [
+            &mut request.headers,
+            &mut request.cookies,
+            &mut request.queries,
+        ] {
+            items.push(("".to_string(), "".to_string()));
+        }
+
+        request
+    }
+}
+
+/// The user and password of a `Basic` authorization header.
+fn basic_credentials(scheme: &str, token: &str) -> Option<(String, String)> {
+    if !scheme.eq_ignore_ascii_case("basic") {
+        return None;
+    }
+
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(token.trim())
+        .ok()?;
+    let (username, password) = String::from_utf8(decoded).ok()?.split_once(':').map(
+        |(username, password)| (username.to_string(), password.to_string()),
+    )?;
+
+    Some((username, password))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::db::env::Env;
+
+    #[test]
+    fn test_resolves_and_converts_back() {
+        let env = Env {
+            items: vec![("TOKEN".into(), "abc".into()), ("ID".into(), "7".into())],
+            ..Default::default()
+        };
+
+        let pending = PendingRequest {
+            url: "https://api.test/users/{{ID}}".into(),
+            queries: vec![("page".into(), "2".into())],
+            cookies: vec![("session".into(), "{{TOKEN}}".into())],
+            authorization: FalconAuthorization::Bearer {
+                prefix: "Bearer".into(),
+                token: "{{TOKEN}}".into(),
+            },
+            body: FlBody::ApplicationJson(r#"{"id": {{ID}}}"#.into()),
+            ..Default::default()
+        };
+
+        let resolved = pending.resolve(&env, "").unwrap();
+
+        assert_eq!(resolved.url, "https://api.test/users/7?page=2");
+        assert_eq!(resolved.header("authorization"), Some("Bearer abc"));
+        assert_eq!(resolved.header("cookie"), Some("session=abc"));
+        assert_eq!(resolved.body, r#"{"id": 7}"#);
+
+        let request = PendingRequest::from(&resolved);
+
+        assert_eq!(request.url, "https://api.test/users/7");
+        assert_eq!(request.queries[0], ("page".into(), "2".into()));
+        assert_eq!(request.cookies[0], ("session".into(), "abc".into()));
+        assert_eq!(request.headers.len(), 1);
+    }
+
+    #[test]
+    fn test_user_headers_win_over_automatic_ones() {
+        let pending = PendingRequest {
+            url: "https://api.test".into(),
+            headers: vec![
+                ("content-type".into(), "application/vnd.api+json".into()),
+                ("Authorization".into(), "Token mine".into()),
+            ],
+            authorization: FalconAuthorization::Bearer {
+                prefix: "Bearer".into(),
+                token: "theirs".into(),
+            },
+            body: FlBody::ApplicationJson(r#"{"id": 7}"#.into()),
+            ..Default::default()
+        };
+
+        let resolved = pending.resolve(&Env::default(), "").unwrap();
+        let content_types: Vec<_> = resolved
+            .headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+            .collect();
+        assert_eq!(content_types.len(), 1);
+        assert_eq!(
+            resolved.header("content-type"),
+            Some("application/vnd.api+json")
+        );
+        assert_eq!(resolved.header("authorization"), Some("Token mine"));
+
+        let built = resolved.build(&Client::new()).unwrap().build().unwrap();
+        assert_eq!(built.headers().get_all(header::CONTENT_TYPE).iter().count(), 1);
+        assert_eq!(built.headers().get_all(header::AUTHORIZATION).iter().count(), 1);
+    }
+
+    #[test]
+    fn test_basic_authorization() {
+        let env = Env {
+            items: vec![("PASSWORD".into(), "secret".into())],
+            ..Default::default()
+        };
+
+        let pending = PendingRequest {
+            url: "https://api.test".into(),
+            authorization: FalconAuthorization::Basic {
+                username: "ada".into(),
+                password: "{{PASSWORD}}".into(),
+            },
+            ..Default::default()
+        };
+
+        let resolved = pending.resolve(&env, "").unwrap();
+        assert_eq!(
+            resolved.header("authorization"),
+            Some("Basic YWRhOnNlY3JldA==")
+        );
+
+        match PendingRequest::from(&resolved).authorization {
+            FalconAuthorization::Basic { username, password } => {
+                assert_eq!((username.as_str(), password.as_str()), ("ada", "secret"));
+            }
+            auth => panic!("expected basic authorization, got {:?}", auth),
+        }
+    }
+}
@@ -0,0 +1,654 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::db::env::Env;
+use super::db::project::Project;
+use super::request::assertion::{evaluate_all, AssertionResult};
+use super::request::extraction;
+use super::request::{FalconResponse, PendingRequest, ResolvedRequest};
+use super::script::{self, ScriptOutput};
+
+pub mod data_file;
+
+use data_file::DataFile;
+
+/// Which requests of the project are run.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub enum RunScope {
+    #[default]
+    Project,
+    Folder(String),
+}
+
+impl Display for RunScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunScope::Project => write!(f, "Whole project"),
+            RunScope::Folder(folder) => write!(f, "Folder: {}", folder),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RunnerOptions {
+    pub scope: RunScope,
+    pub delay_ms: u64,
+    pub iterations: u32,
+    pub stop_on_failure: bool,
+}
+
+impl Default for RunnerOptions {
+    fn default() -> Self {
+        Self {
+            scope: RunScope::Project,
+            delay_ms: 0,
+            iterations: 1,
+            stop_on_failure: false,
+        }
+    }
+}
+
+/// The project scripts, run around the scripts of every request.
+#[derive(Debug, Default, Clone)]
+pub struct ProjectScripts {
+    pub pre_request: String,
+    pub post_response: String,
+}
+
+impl From<&Project> for ProjectScripts {
+    fn from(project: &Project) -> Self {
+        Self {
+            pre_request: project.pre_request_script.clone(),
+            post_response: project.post_response_script.clone(),
+        }
+    }
+}
+
+/// A request sent through the whole pipeline: pre-request scripts, send,
+/// assertions, extractions and post-response scripts.
+#[derive(Debug, Clone)]
+pub struct Execution {
+    pub request: Option<ResolvedRequest>,
+    pub response: Result<FalconResponse, String>,
+    pub tests: Vec<AssertionResult>,
+    pub output: ScriptOutput,
+}
+
+pub async fn execute(
+    pending: &PendingRequest,
+    scripts: &ProjectScripts,
+    env: &mut Env,
+    base_url: &str,
+    client: &Client,
+) -> Execution {
+    let mut output = ScriptOutput::default();
+    let prepared = script::prepare_request(
+        pending,
+        &[&scripts.pre_request, &pending.pre_request_script],
+        env,
+        base_url,
+        &mut output,
+    );
+
+    let request = match prepared {
+        Ok(request) => request,
+        Err(err) => {
+            return Execution {
+                request: None,
+                response: Err(err),
+                tests: vec![],
+                output,
+            }
+        }
+    };
+
+    let response = request
+        .send_with(client)
+        .await
+        .map_err(|err| err.to_string());
+    let mut tests = vec![];
+
+    if let Ok(res) = &response {
+        tests = evaluate_all(&pending.assertions, res);
+
+        if res.status_code.is_success() {
+            extraction::apply_all(&pending.extractions, res, env);
+        }
+
+        script::run_post_response_scripts(
+            &[&scripts.post_response, &pending.post_response_script],
+            &request,
+            res,
+            env,
+            &mut output,
+        );
+        tests.append(&mut output.tests);
+    }
+
+    Execution {
+        request: Some(request),
+        response,
+        tests,
+        output,
+    }
+}
+
+/// A request of the run, `iteration` starts at 1.
+/// `variables` is the data file row of the iteration.
+#[derive(Debug, Clone)]
+pub struct RunStep {
+    pub iteration: u32,
+    pub folder: String,
+    pub request: PendingRequest,
+    pub variables: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RunResult {
+    pub iteration: u32,
+    pub folder: String,
+    pub request_id: Uuid,
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub status_code: Option<u16>,
+    pub duration_ms: Option<u64>,
+    pub error: Option<String>,
+    pub tests: Vec<AssertionResult>,
+    #[serde(default)]
+    pub data: Vec<(String, String)>,
+}
+
+impl RunResult {
+    pub fn new(step: &RunStep, execution: &Execution) -> Self {
+        let (status_code, duration_ms, error) = match &execution.response {
+            Ok(res) => (
+                Some(res.status_code.as_u16()),
+                Some(res.duration.as_duration().as_millis() as u64),
+                None,
+            ),
+            Err(err) => (None, None, Some(err.clone())),
+        };
+
+        Self {
+            iteration: step.iteration,
+            folder: step.folder.clone(),
+            request_id: step.request.id,
+            name: step
+                .request
+                .name
+                .clone()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or(step.request.url.clone()),
+            method: step.request.method.0.to_string(),
+            url: execution
+                .request
+                .as_ref()
+                .map(|request| request.url.clone())
+                .unwrap_or(step.request.url.clone()),
+            status_code,
+            duration_ms,
+            error,
+            tests: execution.tests.clone(),
+            data: step.variables.clone(),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.tests.iter().all(|test| test.passed)
+    }
+
+    pub fn passed_tests(&self) -> usize {
+        self.tests.iter().filter(|test| test.passed).count()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RunSummary {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    /// Requests not sent because the run was stopped.
+    pub skipped: usize,
+    pub tests_passed: usize,
+    pub tests_failed: usize,
+    pub duration_ms: u64,
+}
+
+impl Display for RunSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} requests, {} passed, {} failed, {} skipped. Tests: {} passed, {} failed. Total time: {} ms",
+            self.total,
+            self.passed,
+            self.failed,
+            self.skipped,
+            self.tests_passed,
+            self.tests_failed,
+            self.duration_ms
+        )
+    }
+}
+
+/// Outcome of every request sent in one iteration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IterationSummary {
+    pub iteration: u32,
+    pub data: Vec<(String, String)>,
+    pub passed: usize,
+    pub failed: usize,
+}
+
+impl Display for IterationSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Iteration {}", self.iteration)?;
+
+        if !self.data.is_empty() {
+            let data: Vec<String> = self
+                .data
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            write!(f, " ({})", data.join(", "))?;
+        }
+
+        write!(f, ": {} passed, {} failed", self.passed, self.failed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RunReport {
+    pub project: String,
+    pub env: Option<String>,
+    #[serde(default)]
+    pub data_file: Option<String>,
+    pub options: RunnerOptions,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub summary: RunSummary,
+    pub results: Vec<RunResult>,
+}
+
+impl RunReport {
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|err| err.to_string())
+    }
+}
+
+/// State of a running collection, requests are taken one by one with `next_step`.
+#[derive(Debug, Clone)]
+pub struct CollectionRun {
+    pub project: String,
+    pub options: RunnerOptions,
+    pub env: Env,
+    pub env_name: Option<String>,
+    pub data_file: Option<String>,
+    pub base_url: String,
+    pub scripts: ProjectScripts,
+    pub results: Vec<RunResult>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    queue: VecDeque<RunStep>,
+    total_steps: usize,
+}
+
+impl CollectionRun {
+    /// With a data file there is one iteration per row and `options.iterations` is ignored.
+    pub fn new(
+        project: &Project,
+        env: Option<Env>,
+        base_url: String,
+        options: RunnerOptions,
+        data: Option<&DataFile>,
+    ) -> Self {
+        let folder = match &options.scope {
+            RunScope::Project => None,
+            RunScope::Folder(folder) => Some(folder.as_str()),
+        };
+        let requests = project.ordered_requests(folder);
+
+        let rows: Vec<Vec<(String, String)>> = match data {
+            Some(data) => data.rows.clone(),
+            None => vec![vec![]; options.iterations.max(1) as usize],
+        };
+
+        let queue: VecDeque<RunStep> = rows
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, variables)| {
+                requests.iter().map(move |(folder, request)| RunStep {
+                    iteration: index as u32 + 1,
+                    folder: folder.to_string(),
+                    request: (*request).clone(),
+                    variables: variables.clone(),
+                })
+            })
+            .collect();
+
+        Self {
+            project: project.name.clone(),
+            env_name: env.as_ref().map(|env| env.name.clone()),
+            data_file: data.map(|data| data.path.clone()),
+            env: env.unwrap_or_default(),
+            base_url,
+            scripts: ProjectScripts::from(project),
+            results: vec![],
+            started_at: Utc::now(),
+            finished_at: None,
+            total_steps: queue.len(),
+            queue,
+            options,
+        }
+    }
+
+    /// The next request to send, `None` once the run is over.
+    pub fn next_step(&mut self) -> Option<RunStep> {
+        let step = self.queue.pop_front();
+
+        if step.is_none() && self.finished_at.is_none() {
+            self.finished_at = Some(Utc::now());
+        }
+
+        step
+    }
+
+    /// Delay to wait before sending the next request.
+    pub fn delay(&self) -> Duration {
+        match self.results.is_empty() {
+            true => Duration::ZERO,
+            false => Duration::from_millis(self.options.delay_ms),
+        }
+    }
+
+    /// Stores the result and the env changed by the request.
+    pub fn record(&mut self, result: RunResult, env: Env) {
+        if self.options.stop_on_failure && !result.passed() {
+            self.stop();
+        }
+
+        self.results.push(result);
+        self.env = env;
+    }
+
+    pub fn stop(&mut self) {
+        self.queue.clear();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished_at.is_some()
+    }
+
+    pub fn total_steps(&self) -> usize {
+        self.total_steps
+    }
+
+    pub fn summary(&self) -> RunSummary {
+        let passed = self.results.iter().filter(|result| result.passed()).count();
+        let tests_passed = self.results.iter().map(RunResult::passed_tests).sum();
+        let tests_total: usize = self.results.iter().map(|result| result.tests.len()).sum();
+
+        RunSummary {
+            total: self.results.len(),
+            passed,
+            failed: self.results.len() - passed,
+            skipped: self.total_steps - self.results.len() - self.queue.len(),
+            tests_passed,
+            tests_failed: tests_total - tests_passed,
+            duration_ms: self
+                .results
+                .iter()
+                .filter_map(|result| result.duration_ms)
+                .sum(),
+        }
+    }
+
+    /// Results grouped by iteration, in run order.
+    pub fn iterations(&self) -> Vec<IterationSummary> {
+        let mut iterations: Vec<IterationSummary> = vec![];
+
+        for result in self.results.iter() {
+            if iterations.last().map(|summary| summary.iteration) != Some(result.iteration) {
+                iterations.push(IterationSummary {
+                    iteration: result.iteration,
+                    data: result.data.clone(),
+                    passed: 0,
+                    failed: 0,
+                });
+            }
+
+            if let Some(summary) = iterations.last_mut() {
+                match result.passed() {
+                    true => summary.passed += 1,
+                    false => summary.failed += 1,
+                }
+            }
+        }
+
+        iterations
+    }
+
+    pub fn report(&self) -> RunReport {
+        RunReport {
+            project: self.project.clone(),
+            env: self.env_name.clone(),
+            data_file: self.data_file.clone(),
+            options: self.options.clone(),
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+            summary: self.summary(),
+            results: self.results.clone(),
+        }
+    }
+}
+
+/// Waits for the delay, then sends the step, the updated env is returned with the result.
+///
+/// The data row of the step is only visible to this request, the returned env
+/// keeps the variables changed by scripts and extractions but not the row itself.
+pub async fn run_step(
+    step: RunStep,
+    env: Env,
+    base_url: String,
+    scripts: ProjectScripts,
+    delay: Duration,
+) -> (RunResult, Env, ScriptOutput) {
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+
+    let mut iteration_env = env.clone();
+    for (key, value) in step.variables.iter() {
+        iteration_env.set_variable(key.clone(), value.clone());
+    }
+
+    let execution = execute(
+        &step.request,
+        &scripts,
+        &mut iteration_env,
+        &base_url,
+        &Client::new(),
+    )
+    .await;
+
+    (
+        RunResult::new(&step, &execution),
+        without_row(iteration_env, &env, &step.variables),
+        execution.output,
+    )
+}
+
+/// Restores the variables shadowed by the data row, unless something else changed them.
+fn without_row(mut iteration_env: Env, env: &Env, row: &[(String, String)]) -> Env {
+    for (key, value) in row {
+        if iteration_env.variable(key) != Some(value.as_str()) {
+            continue;
+        }
+
+        match env.variable(key) {
+            Some(original) => iteration_env.set_variable(key.clone(), original),
+            None => iteration_env.items.retain(|(name, _)| name != key),
+        }
+    }
+
+    iteration_env
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::utils::request::assertion::AssertionResult;
+
+    fn project() -> Project {
+        let request = |name: &str| PendingRequest {
+            name: Some(name.into()),
+            ..Default::default()
+        };
+
+        let mut requests = HashMap::new();
+        requests.insert(
+            "users".to_string(),
+            vec![request("list"), request("create")],
+        );
+        requests.insert("root".to_string(), vec![request("login")]);
+        requests.insert("Admin".to_string(), vec![request("stats")]);
+
+        Project {
+            requests,
+            ..Default::default()
+        }
+    }
+
+    fn result(step: &RunStep, passed: bool) -> RunResult {
+        RunResult {
+            iteration: step.iteration,
+            folder: step.folder.clone(),
+            request_id: step.request.id,
+            name: step.request.name.clone().unwrap_or_default(),
+            method: "GET".into(),
+            url: step.request.url.clone(),
+            status_code: Some(200),
+            duration_ms: Some(10),
+            error: None,
+            tests: vec![AssertionResult::new(
+                "status",
+                if passed {
+                    Ok(())
+                } else {
+                    Err("got 500".into())
+                },
+            )],
+            data: step.variables.clone(),
+        }
+    }
+
+    #[test]
+    fn test_runs_in_order_for_every_iteration() {
+        let options = RunnerOptions {
+            iterations: 2,
+            ..Default::default()
+        };
+        let mut run = CollectionRun::new(&project(), None, "".into(), options, None);
+        let mut names = vec![];
+
+        while let Some(step) = run.next_step() {
+            names.push(format!(
+                "{}:{}",
+                step.iteration,
+                step.request.name.clone().unwrap()
+            ));
+            run.record(result(&step, true), Env::default());
+        }
+
+        assert_eq!(
+            names,
+            vec![
+                "1:login", "1:stats", "1:list", "1:create", "2:login", "2:stats", "2:list",
+                "2:create"
+            ]
+        );
+        assert!(run.is_finished());
+        assert_eq!(run.summary().passed, 8);
+    }
+
+    #[test]
+    fn test_stop_on_failure_skips_the_rest() {
+        let options = RunnerOptions {
+            scope: RunScope::Folder("users".into()),
+            stop_on_failure: true,
+            ..Default::default()
+        };
+        let mut run = CollectionRun::new(&project(), None, "".into(), options, None);
+
+        let step = run.next_step().unwrap();
+        assert_eq!(step.request.name.as_deref(), Some("list"));
+        run.record(result(&step, false), Env::default());
+
+        assert!(run.next_step().is_none());
+
+        let summary = run.summary();
+        assert_eq!((summary.failed, summary.skipped), (1, 1));
+        assert_eq!((summary.tests_passed, summary.tests_failed), (0, 1));
+
+        let json = run.report().to_json().unwrap();
+        let report: RunReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(report.results[0].tests[0].message, "got 500");
+    }
+
+    #[test]
+    fn test_data_rows_drive_iterations() {
+        let data = DataFile::from_csv("ID,TOKEN\n1,a\n2,b\n").unwrap();
+        let options = RunnerOptions {
+            scope: RunScope::Folder("root".into()),
+            iterations: 5,
+            ..Default::default()
+        };
+        let mut run = CollectionRun::new(&project(), None, "".into(), options, Some(&data));
+
+        while let Some(step) = run.next_step() {
+            run.record(result(&step, step.iteration == 1), Env::default());
+        }
+
+        let iterations: Vec<String> = run.iterations().iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            iterations,
+            vec![
+                "Iteration 1 (ID=1, TOKEN=a): 1 passed, 0 failed",
+                "Iteration 2 (ID=2, TOKEN=b): 0 passed, 1 failed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_data_row_does_not_leak_into_env() {
+        let env = Env {
+            items: vec![("TOKEN".into(), "kept".into()), ("".into(), "".into())],
+            ..Default::default()
+        };
+        let row = vec![
+            ("TOKEN".into(), "row".into()),
+            ("ID".into(), "1".into()),
+            ("USER".into(), "ada".into()),
+        ];
+
+        let mut iteration_env = env.clone();
+        for (key, value) in row.iter() {
+            iteration_env.set_variable(key, value);
+        }
+        // changed by a script during the iteration
+        iteration_env.set_variable("USER", "grace");
+
+        let env = without_row(iteration_env, &env, &row);
+        assert_eq!(env.variable("TOKEN"), Some("kept"));
+        assert_eq!(env.variable("ID"), None);
+        assert_eq!(env.variable("USER"), Some("grace"));
+    }
+}
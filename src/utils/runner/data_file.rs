@@ -0,0 +1,167 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Rows of a CSV or JSON file, every row is one iteration of a run.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DataFile {
+    pub path: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<(String, String)>>,
+}
+
+impl DataFile {
+    /// Loads a `.json` file as JSON, anything else as CSV.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read {}, cause {}", path.display(), err))?;
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+        let mut data = if is_json {
+            Self::from_json(&contents)?
+        } else {
+            Self::from_csv(&contents)?
+        };
+        data.path = path.display().to_string();
+
+        Ok(data)
+    }
+
+    /// The first line holds the column names.
+    pub fn from_csv(contents: &str) -> Result<Self, String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(contents.as_bytes());
+
+        let columns: Vec<String> = reader
+            .headers()
+            .map_err(|err| err.to_string())?
+            .iter()
+            .map(String::from)
+            .collect();
+
+        let mut rows = vec![];
+        for record in reader.records() {
+            let record = record.map_err(|err| err.to_string())?;
+            rows.push(
+                columns
+                    .iter()
+                    .cloned()
+                    .zip(record.iter().map(String::from))
+                    .collect(),
+            );
+        }
+
+        Self::with_rows(columns, rows)
+    }
+
+    /// An array of objects, strings are used as is and other values as JSON.
+    pub fn from_json(contents: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(contents).map_err(|err| err.to_string())?;
+        let Value::Array(items) = value else {
+            return Err("The JSON file has to contain an array of objects".into());
+        };
+
+        let mut columns: Vec<String> = vec![];
+        let mut rows = vec![];
+
+        for (index, item) in items.into_iter().enumerate() {
+            let Value::Object(fields) = item else {
+                return Err(format!("Element {} is not an object", index));
+            };
+
+            let row: Vec<(String, String)> = fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        Value::String(value) => value,
+                        value => value.to_string(),
+                    };
+                    (key, value)
+                })
+                .collect();
+
+            for (key, _) in row.iter() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+            rows.push(row);
+        }
+
+        Self::with_rows(columns, rows)
+    }
+
+    /// A run of a data file without rows sends nothing, which is refused so a broken
+    /// input fails instead of passing.
+    fn with_rows(columns: Vec<String>, rows: Vec<Vec<(String, String)>>) -> Result<Self, String> {
+        if rows.is_empty() {
+            return Err("The data file has no rows".into());
+        }
+
+        Ok(Self {
+            columns,
+            rows,
+            ..Default::default()
+        })
+    }
+
+    pub fn file_name(&self) -> String {
+        Path::new(&self.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(self.path.clone())
+    }
+
+    /// The value of a column in a row, empty when the row doesn't have it.
+    pub fn value(&self, row: usize, column: &str) -> &str {
+        self.rows
+            .get(row)
+            .and_then(|row| row.iter().find(|(key, _)| key == column))
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_csv_with_quotes() {
+        let data = DataFile::from_csv("ID, NAME\n1, Ada\n2,\"Lovelace, Ada\"\n").unwrap();
+
+        assert_eq!(data.columns, vec!["ID", "NAME"]);
+        assert_eq!(data.rows.len(), 2);
+        assert_eq!(data.value(1, "NAME"), "Lovelace, Ada");
+    }
+
+    #[test]
+    fn test_parses_json_array() {
+        let data = DataFile::from_json(r#"[{"ID": 1, "NAME": "Ada"}, {"ID": 2, "ACTIVE": true}]"#)
+            .unwrap();
+
+        assert_eq!(data.columns, vec!["ID", "NAME", "ACTIVE"]);
+        assert_eq!(data.value(0, "ID"), "1");
+        assert_eq!(data.value(1, "NAME"), "");
+        assert_eq!(data.value(1, "ACTIVE"), "true");
+
+        assert!(DataFile::from_json(r#"{"ID": 1}"#).is_err());
+        assert!(DataFile::from_json(r#"[1, 2]"#).is_err());
+    }
+
+    #[test]
+    fn test_refuses_files_without_rows() {
+        for data in [
+            DataFile::from_csv(""),
+            DataFile::from_csv("ID,NAME\n"),
+            DataFile::from_json("[]"),
+        ] {
+            assert_eq!(data.unwrap_err(), "The data file has no rows");
+        }
+    }
+}
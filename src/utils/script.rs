@@ -0,0 +1,541 @@
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope};
+use serde_json::Value;
+
+use super::db::env::Env;
+use super::request::assertion::AssertionResult;
+use super::request::{FalconResponse, PendingRequest, ResolvedRequest};
+
+/// Scripts are stopped once they run longer than this.
+pub const SCRIPT_TIME_LIMIT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ScriptStage {
+    #[default]
+    PreRequest,
+    PostResponse,
+}
+
+impl ScriptStage {
+    pub const ALL: [ScriptStage; 2] = [ScriptStage::PreRequest, ScriptStage::PostResponse];
+}
+
+impl Display for ScriptStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ScriptStage::PreRequest => "Pre-request",
+            ScriptStage::PostResponse => "Post-response",
+        };
+
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ConsoleLevel {
+    #[default]
+    Log,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsoleLine {
+    pub stage: ScriptStage,
+    pub level: ConsoleLevel,
+    pub message: String,
+}
+
+impl Display for ConsoleLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.stage, self.message)
+    }
+}
+
+/// Everything printed and tested by the scripts of a single send.
+#[derive(Debug, Default, Clone)]
+pub struct ScriptOutput {
+    pub console: Vec<ConsoleLine>,
+    pub tests: Vec<AssertionResult>,
+}
+
+impl ScriptOutput {
+    fn log(&mut self, stage: ScriptStage, level: ConsoleLevel, message: impl Into<String>) {
+        self.console.push(ConsoleLine {
+            stage,
+            level,
+            message: message.into(),
+        });
+    }
+
+    fn append(&mut self, mut other: ScriptOutput) {
+        self.console.append(&mut other.console);
+        self.tests.append(&mut other.tests);
+    }
+}
+
+/// The `console` object of the scripts, `console.log("...")`.
+#[derive(Clone)]
+struct ScriptConsole {
+    stage: ScriptStage,
+    output: Arc<Mutex<ScriptOutput>>,
+}
+
+impl ScriptConsole {
+    fn write(&mut self, level: ConsoleLevel, message: Dynamic) {
+        if let Ok(mut output) = self.output.lock() {
+            output.log(self.stage, level, message.to_string());
+        }
+    }
+}
+
+/// The `env` object of the scripts, `env.get("TOKEN")` and `env.set("TOKEN", "...")`.
+#[derive(Clone)]
+struct ScriptEnv(Env);
+
+/// Resolves the request and runs the pre-request scripts on it, in order.
+///
+/// Scripts get the resolved request, so every change to it is sent as is.
+/// Variables set by the scripts are applied to the parts they didn't touch.
+pub fn prepare_request(
+    pending: &PendingRequest,
+    scripts: &[&str],
+    env: &mut Env,
+    base_url: &str,
+    output: &mut ScriptOutput,
+) -> Result<ResolvedRequest, String> {
+    let resolved = pending
+        .resolve(env, base_url)
+        .map_err(|err| err.to_string())?;
+    let scripts: Vec<&str> = scripts
+        .iter()
+        .copied()
+        .filter(|script| !script.trim().is_empty())
+        .collect();
+
+    if scripts.is_empty() {
+        return Ok(resolved);
+    }
+
+    let mut request = resolved.clone();
+    for script in scripts {
+        run_pre_request(script, &mut request, env, output)?;
+    }
+
+    let rebuilt = pending
+        .resolve(env, base_url)
+        .map_err(|err| err.to_string())?;
+
+    let headers = request
+        .headers
+        .into_iter()
+        .map(|(name, value)| {
+            let untouched = resolved.header(&name) == Some(value.as_str());
+
+            match rebuilt.header(&name).filter(|_| untouched) {
+                Some(rebuilt_value) => (name, rebuilt_value.to_string()),
+                None => (name, env.replace_variables(value)),
+            }
+        })
+        .collect();
+
+    Ok(ResolvedRequest {
+        method: if request.method == resolved.method {
+            rebuilt.method
+        } else {
+            request.method
+        },
+        url: if request.url == resolved.url {
+            rebuilt.url
+        } else {
+            env.replace_variables(request.url)
+        },
+        headers,
+        body: if request.body == resolved.body {
+            rebuilt.body
+        } else {
+            env.replace_variables(request.body)
+        },
+    })
+}
+
+/// Runs a pre-request script, `request` and `env` are updated with the script changes.
+pub fn run_pre_request(
+    script: &str,
+    request: &mut ResolvedRequest,
+    env: &mut Env,
+    output: &mut ScriptOutput,
+) -> Result<(), String> {
+    let mut scope = Scope::new();
+    scope.push("request", request_map(request));
+
+    run(
+        ScriptStage::PreRequest,
+        script,
+        scope,
+        env,
+        output,
+        |scope| {
+            if let Some(map) = scope.get_value::<Map>("request") {
+                apply_request_map(request, map);
+            }
+        },
+    )
+}
+
+/// Runs a post-response script, `env` is updated with the variables set by the script.
+pub fn run_post_response(
+    script: &str,
+    request: &ResolvedRequest,
+    response: &FalconResponse,
+    env: &mut Env,
+    output: &mut ScriptOutput,
+) -> Result<(), String> {
+    let mut scope = Scope::new();
+    scope.push_constant("request", request_map(request));
+    scope.push_constant("response", response_map(response));
+
+    run(
+        ScriptStage::PostResponse,
+        script,
+        scope,
+        env,
+        output,
+        |_| {},
+    )
+}
+
+/// Runs every non empty post-response script in order, failing scripts don't stop the others.
+pub fn run_post_response_scripts(
+    scripts: &[&str],
+    request: &ResolvedRequest,
+    response: &FalconResponse,
+    env: &mut Env,
+    output: &mut ScriptOutput,
+) {
+    for script in scripts.iter().filter(|script| !script.trim().is_empty()) {
+        // errors are already written to the console
+        let _ = run_post_response(script, request, response, env, output);
+    }
+}
+
+fn run(
+    stage: ScriptStage,
+    script: &str,
+    mut scope: Scope,
+    env: &mut Env,
+    output: &mut ScriptOutput,
+    read_scope: impl FnOnce(&Scope),
+) -> Result<(), String> {
+    let shared = Arc::new(Mutex::new(ScriptOutput::default()));
+    let engine = engine(stage, shared.clone());
+
+    scope.push(
+        "console",
+        ScriptConsole {
+            stage,
+            output: shared.clone(),
+        },
+    );
+    scope.push("env", ScriptEnv(env.clone()));
+
+    let result = engine.run_with_scope(&mut scope, script);
+    drop(engine);
+
+    if let Ok(mut produced) = shared.lock() {
+        output.append(std::mem::take(&mut *produced));
+    }
+
+    match result {
+        Ok(_) => {
+            if let Some(ScriptEnv(changed)) = scope.get_value::<ScriptEnv>("env") {
+                *env = changed;
+            }
+            read_scope(&scope);
+            Ok(())
+        }
+        Err(err) => {
+            let message = match *err {
+                EvalAltResult::ErrorTerminated(..) => format!(
+                    "Script stopped, it ran longer than {} ms",
+                    SCRIPT_TIME_LIMIT.as_millis()
+                ),
+                err => format!("Script failed, {}", err),
+            };
+            output.log(stage, ConsoleLevel::Error, message.clone());
+            Err(message)
+        }
+    }
+}
+
+/// A sandboxed engine: no modules, no `eval`, limited resources and time.
+fn engine(stage: ScriptStage, output: Arc<Mutex<ScriptOutput>>) -> Engine {
+    let mut engine = Engine::new();
+    let started = Instant::now();
+
+    engine
+        .set_module_resolver(DummyModuleResolver::new())
+        .set_max_expr_depths(64, 32)
+        .set_max_call_levels(32)
+        .set_max_string_size(10 * 1024 * 1024)
+        .set_max_array_size(100_000)
+        .set_max_map_size(100_000)
+        .on_progress(move |_| {
+            (started.elapsed() > SCRIPT_TIME_LIMIT).then(|| Dynamic::from("timeout"))
+        });
+    engine.disable_symbol("eval");
+
+    let print_output = output.clone();
+    engine.on_print(move |message| {
+        if let Ok(mut output) = print_output.lock() {
+            output.log(stage, ConsoleLevel::Log, message);
+        }
+    });
+    let debug_output = output.clone();
+    engine.on_debug(move |message, _, _| {
+        if let Ok(mut output) = debug_output.lock() {
+            output.log(stage, ConsoleLevel::Log, message);
+        }
+    });
+
+    engine
+        .register_type_with_name::<ScriptConsole>("Console")
+        .register_fn("log", |console: &mut ScriptConsole, message: Dynamic| {
+            console.write(ConsoleLevel::Log, message)
+        })
+        .register_fn("info", |console: &mut ScriptConsole, message: Dynamic| {
+            console.write(ConsoleLevel::Log, message)
+        })
+        .register_fn("warn", |console: &mut ScriptConsole, message: Dynamic| {
+            console.write(ConsoleLevel::Warn, message)
+        })
+        .register_fn("error", |console: &mut ScriptConsole, message: Dynamic| {
+            console.write(ConsoleLevel::Error, message)
+        });
+
+    engine
+        .register_type_with_name::<ScriptEnv>("Env")
+        .register_fn("get", |env: &mut ScriptEnv, key: &str| -> Dynamic {
+            env.0
+                .variable(key)
+                .map(|value| Dynamic::from(value.to_string()))
+                .unwrap_or(Dynamic::UNIT)
+        })
+        .register_fn("set", |env: &mut ScriptEnv, key: &str, value: Dynamic| {
+            env.0.set_variable(key, value.to_string())
+        });
+
+    // test results belong to a response
+    if stage == ScriptStage::PostResponse {
+        engine.register_fn("test", move |name: &str, passed: bool| {
+            let result = match passed {
+                true => Ok(()),
+                false => Err("script check failed".to_string()),
+            };
+
+            if let Ok(mut output) = output.lock() {
+                output.tests.push(AssertionResult::new(name, result));
+            }
+        });
+    }
+
+    engine
+        .register_fn(
+            "parse_json",
+            |json: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+                let value: Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+                rhai::serde::to_dynamic(value)
+            },
+        )
+        .register_fn(
+            "to_json",
+            |value: Dynamic| -> Result<String, Box<EvalAltResult>> {
+                serde_json::to_string(&value).map_err(|err| err.to_string().into())
+            },
+        );
+
+    engine
+}
+
+fn request_map(request: &ResolvedRequest) -> Map {
+    let mut headers = Map::new();
+    for (name, value) in request.headers.iter() {
+        headers.insert(name.as_str().into(), value.clone().into());
+    }
+
+    let mut map = Map::new();
+    map.insert("method".into(), request.method.0.to_string().into());
+    map.insert("url".into(), request.url.clone().into());
+    map.insert("headers".into(), headers.into());
+    map.insert("body".into(), request.body.clone().into());
+    map
+}
+
+/// Header order is kept for existing headers, new ones are appended.
+fn apply_request_map(request: &mut ResolvedRequest, mut map: Map) {
+    let text = |value: Option<Dynamic>| value.map(|value| value.to_string());
+
+    if let Some(method) = text(map.remove("method")) {
+        request.method = method.as_str().into();
+    }
+    if let Some(url) = text(map.remove("url")) {
+        request.url = url;
+    }
+    if let Some(body) = text(map.remove("body")) {
+        request.body = body;
+    }
+    if let Some(mut headers) = map
+        .remove("headers")
+        .and_then(|headers| headers.try_cast::<Map>())
+    {
+        let mut updated = vec![];
+        for (name, _) in request.headers.iter() {
+            if let Some(value) = headers.remove(name.as_str()) {
+                updated.push((name.clone(), value.to_string()));
+            }
+        }
+        for (name, value) in headers {
+            updated.push((name.to_string(), value.to_string()));
+        }
+        request.headers = updated;
+    }
+}
+
+fn response_map(response: &FalconResponse) -> Map {
+    let mut headers = Map::new();
+    for (name, value) in response.headers.iter() {
+        headers.insert(
+            name.as_str().into(),
+            String::from_utf8_lossy(value.as_bytes()).to_string().into(),
+        );
+    }
+
+    let mut cookies = Map::new();
+    for cookie in response.cookies.iter() {
+        cookies.insert(
+            cookie.name.as_str().into(),
+            cookie.value.clone().unwrap_or_default().into(),
+        );
+    }
+
+    let json = serde_json::from_str::<Value>(&response.body)
+        .ok()
+        .and_then(|value| rhai::serde::to_dynamic(value).ok())
+        .unwrap_or(Dynamic::UNIT);
+
+    let mut map = Map::new();
+    map.insert(
+        "status".into(),
+        Dynamic::from(response.status_code.as_u16() as rhai::INT),
+    );
+    map.insert("headers".into(), headers.into());
+    map.insert("cookies".into(), cookies.into());
+    map.insert("body".into(), response.body.clone().into());
+    map.insert("json".into(), json);
+    map.insert(
+        "time".into(),
+        Dynamic::from(response.duration.as_duration().as_millis() as rhai::INT),
+    );
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::StatusCode;
+
+    use super::*;
+
+    fn env() -> Env {
+        Env {
+            items: vec![("TOKEN".into(), "old".into()), ("".into(), "".into())],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pre_request_updates_request_and_env() {
+        let pending = PendingRequest {
+            url: "https://api.test/users".into(),
+            headers: vec![("X-Token".into(), "{{TOKEN}}".into())],
+            ..Default::default()
+        };
+        let script = r#"
+            env.set("TOKEN", "new");
+            request.headers["X-Trace"] = "1";
+            console.log("sending " + request.url);
+        "#;
+
+        let mut env = env();
+        let mut output = ScriptOutput::default();
+        let request = prepare_request(&pending, &[script], &mut env, "", &mut output).unwrap();
+
+        assert_eq!(request.header("x-token"), Some("new"));
+        assert_eq!(request.header("x-trace"), Some("1"));
+        assert_eq!(env.variable("TOKEN"), Some("new"));
+        assert_eq!(env.items.last(), Some(&("".into(), "".into())));
+        assert_eq!(
+            output.console[0].to_string(),
+            "[Pre-request] sending https://api.test/users"
+        );
+    }
+
+    #[test]
+    fn test_post_response_sets_env_and_records_tests() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+        let response = FalconResponse {
+            status_code: StatusCode::OK,
+            body: r#"{"token": "abc", "items": [1, 2]}"#.into(),
+            headers,
+            cookies: vec![],
+            duration: Duration::from_millis(30).into(),
+            size_kb: 1.0,
+        };
+        let script = r#"
+            env.set("TOKEN", response.json.token);
+            test("status is 200", response.status == 200);
+            test("has three items", response.json.items.len() == 3);
+        "#;
+
+        let mut env = env();
+        let mut output = ScriptOutput::default();
+        run_post_response(
+            script,
+            &ResolvedRequest::default(),
+            &response,
+            &mut env,
+            &mut output,
+        )
+        .unwrap();
+
+        assert_eq!(env.variable("TOKEN"), Some("abc"));
+        assert!(output.tests[0].passed);
+        assert!(!output.tests[1].passed);
+    }
+
+    #[test]
+    fn test_scripts_are_sandboxed() {
+        let mut env = env();
+        let mut output = ScriptOutput::default();
+        let mut request = ResolvedRequest::default();
+
+        let err = run_pre_request("loop { }", &mut request, &mut env, &mut output).unwrap_err();
+        assert!(err.contains("ran longer than"));
+
+        let err = run_pre_request(
+            r#"import "secrets" as s; env.set("TOKEN", "x");"#,
+            &mut request,
+            &mut env,
+            &mut output,
+        );
+        assert!(err.is_err());
+        assert_eq!(env.variable("TOKEN"), Some("old"));
+        assert_eq!(output.console.len(), 2);
+        assert_eq!(output.console[1].level, ConsoleLevel::Error);
+    }
+}